@@ -331,6 +331,18 @@ function runTextSearch(projectPath, pattern, maxResults = 60) {
     return output || "No text matches found.";
 }
 function getTouchedFiles(projectPath) {
+    const { success, output } = runCtxArgv(["impact", "--json"], projectPath);
+    if (success) {
+        try {
+            const parsed = JSON.parse(output);
+            if (parsed.changed_files) {
+                return parsed.changed_files.map((f) => f.path).sort();
+            }
+        }
+        catch {
+            // fall back to plain git below
+        }
+    }
     const outputs = [
         runGit(projectPath, ["diff", "--name-only"]),
         runGit(projectPath, ["diff", "--name-only", "--cached"]),
@@ -383,6 +383,18 @@ function runTextSearch(projectPath: string, pattern: string, maxResults = 60): s
 }
 
 function getTouchedFiles(projectPath: string): string[] {
+    const { success, output } = runCtxArgv(["impact", "--json"], projectPath);
+    if (success) {
+        try {
+            const parsed = JSON.parse(output) as { changed_files?: Array<{ path: string }> };
+            if (parsed.changed_files) {
+                return parsed.changed_files.map((f) => f.path).sort();
+            }
+        } catch {
+            // fall back to plain git below
+        }
+    }
+
     const outputs = [
         runGit(projectPath, ["diff", "--name-only"]),
         runGit(projectPath, ["diff", "--name-only", "--cached"]),
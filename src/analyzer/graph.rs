@@ -1,7 +1,11 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
+use crate::db::models::{
+    ClosingEdge, CoupledFile, DependencyCycle, DependencyGraph, FileHealth, GraphEdge, GraphNode,
+    ResolvedImport, Symbol, SymbolImpact,
+};
 use crate::db::Database;
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 /// Compute the blast radius of a file: all files that would be affected
 /// if this file changes (transitive dependents)
@@ -28,6 +32,469 @@ pub fn blast_radius(db: &Database, file_id: i64) -> Result<Vec<(i64, String, usi
     Ok(result)
 }
 
+/// Files that historically change together with `path` (co-change confidence
+/// of at least `coupling.min_confidence`) and are not already in `radius`
+pub fn historical_blast_radius(
+    db: &Database,
+    path: &str,
+    radius: &[(i64, String, usize)],
+) -> Result<Vec<CoupledFile>> {
+    let structural: HashSet<&str> = radius.iter().map(|(_, p, _)| p.as_str()).collect();
+    Ok(db
+        .get_coupled_files(path, db.config.coupling.min_confidence)?
+        .into_iter()
+        .filter(|c| !structural.contains(c.path.as_str()))
+        .collect())
+}
+
+/// Compute the blast radius of a set of symbols: callers (transitively, up to
+/// `max_depth`) plus files that import the symbols by name
+pub fn symbol_blast_radius(
+    db: &Database,
+    targets: &[Symbol],
+    max_depth: Option<usize>,
+) -> Result<Vec<SymbolImpact>> {
+    let mut visited: HashSet<i64> = targets.iter().map(|s| s.id).collect();
+    let mut file_level: HashSet<i64> = HashSet::new();
+    let mut queue: VecDeque<(i64, usize)> = targets.iter().map(|s| (s.id, 0)).collect();
+    let mut result = Vec::new();
+
+    // Importers only see the changed symbols themselves, not their transitive
+    // callers; like direct callers they are one level out
+    if max_depth.is_none_or(|max| max >= 1) {
+        for target in targets {
+            for mut impact in db.get_name_importers(target.file_id, &target.name)? {
+                if file_level.insert(impact.file_id) {
+                    impact.depth = 1;
+                    result.push(impact);
+                }
+            }
+        }
+    }
+
+    while let Some((symbol_id, depth)) = queue.pop_front() {
+        if max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+
+        for mut impact in db.get_symbol_callers(symbol_id)? {
+            impact.depth = depth + 1;
+            match impact.symbol_id {
+                Some(caller_id) => {
+                    if visited.insert(caller_id) {
+                        queue.push_back((caller_id, depth + 1));
+                        result.push(impact);
+                    }
+                }
+                // Top-level call site: the file itself is affected
+                None => {
+                    if file_level.insert(impact.file_id) {
+                        result.push(impact);
+                    }
+                }
+            }
+        }
+    }
+
+    result.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.path.cmp(&b.path)));
+    Ok(result)
+}
+
+/// Import cycles in the resolved file graph, largest first
+pub fn dependency_cycles(db: &Database) -> Result<Vec<DependencyCycle>> {
+    Ok(find_cycles(&db.get_import_edges()?))
+}
+
+fn find_cycles(edges: &[(String, String)]) -> Vec<DependencyCycle> {
+    let files: Vec<&str> = edges
+        .iter()
+        .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let ids: HashMap<&str, usize> = files.iter().enumerate().map(|(i, f)| (*f, i)).collect();
+    let mut adjacency = vec![Vec::new(); files.len()];
+    for (from, to) in edges {
+        adjacency[ids[from.as_str()]].push(ids[to.as_str()]);
+    }
+    for targets in &mut adjacency {
+        targets.sort_unstable();
+        targets.dedup();
+    }
+
+    let mut cycles: Vec<DependencyCycle> = strongly_connected_components(&adjacency)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|mut component| {
+            component.sort_unstable();
+            let closing_edges = closing_edges(&adjacency, &component)
+                .into_iter()
+                .map(|(from, to, cycle)| ClosingEdge {
+                    from: files[from].to_string(),
+                    to: files[to].to_string(),
+                    cycle: cycle.into_iter().map(|i| files[i].to_string()).collect(),
+                })
+                .collect();
+            DependencyCycle {
+                files: component
+                    .into_iter()
+                    .map(|i| files[i].to_string())
+                    .collect(),
+                closing_edges,
+            }
+        })
+        .collect();
+    cycles.sort_by(|a, b| {
+        b.files
+            .len()
+            .cmp(&a.files.len())
+            .then(a.files.cmp(&b.files))
+    });
+    cycles
+}
+
+/// Tarjan's algorithm, iterative so deep import chains cannot overflow the stack
+fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; adjacency.len()];
+    let mut lowlink = vec![0; adjacency.len()];
+    let mut on_stack = vec![false; adjacency.len()];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    for root in 0..adjacency.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, position of the next edge to follow)
+        let mut work = vec![(root, 0)];
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(v, edge)) = work.last() {
+            if let Some(&w) = adjacency[v].get(edge) {
+                work.last_mut().unwrap().1 += 1;
+                if index[w] == UNVISITED {
+                    index[w] = next_index;
+                    lowlink[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    work.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[v]);
+            }
+            if lowlink[v] == index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Back edges of a depth-first walk of one component, starting from its first
+/// file: the imports that close each loop, with the loop as `to → … → from → to`
+fn closing_edges(adjacency: &[Vec<usize>], component: &[usize]) -> Vec<(usize, usize, Vec<usize>)> {
+    let members: HashSet<usize> = component.iter().copied().collect();
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut path = vec![component[0]];
+    let mut work = vec![(component[0], 0)];
+    let mut result = Vec::new();
+    visited.insert(component[0]);
+
+    while let Some(&(v, edge)) = work.last() {
+        let Some(&w) = adjacency[v].get(edge) else {
+            work.pop();
+            path.pop();
+            continue;
+        };
+        work.last_mut().unwrap().1 += 1;
+        if !members.contains(&w) {
+            continue;
+        }
+        if let Some(pos) = path.iter().position(|&p| p == w) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(w);
+            result.push((v, w, cycle));
+        } else if visited.insert(w) {
+            path.push(w);
+            work.push((w, 0));
+        }
+    }
+
+    result
+}
+
+/// Import chains from `from` to `to`, shortest first. Without `all` only one
+/// shortest chain; with it every chain of at most `max_hops` imports (and at
+/// least the shortest ones), up to `limit` chains.
+pub fn import_paths(
+    db: &Database,
+    from: &str,
+    to: &str,
+    all: bool,
+    max_hops: usize,
+    limit: usize,
+) -> Result<Vec<Vec<ResolvedImport>>> {
+    let imports = db.get_resolved_imports()?;
+    Ok(if all {
+        find_import_paths(&imports, from, to, max_hops, limit)
+    } else {
+        find_import_paths(&imports, from, to, 0, 1)
+    })
+}
+
+fn find_import_paths(
+    imports: &[ResolvedImport],
+    from: &str,
+    to: &str,
+    max_hops: usize,
+    limit: usize,
+) -> Vec<Vec<ResolvedImport>> {
+    if from == to || limit == 0 {
+        return Vec::new();
+    }
+
+    // One hop per file pair: the first import statement between them
+    let mut adjacency: HashMap<&str, Vec<&ResolvedImport>> = HashMap::new();
+    let mut reverse: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut seen = HashSet::new();
+    for import in imports {
+        if import.path != import.target
+            && seen.insert((import.path.as_str(), import.target.as_str()))
+        {
+            adjacency.entry(&import.path).or_default().push(import);
+            reverse
+                .entry(&import.target)
+                .or_default()
+                .push(&import.path);
+        }
+    }
+    for hops in adjacency.values_mut() {
+        hops.sort_by(|a, b| a.target.cmp(&b.target));
+    }
+
+    // Fewest hops from each file to `to`, used to prune the enumeration
+    let mut distance: HashMap<&str, usize> = HashMap::from([(to, 0)]);
+    let mut queue = VecDeque::from([to]);
+    while let Some(file) = queue.pop_front() {
+        let next = distance[file] + 1;
+        for &importer in reverse.get(file).into_iter().flatten() {
+            if !distance.contains_key(importer) {
+                distance.insert(importer, next);
+                queue.push_back(importer);
+            }
+        }
+    }
+    let Some(&shortest) = distance.get(from) else {
+        return Vec::new();
+    };
+
+    let mut search = PathSearch {
+        adjacency: &adjacency,
+        distance: &distance,
+        to,
+        limit,
+        chain: Vec::new(),
+        on_chain: HashSet::from([from]),
+        paths: Vec::new(),
+    };
+    for length in shortest..=max_hops.max(shortest) {
+        search.extend(from, length);
+        if search.paths.len() >= limit {
+            break;
+        }
+    }
+    search.paths
+}
+
+/// Depth-first enumeration of simple chains with an exact number of hops
+struct PathSearch<'a> {
+    adjacency: &'a HashMap<&'a str, Vec<&'a ResolvedImport>>,
+    distance: &'a HashMap<&'a str, usize>,
+    to: &'a str,
+    limit: usize,
+    chain: Vec<&'a ResolvedImport>,
+    on_chain: HashSet<&'a str>,
+    paths: Vec<Vec<ResolvedImport>>,
+}
+
+impl<'a> PathSearch<'a> {
+    fn extend(&mut self, file: &'a str, remaining: usize) {
+        if file == self.to {
+            if remaining == 0 {
+                self.paths
+                    .push(self.chain.iter().map(|hop| (*hop).clone()).collect());
+            }
+            return;
+        }
+        let Some(hops) = self.adjacency.get(file) else {
+            return;
+        };
+        for &hop in hops {
+            if self.paths.len() >= self.limit {
+                return;
+            }
+            let next = hop.target.as_str();
+            let reachable = self.distance.get(next).is_some_and(|&d| d < remaining);
+            if !reachable || !self.on_chain.insert(next) {
+                continue;
+            }
+            self.chain.push(hop);
+            self.extend(next, remaining - 1);
+            self.chain.pop();
+            self.on_chain.remove(next);
+        }
+    }
+}
+
+/// Which part of the import graph to export, and at what granularity
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    /// File or directory to center the graph on
+    pub root: Option<String>,
+    /// With `root`, the most import hops (in either direction) to follow
+    pub depth: Option<usize>,
+    /// Merge files into their directory, keeping this many leading components
+    pub collapse: Option<usize>,
+}
+
+/// The resolved import graph without `mod` edges, with per-node language,
+/// size and churn. Files outside any import edge are left out unless `root`
+/// selects them.
+pub fn export_graph(db: &Database, options: &GraphOptions) -> Result<DependencyGraph> {
+    build_graph(&db.get_file_health()?, &db.get_import_edges()?, options)
+}
+
+fn build_graph(
+    files: &[FileHealth],
+    edges: &[(String, String)],
+    options: &GraphOptions,
+) -> Result<DependencyGraph> {
+    let mut keep: HashSet<&str> = edges
+        .iter()
+        .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+        .collect();
+
+    if let Some(root) = &options.root {
+        let root = root.trim_end_matches('/');
+        let seeds: Vec<&str> = files
+            .iter()
+            .map(|f| f.path.as_str())
+            .filter(|path| {
+                *path == root
+                    || path
+                        .strip_prefix(root)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .collect();
+        if seeds.is_empty() {
+            bail!("File or directory not found: {}", root);
+        }
+
+        let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (a, b) in edges {
+            neighbors.entry(a).or_default().push(b);
+            neighbors.entry(b).or_default().push(a);
+        }
+        let mut depth_of: HashMap<&str, usize> = seeds.iter().map(|&s| (s, 0)).collect();
+        let mut queue: VecDeque<&str> = seeds.into_iter().collect();
+        while let Some(file) = queue.pop_front() {
+            let depth = depth_of[file];
+            if options.depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            for &next in neighbors.get(file).into_iter().flatten() {
+                if !depth_of.contains_key(next) {
+                    depth_of.insert(next, depth + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        keep = depth_of.into_keys().collect();
+    }
+
+    let node_id = |path: &str| match options.collapse {
+        Some(levels) => collapse_path(path, levels),
+        None => path.to_string(),
+    };
+
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    // Lines of the largest file seen per node, which decides its language
+    let mut largest: HashMap<String, i64> = HashMap::new();
+    for file in files.iter().filter(|f| keep.contains(f.path.as_str())) {
+        let id = node_id(&file.path);
+        let node = nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+            id: id.clone(),
+            language: String::new(),
+            lines: 0,
+            commits: 0,
+            churn: 0.0,
+            files: 0,
+        });
+        node.lines += file.line_count;
+        node.commits += file.commit_count;
+        node.churn = node.churn.max(file.churn_score);
+        node.files += 1;
+        let biggest = largest.entry(id).or_insert(-1);
+        if file.line_count > *biggest {
+            *biggest = file.line_count;
+            node.language = file.language.clone();
+        }
+    }
+
+    let mut weights: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for (a, b) in edges {
+        if !keep.contains(a.as_str()) || !keep.contains(b.as_str()) {
+            continue;
+        }
+        let (from, to) = (node_id(a), node_id(b));
+        // Imports inside a collapsed directory
+        if from != to {
+            *weights.entry((from, to)).or_insert(0) += 1;
+        }
+    }
+
+    Ok(DependencyGraph {
+        nodes: nodes.into_values().collect(),
+        edges: weights
+            .into_iter()
+            .map(|((from, to), weight)| GraphEdge { from, to, weight })
+            .collect(),
+    })
+}
+
+/// `src/db/mod.rs` → `src/db` with two levels, `src` with one; top-level
+/// files stay as they are
+fn collapse_path(path: &str, levels: usize) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    let dirs = parts.len() - 1;
+    if dirs == 0 {
+        return path.to_string();
+    }
+    parts[..levels.clamp(1, dirs)].join("/")
+}
+
 /// Build a map visualization of the dependency tree
 pub fn dependency_tree_display(db: &Database, file_id: i64) -> Result<Vec<String>> {
     let mut lines = Vec::new();
@@ -50,3 +517,280 @@ pub fn dependency_tree_display(db: &Database, file_id: i64) -> Result<Vec<String
 
     Ok(lines)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::modules::ProjectModules;
+    use crate::db::models::SymbolKind;
+
+    fn edges(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_each_cycle_with_its_closing_edges() {
+        let cycles = find_cycles(&edges(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "b"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "a"),
+        ]));
+
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].files, vec!["a", "b", "c"]);
+        let closing: Vec<_> = cycles[0]
+            .closing_edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(closing, vec![("c", "a"), ("c", "b")]);
+        assert_eq!(cycles[0].closing_edges[0].cycle, vec!["a", "b", "c", "a"]);
+        assert_eq!(cycles[0].closing_edges[1].cycle, vec!["b", "c", "b"]);
+
+        assert_eq!(cycles[1].files, vec!["d", "e"]);
+    }
+
+    #[test]
+    fn acyclic_graph_has_no_cycles() {
+        assert!(find_cycles(&edges(&[("a", "b"), ("b", "c"), ("a", "c")])).is_empty());
+    }
+
+    fn file(path: &str, language: &str, lines: i64) -> FileHealth {
+        FileHealth {
+            path: path.to_string(),
+            language: language.to_string(),
+            line_count: lines,
+            commit_count: 1,
+            churn_score: lines as f64 / 1000.0,
+            dependents_count: 0,
+            is_fragile: false,
+            is_dead: false,
+            is_large: false,
+            is_single_owner: false,
+            owner: None,
+        }
+    }
+
+    fn graph_fixture() -> (Vec<FileHealth>, Vec<(String, String)>) {
+        let files = vec![
+            file("build.rs", "rust", 10),
+            file("src/main.rs", "rust", 50),
+            file("src/db/mod.rs", "rust", 300),
+            file("src/db/schema.rs", "rust", 200),
+            file("src/db/seed.sql", "sql", 400),
+            file("src/web/app.ts", "typescript", 100),
+            file("src/web/api.ts", "typescript", 80),
+        ];
+        let edges = edges(&[
+            ("src/main.rs", "src/db/mod.rs"),
+            ("src/db/mod.rs", "src/db/schema.rs"),
+            ("src/web/app.ts", "src/web/api.ts"),
+            ("src/web/api.ts", "src/db/mod.rs"),
+            ("src/web/app.ts", "src/db/schema.rs"),
+        ]);
+        (files, edges)
+    }
+
+    #[test]
+    fn exports_file_graph_around_root() {
+        let (files, edges) = graph_fixture();
+
+        let full = build_graph(&files, &edges, &GraphOptions::default()).unwrap();
+        let ids: Vec<_> = full.nodes.iter().map(|n| n.id.as_str()).collect();
+        // Files outside every import edge are left out
+        assert!(!ids.contains(&"build.rs") && !ids.contains(&"src/db/seed.sql"));
+        assert_eq!(full.nodes.len(), 5);
+        assert_eq!(full.edges.len(), 5);
+
+        let options = GraphOptions {
+            root: Some("src/main.rs".to_string()),
+            depth: Some(1),
+            collapse: None,
+        };
+        let near = build_graph(&files, &edges, &options).unwrap();
+        let ids: Vec<_> = near.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["src/db/mod.rs", "src/main.rs"]);
+        assert_eq!(near.edges.len(), 1);
+
+        let options = GraphOptions {
+            root: Some("src/nope".to_string()),
+            ..Default::default()
+        };
+        assert!(build_graph(&files, &edges, &options).is_err());
+    }
+
+    #[test]
+    fn collapses_files_by_directory() {
+        let (files, edges) = graph_fixture();
+        let options = GraphOptions {
+            root: Some("src/".to_string()),
+            depth: None,
+            collapse: Some(2),
+        };
+        let graph = build_graph(&files, &edges, &options).unwrap();
+
+        let ids: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["src", "src/db", "src/web"]);
+        let db = &graph.nodes[1];
+        assert_eq!((db.files, db.lines), (3, 900));
+        assert_eq!(db.language, "sql");
+        assert_eq!(db.churn, 0.4);
+
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), e.weight))
+            .collect();
+        assert_eq!(edges, vec![("src", "src/db", 1), ("src/web", "src/db", 2)]);
+        assert_eq!(collapse_path("src/db/mod.rs", 1), "src");
+        assert_eq!(collapse_path("src/db/mod.rs", 5), "src/db");
+        assert_eq!(collapse_path("build.rs", 2), "build.rs");
+    }
+
+    fn imports(pairs: &[(&str, &str)]) -> Vec<ResolvedImport> {
+        pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (from, to))| ResolvedImport {
+                path: from.to_string(),
+                line: i as i64 + 1,
+                import: format!("./{}", to),
+                kind: "import".to_string(),
+                names: vec![to.to_uppercase()],
+                target: to.to_string(),
+            })
+            .collect()
+    }
+
+    fn chains(paths: &[Vec<ResolvedImport>]) -> Vec<Vec<&str>> {
+        paths
+            .iter()
+            .map(|hops| {
+                let mut files = vec![hops[0].path.as_str()];
+                files.extend(hops.iter().map(|h| h.target.as_str()));
+                files
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_shortest_import_chain() {
+        let graph = imports(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("a", "x"),
+            ("x", "d"),
+            ("d", "a"),
+        ]);
+
+        let shortest = find_import_paths(&graph, "a", "d", 0, 1);
+        assert_eq!(chains(&shortest), vec![vec!["a", "x", "d"]]);
+        assert_eq!(shortest[0][1].line, 5);
+        assert_eq!(shortest[0][1].names, vec!["D"]);
+
+        assert!(find_import_paths(&graph, "d", "q", 0, 1).is_empty());
+        assert!(find_import_paths(&graph, "a", "a", 0, 1).is_empty());
+    }
+
+    #[test]
+    fn enumerates_bounded_chains_shortest_first() {
+        let graph = imports(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("a", "x"),
+            ("x", "d"),
+            ("x", "b"),
+            ("b", "a"),
+        ]);
+
+        let all = find_import_paths(&graph, "a", "d", 10, 10);
+        assert_eq!(
+            chains(&all),
+            vec![
+                vec!["a", "x", "d"],
+                vec!["a", "b", "c", "d"],
+                vec!["a", "x", "b", "c", "d"],
+            ]
+        );
+        assert_eq!(find_import_paths(&graph, "a", "d", 3, 10).len(), 2);
+        assert_eq!(find_import_paths(&graph, "a", "d", 10, 2).len(), 2);
+    }
+
+    #[test]
+    fn symbol_blast_radius_respects_max_depth() {
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "typescript", 0, "", 10).unwrap();
+        let lib = file("src/lib.ts");
+        let app = file("src/app.ts");
+        let index = file("src/index.ts");
+        let cli = file("src/cli.ts");
+        let save = db
+            .insert_symbol(lib, "save", &SymbolKind::Function, 1, 3, "", None)
+            .unwrap();
+        let handler = db
+            .insert_symbol(app, "handler", &SymbolKind::Function, 2, 4, "", None)
+            .unwrap();
+        let main = db
+            .insert_symbol(cli, "main", &SymbolKind::Function, 2, 4, "", None)
+            .unwrap();
+        db.insert_dependency(app, "./lib", "import", r#"["save"]"#, 1)
+            .unwrap();
+        db.insert_dependency(index, "./lib", "import", r#"["save"]"#, 1)
+            .unwrap();
+        db.insert_dependency(cli, "./app", "import", r#"["handler"]"#, 1)
+            .unwrap();
+        db.insert_reference(app, Some(handler), "save", "call", 3)
+            .unwrap();
+        db.insert_reference(cli, Some(main), "handler", "call", 3)
+            .unwrap();
+        db.resolve_dependencies(&ProjectModules::default()).unwrap();
+        db.resolve_references().unwrap();
+
+        let targets: Vec<Symbol> = db
+            .get_symbols_for_file(lib)
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.id == save)
+            .collect();
+        let radius = |max_depth| -> Vec<(String, Option<String>, usize)> {
+            symbol_blast_radius(&db, &targets, max_depth)
+                .unwrap()
+                .into_iter()
+                .map(|i| (i.path, i.symbol, i.depth))
+                .collect()
+        };
+        let import = |path: &str| (path.to_string(), None, 1);
+        let call =
+            |path: &str, name: &str, depth| (path.to_string(), Some(name.to_string()), depth);
+
+        assert_eq!(
+            radius(None),
+            [
+                import("src/app.ts"),
+                call("src/app.ts", "handler", 1),
+                import("src/index.ts"),
+                call("src/cli.ts", "main", 2),
+            ]
+        );
+        assert_eq!(
+            radius(Some(1)),
+            [
+                import("src/app.ts"),
+                call("src/app.ts", "handler", 1),
+                import("src/index.ts"),
+            ]
+        );
+        // Importers are one level out too, so depth 0 leaves nothing
+        assert!(radius(Some(0)).is_empty());
+    }
+}
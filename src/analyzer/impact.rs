@@ -0,0 +1,228 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+use super::graph;
+use crate::db::models::{Symbol, SymbolImpact};
+use crate::db::Database;
+use crate::git::diff::ChangedFile;
+
+/// A changed file with the stored symbols its changed lines touch
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedFileImpact {
+    pub path: String,
+    pub status: String,
+    pub ranges: Vec<(usize, usize)>,
+    pub symbols: Vec<ChangedSymbol>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedSymbol {
+    pub name: String,
+    pub kind: String,
+    pub start_line: i64,
+    pub end_line: i64,
+}
+
+/// Combined blast radius of a set of changes
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeImpact {
+    pub changed_files: Vec<ChangedFileImpact>,
+    pub affected_symbols: Vec<SymbolImpact>,
+    /// Affected file path → shallowest depth at which it is reached
+    pub affected_files: BTreeMap<String, usize>,
+    pub risk: &'static str,
+}
+
+/// Map changed line ranges onto stored symbols and union their blast radius.
+///
+/// Files whose changes touch no symbol (top-level edits) and deleted files fall
+/// back to file-level blast radius.
+pub fn change_impact(db: &Database, changes: &[ChangedFile]) -> Result<ChangeImpact> {
+    let mut changed_files = Vec::new();
+    let mut touched: Vec<Symbol> = Vec::new();
+    let mut affected_files: BTreeMap<String, usize> = BTreeMap::new();
+
+    for change in changes {
+        let mut entry = ChangedFileImpact {
+            path: change.path.clone(),
+            status: change.status.clone(),
+            ranges: change.ranges.clone(),
+            symbols: Vec::new(),
+        };
+
+        let Some(file_id) = db.get_file_id(&change.path)? else {
+            // Not indexed (new, untracked, or not a source file)
+            changed_files.push(entry);
+            continue;
+        };
+
+        let file_symbols = if change.status == "deleted" {
+            Vec::new()
+        } else {
+            innermost_touched(db.get_symbols_for_file(file_id)?, &change.ranges)
+        };
+
+        if file_symbols.is_empty() {
+            for (_, path, depth) in graph::blast_radius(db, file_id)? {
+                merge_depth(&mut affected_files, path, depth);
+            }
+        }
+
+        entry.symbols = file_symbols
+            .iter()
+            .map(|s| ChangedSymbol {
+                name: s.name.clone(),
+                kind: s.kind.as_str().to_string(),
+                start_line: s.start_line,
+                end_line: s.end_line,
+            })
+            .collect();
+        touched.extend(file_symbols);
+        changed_files.push(entry);
+    }
+
+    let affected_symbols = graph::symbol_blast_radius(db, &touched, None)?;
+    for impact in &affected_symbols {
+        merge_depth(&mut affected_files, impact.path.clone(), impact.depth);
+    }
+
+    Ok(ChangeImpact {
+        changed_files,
+        affected_symbols,
+        risk: db.config.risk.level(affected_files.len()),
+        affected_files,
+    })
+}
+
+/// Symbols overlapping any range, dropping parents whose children are also touched
+fn innermost_touched(symbols: Vec<Symbol>, ranges: &[(usize, usize)]) -> Vec<Symbol> {
+    let overlapping: Vec<Symbol> = symbols
+        .into_iter()
+        .filter(|s| {
+            ranges
+                .iter()
+                .any(|&(start, end)| s.start_line <= end as i64 && start as i64 <= s.end_line)
+        })
+        .collect();
+
+    let parents: HashSet<i64> = overlapping
+        .iter()
+        .filter_map(|s| s.parent_symbol_id)
+        .collect();
+
+    overlapping
+        .into_iter()
+        .filter(|s| !parents.contains(&s.id))
+        .collect()
+}
+
+fn merge_depth(files: &mut BTreeMap<String, usize>, path: String, depth: usize) {
+    let entry = files.entry(path).or_insert(depth);
+    *entry = (*entry).min(depth);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::modules::ProjectModules;
+    use crate::db::models::SymbolKind;
+
+    /// `src/lib.ts`: class Store (1-20) with save (3-8) and load (10-15), and
+    /// helper (22-25); `src/app.ts` imports it and calls `save` from main
+    fn store_project() -> (Database, i64) {
+        let db = Database::open_in_memory().unwrap();
+        let lib = db
+            .upsert_file("src/lib.ts", "typescript", 0, "", 30)
+            .unwrap();
+        let app = db
+            .upsert_file("src/app.ts", "typescript", 0, "", 10)
+            .unwrap();
+        let store = db
+            .insert_symbol(lib, "Store", &SymbolKind::Class, 1, 20, "", None)
+            .unwrap();
+        db.insert_symbol(lib, "save", &SymbolKind::Method, 3, 8, "", Some(store))
+            .unwrap();
+        db.insert_symbol(lib, "load", &SymbolKind::Method, 10, 15, "", Some(store))
+            .unwrap();
+        db.insert_symbol(lib, "helper", &SymbolKind::Function, 22, 25, "", None)
+            .unwrap();
+        let main = db
+            .insert_symbol(app, "main", &SymbolKind::Function, 2, 5, "", None)
+            .unwrap();
+        db.insert_dependency(app, "./lib", "import", "[\"Store\"]", 1)
+            .unwrap();
+        db.insert_reference(app, Some(main), "save", "call", 3)
+            .unwrap();
+        db.resolve_dependencies(&ProjectModules::default()).unwrap();
+        db.resolve_references().unwrap();
+        (db, lib)
+    }
+
+    fn change(path: &str, status: &str, ranges: &[(usize, usize)]) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            status: status.to_string(),
+            ranges: ranges.to_vec(),
+        }
+    }
+
+    fn names(symbols: &[Symbol]) -> Vec<&str> {
+        symbols.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn innermost_touched_prefers_nested_symbols() {
+        let (db, lib) = store_project();
+        let symbols = || db.get_symbols_for_file(lib).unwrap();
+
+        assert_eq!(names(&innermost_touched(symbols(), &[(5, 5)])), ["save"]);
+        // A range spanning both methods touches both, never the class
+        let both = innermost_touched(symbols(), &[(7, 11)]);
+        assert_eq!(names(&both), ["save", "load"]);
+        // Between the methods only the class itself changed
+        assert_eq!(names(&innermost_touched(symbols(), &[(9, 9)])), ["Store"]);
+        assert!(innermost_touched(symbols(), &[(27, 30)]).is_empty());
+    }
+
+    #[test]
+    fn change_impact_maps_ranges_to_symbols() {
+        let (db, _) = store_project();
+
+        // Deleted lines are anchored where they were, inside `save`
+        let impact = change_impact(&db, &[change("src/lib.ts", "modified", &[(6, 6)])]).unwrap();
+        let touched = &impact.changed_files[0].symbols;
+        assert_eq!(touched.len(), 1);
+        assert_eq!(touched[0].name, "save");
+        assert!(impact
+            .affected_symbols
+            .iter()
+            .any(|s| s.symbol.as_deref() == Some("main") && s.path == "src/app.ts"));
+        assert_eq!(impact.affected_files.get("src/app.ts"), Some(&1));
+
+        // `load` has no callers, so nothing else is affected
+        let impact = change_impact(&db, &[change("src/lib.ts", "modified", &[(12, 12)])]).unwrap();
+        assert_eq!(impact.changed_files[0].symbols[0].name, "load");
+        assert!(impact.affected_files.is_empty());
+    }
+
+    #[test]
+    fn change_impact_falls_back_to_file_blast_radius() {
+        let (db, _) = store_project();
+
+        // Top-level edit outside every symbol
+        let impact = change_impact(&db, &[change("src/lib.ts", "modified", &[(28, 29)])]).unwrap();
+        assert!(impact.changed_files[0].symbols.is_empty());
+        assert_eq!(impact.affected_files.get("src/app.ts"), Some(&1));
+
+        // Deleted files keep their ranges but touch no symbols
+        let impact = change_impact(&db, &[change("src/lib.ts", "deleted", &[(1, 1)])]).unwrap();
+        assert!(impact.changed_files[0].symbols.is_empty());
+        assert_eq!(impact.affected_files.get("src/app.ts"), Some(&1));
+
+        // Files missing from the index are listed without impact
+        let impact = change_impact(&db, &[change("notes.txt", "untracked", &[(1, 3)])]).unwrap();
+        assert_eq!(impact.changed_files[0].path, "notes.txt");
+        assert!(impact.affected_files.is_empty());
+    }
+}
@@ -1,109 +1,351 @@
 pub mod graph;
+pub mod impact;
+pub mod modules;
 pub mod parser;
+pub mod rules;
 pub mod scanner;
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
+use crate::db::models::{SymbolKind, SymbolMetrics};
 use crate::db::Database;
-use parser::{parse_file, ExtractedSymbol};
+use modules::ProjectModules;
+use parser::{parse_file, ExtractedMetrics, ExtractedSymbol, ParseResult};
 
-/// Run a full analysis of the project
+/// Parsed files buffered between the worker pool and the writer
+const PIPELINE_CAPACITY: usize = 512;
+
+/// Run a full analysis of the project.
+///
+/// Reading, hashing and parsing run on a rayon worker pool; the calling thread
+/// is the single writer. All writes happen in one transaction, so readers see
+/// either the previous index or the new one, and a failed scan changes nothing.
 pub fn analyze_project(db: &Database, root: &Path) -> Result<AnalysisResult> {
-    let files = scanner::scan_project(root)?;
+    let filter = scanner::PathFilter::new(root, &db.config.scan)?;
+    let paths = scanner::discover_files(root, &filter)?;
+    let known_hashes: HashMap<String, String> = db
+        .get_all_files()?
+        .into_iter()
+        .map(|f| (f.path, f.hash))
+        .collect();
+
+    db.transaction(|| analyze_discovered(db, root, &paths, &known_hashes))
+}
 
-    let mut total_symbols = 0usize;
-    let mut total_imports = 0usize;
-    let mut analyzed_files = 0usize;
-    let mut skipped_files = 0usize;
+fn analyze_discovered(
+    db: &Database,
+    root: &Path,
+    paths: &[PathBuf],
+    known_hashes: &HashMap<String, String>,
+) -> Result<AnalysisResult> {
+    let mut result = AnalysisResult::default();
     let mut all_paths: Vec<String> = Vec::new();
+    let mut changed_files: Vec<i64> = Vec::new();
+
+    let (tx, rx) = mpsc::sync_channel::<PreparedFile>(PIPELINE_CAPACITY);
+    std::thread::scope(|scope| -> Result<()> {
+        scope.spawn(|| {
+            // Stops early once the writer hangs up
+            let _ = paths.par_iter().try_for_each_with(tx, |tx, path| {
+                match scanner::scan_file(root, path) {
+                    Some(file) => {
+                        let known = known_hashes.get(&file.relative_path).map(String::as_str);
+                        tx.send(prepare_file(file, known)).map_err(|_| ())
+                    }
+                    None => Ok(()),
+                }
+            });
+        });
 
-    for file in &files {
-        all_paths.push(file.relative_path.clone());
+        for prepared in rx {
+            all_paths.push(prepared.file.relative_path.clone());
+            changed_files.extend(write_file(db, prepared, &mut result)?);
+        }
+        Ok(())
+    })?;
+    result.total_files = all_paths.len();
 
-        // Check if file needs re-analysis BEFORE upserting (hash changed)
-        let existing = db.get_file_by_path(&file.relative_path)?;
-        let needs_reanalyze = existing.map(|f| f.hash != file.hash).unwrap_or(true);
+    // Remove files that no longer exist
+    result.removed_files = db.remove_files_not_in(&all_paths)?;
 
-        // Upsert file into DB (updates hash, size, line_count)
-        db.upsert_file(
-            &file.relative_path,
-            &file.language,
-            file.size_bytes as i64,
-            &file.hash,
-            file.line_count as i64,
-        )?;
+    // Resolve dependency links, then call targets (which prefer imported files).
+    // Include globs may have hidden the manifests from `paths`.
+    let modules = if db.config.scan.include.is_empty() {
+        ProjectModules::load(root, paths)
+    } else {
+        ProjectModules::discover(root, &db.config.scan)?
+    };
+    db.resolve_dependencies(&modules)?;
+    db.resolve_references()?;
+    // Unchanged files may call names that re-analyzed files now define
+    if !known_hashes.is_empty() {
+        let mut defined: HashSet<String> = HashSet::new();
+        for file_id in &changed_files {
+            defined.extend(
+                db.get_symbols_for_file(*file_id)?
+                    .into_iter()
+                    .map(|s| s.name),
+            );
+        }
+        db.resolve_references_for(&[], &defined.into_iter().collect::<Vec<_>>())?;
+    }
 
-        if !needs_reanalyze {
-            skipped_files += 1;
+    // Rebuild search index
+    db.rebuild_search_index()?;
+
+    Ok(result)
+}
+
+/// Re-analyze only the given paths (as reported by a file watcher).
+///
+/// Paths may be absolute or project-relative, files or directories. Paths that
+/// no longer exist are removed from the index along with everything below them,
+/// so deletes and both halves of a rename are handled. Only dependency and
+/// reference rows touched by the change are re-resolved, and the search index
+/// is updated per file instead of being rebuilt. Like `analyze_project`, the
+/// update is applied in a single transaction.
+///
+/// `modules` caches the project's import maps across calls (a watcher
+/// session): they are loaded on first use and reloaded only when one of
+/// `paths` is a tsconfig/jsconfig, package.json, go.mod or go.work.
+pub fn analyze_paths(
+    db: &Database,
+    root: &Path,
+    paths: &[PathBuf],
+    modules: &mut Option<ProjectModules>,
+) -> Result<AnalysisResult> {
+    db.transaction(|| analyze_changed_paths(db, root, paths, modules))
+}
+
+fn analyze_changed_paths(
+    db: &Database,
+    root: &Path,
+    paths: &[PathBuf],
+    modules: &mut Option<ProjectModules>,
+) -> Result<AnalysisResult> {
+    let manifests_changed = paths.iter().any(|p| ProjectModules::is_manifest(p));
+    if manifests_changed {
+        *modules = None;
+    }
+    let canonical_root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let filter = scanner::PathFilter::new(root, &db.config.scan)?;
+
+    let mut result = AnalysisResult::default();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut changed_files: Vec<i64> = Vec::new();
+    let mut added_stems: Vec<String> = Vec::new();
+    // Directories that gained or lost files, whose Go package changed
+    let mut package_dirs: HashSet<String> = HashSet::new();
+    let mut touched_names: HashSet<String> = HashSet::new();
+
+    for path in paths {
+        let absolute = if path.is_absolute() {
+            path.clone()
+        } else {
+            root.join(path)
+        };
+        let Ok(relative) = absolute
+            .strip_prefix(root)
+            .or_else(|_| absolute.strip_prefix(&canonical_root))
+        else {
+            continue;
+        };
+        // Re-anchor on `root` so walks and filters see the same prefix
+        let relative = relative.to_path_buf();
+        let absolute = root.join(&relative);
+        // A vanished path may have been a directory; treating it as one keeps
+        // file-only include globs from hiding its removal
+        if relative.as_os_str().is_empty() || filter.is_excluded(&relative, !absolute.is_file()) {
             continue;
         }
 
-        // Get the file_id
-        let file_id = db
-            .get_file_id(&file.relative_path)?
-            .context("File should exist after upsert")?;
-
-        // Clear old data for re-analysis
-        db.clear_symbols(file_id)?;
-        db.clear_dependencies(file_id)?;
-
-        // Parse with tree-sitter if supported
-        if scanner::is_parseable(&file.language) {
-            match parse_file(&file.content, &file.language) {
-                Ok(result) => {
-                    // Store symbols
-                    for sym in &result.symbols {
-                        store_symbol(db, file_id, sym, None)?;
-                        total_symbols += 1;
-                        total_symbols += sym.children.len();
-                    }
+        let relative_str = relative.to_string_lossy().to_string();
+        if !seen.insert(relative_str.clone()) {
+            continue;
+        }
+
+        let scanned = if absolute.is_dir() {
+            scanner::scan_directory(root, &absolute, &filter)?
+        } else {
+            scanner::scan_file(root, &absolute).into_iter().collect()
+        };
 
-                    // Store imports as dependencies
-                    for imp in &result.imports {
-                        db.insert_dependency(
-                            file_id,
-                            &imp.path,
-                            &imp.kind,
-                            &serde_json::to_string(&imp.names).unwrap_or_else(|_| "[]".to_string()),
-                        )?;
-                        total_imports += 1;
+        if scanned.is_empty() {
+            // Deleted, renamed away, or no longer a readable source file
+            for (file_id, path) in db.get_files_under(&relative_str)? {
+                package_dirs.insert(parent_dir(&path).to_string());
+                let old_symbols = db.get_symbols_for_file(file_id)?;
+                db.unindex_symbols(&old_symbols.iter().map(|s| s.id).collect::<Vec<_>>())?;
+                touched_names.extend(old_symbols.into_iter().map(|s| s.name));
+                db.remove_file(file_id)?;
+                result.removed_files += 1;
+            }
+            continue;
+        }
+
+        for file in scanned {
+            result.total_files += 1;
+            let old_symbols = match db.get_file_id(&file.relative_path)? {
+                Some(file_id) => db.get_symbols_for_file(file_id)?,
+                None => {
+                    package_dirs.insert(parent_dir(&file.relative_path).to_string());
+                    if let Some(stem) = Path::new(&file.relative_path).file_stem() {
+                        added_stems.push(stem.to_string_lossy().to_string());
                     }
+                    Vec::new()
                 }
-                Err(_) => {
-                    // Parsing failed, but file is still tracked
-                }
-            }
+            };
+
+            let Some(file_id) = analyze_file(db, file, &mut result)? else {
+                continue;
+            };
+
+            db.unindex_symbols(&old_symbols.iter().map(|s| s.id).collect::<Vec<_>>())?;
+            db.index_file_symbols(file_id)?;
+            touched_names.extend(old_symbols.into_iter().map(|s| s.name));
+            touched_names.extend(
+                db.get_symbols_for_file(file_id)?
+                    .into_iter()
+                    .map(|s| s.name),
+            );
+            changed_files.push(file_id);
         }
+    }
 
-        analyzed_files += 1;
+    let touched_names: Vec<String> = touched_names.into_iter().collect();
+    if manifests_changed
+        || !changed_files.is_empty()
+        || !added_stems.is_empty()
+        || result.removed_files > 0
+    {
+        let modules = match modules {
+            Some(modules) => modules,
+            None => modules.insert(ProjectModules::discover(root, &db.config.scan)?),
+        };
+        let package_dirs: Vec<String> = package_dirs.into_iter().collect();
+        db.resolve_dependencies_for(modules, &changed_files, &added_stems, &package_dirs)?;
+        if manifests_changed {
+            db.reresolve_module_imports(modules)?;
+        }
     }
+    db.resolve_references_for(&changed_files, &touched_names)?;
 
-    // Remove files that no longer exist
-    let removed = db.remove_files_not_in(&all_paths)?;
+    Ok(result)
+}
 
-    // Resolve dependency links
-    db.resolve_dependencies()?;
+/// Root-relative directory of a root-relative file path
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
 
-    // Rebuild search index
-    db.rebuild_search_index()?;
+/// A scanned file, parsed off the writer thread when its content changed
+struct PreparedFile {
+    file: scanner::ScannedFile,
+    /// `None` when the stored hash still matches
+    parsed: Option<ParseResult>,
+}
 
-    Ok(AnalysisResult {
-        total_files: files.len(),
-        analyzed_files,
-        skipped_files,
-        removed_files: removed,
-        total_symbols,
-        total_imports,
-    })
+/// Parse a file unless its hash matches `known_hash`. The content is dropped
+/// afterwards so queued files stay small.
+fn prepare_file(mut file: scanner::ScannedFile, known_hash: Option<&str>) -> PreparedFile {
+    let parsed = if known_hash == Some(file.hash.as_str()) {
+        None
+    } else if scanner::is_parseable(&file.language) {
+        // Parsing failed, but file is still tracked
+        Some(parse_file(&file.content, &file.language).unwrap_or_default())
+    } else {
+        Some(ParseResult::default())
+    };
+    file.content = String::new();
+    PreparedFile { file, parsed }
+}
+
+/// Upsert a scanned file and re-parse it if its content changed.
+/// Returns the file id when the file was (re-)analyzed.
+fn analyze_file(
+    db: &Database,
+    file: scanner::ScannedFile,
+    result: &mut AnalysisResult,
+) -> Result<Option<i64>> {
+    let known = db.get_file_by_path(&file.relative_path)?.map(|f| f.hash);
+    write_file(db, prepare_file(file, known.as_deref()), result)
 }
 
-/// Recursively store a symbol and its children
+/// Store a prepared file. Returns the file id when the file was (re-)analyzed.
+fn write_file(
+    db: &Database,
+    prepared: PreparedFile,
+    result: &mut AnalysisResult,
+) -> Result<Option<i64>> {
+    let file = &prepared.file;
+
+    // Upsert file into DB (updates hash, size, line_count)
+    db.upsert_file(
+        &file.relative_path,
+        &file.language,
+        file.size_bytes as i64,
+        &file.hash,
+        file.line_count as i64,
+    )?;
+
+    let Some(parsed) = prepared.parsed else {
+        result.skipped_files += 1;
+        return Ok(None);
+    };
+
+    // Get the file_id
+    let file_id = db
+        .get_file_id(&file.relative_path)?
+        .context("File should exist after upsert")?;
+
+    // Clear old data for re-analysis
+    db.clear_references(file_id)?;
+    db.clear_symbols(file_id)?;
+    db.clear_dependencies(file_id)?;
+
+    // Store symbols
+    let mut spans = Vec::new();
+    for sym in &parsed.symbols {
+        store_symbol(db, file_id, sym, None, &parsed.metrics, &mut spans)?;
+        result.total_symbols += 1;
+        result.total_symbols += sym.children.len();
+    }
+
+    // Store call sites, attributed to their innermost enclosing symbol
+    for call in &parsed.calls {
+        let caller = enclosing_symbol(&spans, call.line);
+        db.insert_reference(file_id, caller, &call.name, &call.kind, call.line as i64)?;
+        result.total_calls += 1;
+    }
+
+    // Store imports as dependencies
+    for imp in &parsed.imports {
+        db.insert_dependency(
+            file_id,
+            &imp.path,
+            &imp.kind,
+            &serde_json::to_string(&imp.names).unwrap_or_else(|_| "[]".to_string()),
+            imp.line as i64,
+        )?;
+        result.total_imports += 1;
+    }
+
+    result.analyzed_files += 1;
+    Ok(Some(file_id))
+}
+
+/// Recursively store a symbol and its children, recording `(id, start, end)` spans
 fn store_symbol(
     db: &Database,
     file_id: i64,
     sym: &ExtractedSymbol,
     parent_id: Option<i64>,
+    metrics: &[ExtractedMetrics],
+    spans: &mut Vec<(i64, usize, usize)>,
 ) -> Result<()> {
     let sym_id = db.insert_symbol(
         file_id,
@@ -115,15 +357,49 @@ fn store_symbol(
         parent_id,
     )?;
 
+    if matches!(
+        sym.kind,
+        SymbolKind::Function | SymbolKind::Method | SymbolKind::Component | SymbolKind::Hook
+    ) {
+        if let Some(m) = function_metrics(metrics, sym.start_line, sym.end_line) {
+            db.set_symbol_metrics(sym_id, m)?;
+        }
+    }
+
+    spans.push((sym_id, sym.start_line, sym.end_line));
+
     for child in &sym.children {
-        store_symbol(db, file_id, child, Some(sym_id))?;
+        store_symbol(db, file_id, child, Some(sym_id), metrics, spans)?;
     }
 
     Ok(())
 }
 
+/// Metrics of the outermost function node ending with the symbol. Symbol
+/// spans may start earlier than the function node (decorators, `const f =`).
+fn function_metrics(
+    metrics: &[ExtractedMetrics],
+    start_line: usize,
+    end_line: usize,
+) -> Option<&SymbolMetrics> {
+    metrics
+        .iter()
+        .filter(|m| m.end_line == end_line && m.start_line >= start_line)
+        .min_by_key(|m| m.start_line)
+        .map(|m| &m.metrics)
+}
+
+/// Find the smallest symbol span containing `line`
+fn enclosing_symbol(spans: &[(i64, usize, usize)], line: usize) -> Option<i64> {
+    spans
+        .iter()
+        .filter(|(_, start, end)| *start <= line && line <= *end)
+        .min_by_key(|(_, start, end)| end - start)
+        .map(|(id, _, _)| *id)
+}
+
 /// Result of a project analysis
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct AnalysisResult {
     pub total_files: usize,
     pub analyzed_files: usize,
@@ -131,4 +407,160 @@ pub struct AnalysisResult {
     pub removed_files: usize,
     pub total_symbols: usize,
     pub total_imports: usize,
+    pub total_calls: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A project on disk, analyzed into an in-memory database
+    struct Project {
+        root: PathBuf,
+        db: Database,
+        modules: Option<ProjectModules>,
+    }
+
+    impl Project {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root = std::env::temp_dir().join(format!("ctx-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            let project = Self {
+                root,
+                db: Database::open_in_memory().unwrap(),
+                modules: None,
+            };
+            for (path, content) in files {
+                project.write(path, content);
+            }
+            analyze_project(&project.db, &project.root).unwrap();
+            project
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        fn update(&mut self, paths: &[&str]) -> AnalysisResult {
+            let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+            analyze_paths(&self.db, &self.root, &paths, &mut self.modules).unwrap()
+        }
+
+        /// Paths of the symbols the search index finds for `name`
+        fn search(&self, name: &str) -> Vec<String> {
+            self.db
+                .search(name)
+                .unwrap()
+                .into_iter()
+                .filter(|r| r.name == name)
+                .map(|r| r.path)
+                .collect()
+        }
+
+        fn indexed(&self, path: &str) -> bool {
+            self.db.get_file_id(path).unwrap().is_some()
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn deleted_file_is_removed() {
+        let mut project = Project::new(
+            "paths-delete",
+            &[
+                ("src/a.rs", "fn alpha() {}\n"),
+                ("src/b.rs", "fn beta() {}\n"),
+            ],
+        );
+        std::fs::remove_file(project.root.join("src/a.rs")).unwrap();
+
+        let result = project.update(&["src/a.rs"]);
+        assert_eq!(result.removed_files, 1);
+        assert!(!project.indexed("src/a.rs"));
+        assert!(project.search("alpha").is_empty());
+        assert_eq!(project.search("beta"), ["src/b.rs"]);
+    }
+
+    #[test]
+    fn renamed_file_moves_its_symbols() {
+        let mut project = Project::new("paths-rename", &[("src/a.rs", "fn alpha() {}\n")]);
+        std::fs::rename(project.root.join("src/a.rs"), project.root.join("src/c.rs")).unwrap();
+
+        // Watchers report both halves of a rename
+        let result = project.update(&["src/a.rs", "src/c.rs"]);
+        assert_eq!((result.removed_files, result.analyzed_files), (1, 1));
+        assert!(!project.indexed("src/a.rs"));
+        assert!(project.indexed("src/c.rs"));
+        assert_eq!(project.search("alpha"), ["src/c.rs"]);
+    }
+
+    #[test]
+    fn removed_directory_drops_every_file_below_it() {
+        let mut project = Project::new(
+            "paths-rmdir",
+            &[
+                ("src/util/x.rs", "fn xray() {}\n"),
+                ("src/util/nested/y.rs", "fn yank() {}\n"),
+                ("src/main.rs", "fn main() {}\n"),
+            ],
+        );
+        std::fs::remove_dir_all(project.root.join("src/util")).unwrap();
+
+        let result = project.update(&["src/util"]);
+        assert_eq!(result.removed_files, 2);
+        assert!(!project.indexed("src/util/x.rs"));
+        assert!(!project.indexed("src/util/nested/y.rs"));
+        assert!(project.search("xray").is_empty());
+        assert!(project.search("yank").is_empty());
+        assert_eq!(project.search("main"), ["src/main.rs"]);
+    }
+
+    #[test]
+    fn changed_symbols_replace_the_old_ones() {
+        let mut project = Project::new(
+            "paths-edit",
+            &[("src/a.rs", "fn alpha() {}\nfn keep() {}\n")],
+        );
+        project.write("src/a.rs", "fn gamma() {}\nfn keep() {}\n");
+
+        let result = project.update(&["src/a.rs"]);
+        assert_eq!(result.analyzed_files, 1);
+        assert!(project.search("alpha").is_empty());
+        assert_eq!(project.search("gamma"), ["src/a.rs"]);
+        assert_eq!(project.search("keep"), ["src/a.rs"]);
+    }
+
+    #[test]
+    fn new_definition_re_resolves_existing_callers() {
+        let callee_file = |project: &Project| {
+            project.db.get_callers("helper").unwrap()[0]
+                .callee_file
+                .clone()
+        };
+        let mut project = Project::new(
+            "paths-re-resolve",
+            &[
+                ("src/main.rs", "fn main() {\n    helper();\n}\n"),
+                ("src/util.rs", "pub fn helper() {}\n"),
+            ],
+        );
+        assert_eq!(callee_file(&project).as_deref(), Some("src/util.rs"));
+
+        // On a full rescan, an unchanged caller sees the new definition
+        project.write("src/other.rs", "pub fn helper() {}\n");
+        analyze_project(&project.db, &project.root).unwrap();
+        assert_eq!(callee_file(&project), None);
+
+        // And on a watcher update, the removed one
+        std::fs::remove_file(project.root.join("src/util.rs")).unwrap();
+        project.update(&["src/util.rs"]);
+        assert_eq!(callee_file(&project).as_deref(), Some("src/other.rs"));
+    }
 }
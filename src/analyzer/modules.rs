@@ -0,0 +1,772 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::scanner::{self, PathFilter};
+use crate::config::ScanConfig;
+
+/// `extends` chains longer than this are assumed to be cycles
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// `exports` conditions in order of preference; source-like entries first so
+/// a package resolves to its indexed sources rather than build output
+const EXPORT_CONDITIONS: &[&str] = &[
+    "source",
+    "development",
+    "import",
+    "module",
+    "require",
+    "node",
+    "browser",
+    "default",
+    "types",
+];
+
+/// Project files that map bare import specifiers to paths: tsconfig/jsconfig
+/// `baseUrl` and `paths`, workspace packages by their package.json name, and
+/// Go modules from go.mod/go.work.
+///
+/// Built by the analyzer and handed to dependency resolution, which tries
+/// these candidates before its generic path guesses.
+#[derive(Debug, Default)]
+pub struct ProjectModules {
+    /// One per tsconfig/jsconfig, deepest directory first
+    ts_projects: Vec<TsProject>,
+    /// Longest name first, so `@acme/ui-kit` is tried before `@acme/ui`
+    packages: Vec<WorkspacePackage>,
+    /// Longest module path first, so nested modules win over their parent
+    go_modules: Vec<GoModule>,
+}
+
+/// A Go module path and the directory holding its sources
+#[derive(Debug, PartialEq)]
+struct GoModule {
+    path: String,
+    dir: String,
+}
+
+#[derive(Debug, Default)]
+struct TsProject {
+    /// Directory of the config file, relative to the root ("" at the root)
+    dir: String,
+    /// `jsconfig.json` only applies where there is no `tsconfig.json`
+    is_jsconfig: bool,
+    /// `compilerOptions.baseUrl`, relative to the root
+    base_url: Option<String>,
+    /// `compilerOptions.paths`, targets relative to the root
+    paths: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug)]
+struct WorkspacePackage {
+    name: String,
+    dir: String,
+    /// `exports` subpaths (`.`, `./utils`, `./*`) and their targets, relative to `dir`
+    exports: Vec<(String, Vec<String>)>,
+    /// `source`/`module`/`main`/`types` entries, used without an `exports` map
+    entries: Vec<String>,
+}
+
+impl ProjectModules {
+    /// Load the config files among the discovered project `paths`
+    pub fn load(root: &Path, paths: &[PathBuf]) -> Self {
+        let mut modules = Self::default();
+        for path in paths {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let dir = relative
+                .parent()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            match relative.file_name().and_then(|n| n.to_str()) {
+                Some(name @ ("tsconfig.json" | "jsconfig.json")) => {
+                    let config = relative.to_string_lossy().replace('\\', "/");
+                    if let Some(project) = load_ts_config(root, &config, 0) {
+                        modules.ts_projects.push(TsProject {
+                            dir,
+                            is_jsconfig: name == "jsconfig.json",
+                            ..project
+                        });
+                    }
+                }
+                Some("package.json") => {
+                    if let Some(package) = load_package(path, dir) {
+                        modules.packages.push(package);
+                    }
+                }
+                Some("go.mod") => modules.go_modules.extend(load_go_mod(root, &dir)),
+                // Workspace members outside the scanned tree (or excluded from it)
+                Some("go.work") => {
+                    for member in load_go_work(root, &dir) {
+                        modules.go_modules.extend(load_go_mod(root, &member));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        modules.ts_projects.sort_by(|a, b| {
+            depth(&b.dir)
+                .cmp(&depth(&a.dir))
+                .then(a.is_jsconfig.cmp(&b.is_jsconfig))
+        });
+        modules
+            .packages
+            .sort_by_key(|p| std::cmp::Reverse(p.name.len()));
+        modules
+            .go_modules
+            .sort_by(|a, b| b.path.len().cmp(&a.path.len()).then(a.dir.cmp(&b.dir)));
+        modules.go_modules.dedup();
+        modules
+    }
+
+    /// Whether `path` is a file `load` reads, or a tsconfig/jsconfig another
+    /// one may `extends`, so that changing it can change import resolution
+    pub fn is_manifest(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| {
+                matches!(name, "package.json" | "go.mod" | "go.work")
+                    || ((name.starts_with("tsconfig") || name.starts_with("jsconfig"))
+                        && name.ends_with(".json"))
+            })
+    }
+
+    /// Walk the project for config files (for incremental updates, which only
+    /// see the changed paths, and for scans narrowed by include globs).
+    ///
+    /// Include globs are ignored: `--include 'src/**'` limits what is indexed,
+    /// not which tsconfig.json or go.mod maps the imports inside `src/`.
+    pub fn discover(root: &Path, scan: &ScanConfig) -> Result<Self> {
+        let filter = Self::manifest_filter(root, scan)?;
+        Ok(Self::load(root, &scanner::discover_files(root, &filter)?))
+    }
+
+    /// `scan`'s filters without its include globs, which select what is
+    /// indexed rather than which config files apply
+    pub fn manifest_filter(root: &Path, scan: &ScanConfig) -> Result<PathFilter> {
+        let manifests = ScanConfig {
+            include: vec![],
+            ..scan.clone()
+        };
+        PathFilter::new(root, &manifests)
+    }
+
+    /// Root-relative paths a bare `specifier` imported by `from_file` may
+    /// refer to, best first. Extension and `index` variants are left to the
+    /// caller.
+    pub fn candidates(&self, from_file: &str, specifier: &str) -> Vec<String> {
+        if specifier.starts_with('.') || specifier.starts_with('/') || !is_js_like(from_file) {
+            return vec![];
+        }
+
+        let mut bases = Vec::new();
+        // Only the nearest config applies, as for tsc
+        let project = self
+            .ts_projects
+            .iter()
+            .find(|p| is_under(from_file, &p.dir));
+        if let Some((targets, capture)) = project.and_then(|p| best_match(&p.paths, specifier)) {
+            bases.extend(targets.iter().map(|t| t.replacen('*', capture, 1)));
+        }
+        if let Some(package) = self.packages.iter().find(|p| {
+            specifier == p.name
+                || specifier
+                    .strip_prefix(&p.name)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        }) {
+            bases.extend(package.resolve(&specifier[package.name.len()..]));
+        }
+        // baseUrl is the last resort
+        if let Some(base_url) = project.and_then(|p| p.base_url.as_ref()) {
+            bases.push(join(base_url, specifier));
+        }
+
+        bases
+            .iter()
+            .flat_map(|base| source_variants(base))
+            .collect()
+    }
+
+    /// Root-relative directory of the package a Go import path names, when
+    /// it belongs to one of the project's modules
+    pub fn go_package_dir(&self, import: &str) -> Option<String> {
+        self.go_modules.iter().find_map(|module| {
+            let rest = import.strip_prefix(&module.path)?;
+            if rest.is_empty() || rest.starts_with('/') {
+                Some(join(&module.dir, rest))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The Go import paths naming the package in root-relative `dir`: the
+    /// inverse of `go_package_dir`
+    pub fn go_import_paths(&self, dir: &str) -> Vec<String> {
+        self.go_modules
+            .iter()
+            .filter_map(|module| {
+                let rest = if module.dir.is_empty() || dir == module.dir {
+                    dir.strip_prefix(&module.dir)?
+                } else {
+                    dir.strip_prefix(&module.dir)?.strip_prefix('/')?
+                };
+                Some(if rest.is_empty() {
+                    module.path.clone()
+                } else {
+                    format!("{}/{}", module.path, rest)
+                })
+            })
+            .filter(|import| self.go_package_dir(import).as_deref() == Some(dir))
+            .collect()
+    }
+}
+
+impl WorkspacePackage {
+    /// `subpath` is what follows the package name: "" or "/utils"
+    fn resolve(&self, subpath: &str) -> Vec<String> {
+        let subpath = format!(".{}", subpath);
+        if !self.exports.is_empty() {
+            return match best_match(&self.exports, &subpath) {
+                Some((targets, capture)) => targets
+                    .iter()
+                    .map(|t| join(&self.dir, &t.replacen('*', capture, 1)))
+                    .collect(),
+                // Not exported: nothing else is importable
+                None => vec![],
+            };
+        }
+
+        if subpath == "." {
+            let mut bases: Vec<String> = self.entries.iter().map(|e| join(&self.dir, e)).collect();
+            bases.push(join(&self.dir, "index"));
+            bases.push(join(&self.dir, "src/index"));
+            bases
+        } else {
+            vec![join(&self.dir, &subpath)]
+        }
+    }
+}
+
+/// The entry whose pattern matches `specifier`, with the text matched by `*`.
+/// An exact pattern beats any wildcard; otherwise the longest prefix wins.
+fn best_match<'a, 's>(
+    entries: &'a [(String, Vec<String>)],
+    specifier: &'s str,
+) -> Option<(&'a [String], &'s str)> {
+    entries
+        .iter()
+        .filter_map(|(pattern, targets)| {
+            let (rank, capture) = match pattern.split_once('*') {
+                None if pattern == specifier => (usize::MAX, ""),
+                None => return None,
+                Some((prefix, suffix)) => {
+                    let capture = specifier
+                        .strip_prefix(prefix)?
+                        .strip_suffix(suffix)
+                        .filter(|_| specifier.len() >= prefix.len() + suffix.len())?;
+                    (prefix.len(), capture)
+                }
+            };
+            Some((rank, targets.as_slice(), capture))
+        })
+        .max_by_key(|(rank, _, _)| *rank)
+        .map(|(_, targets, capture)| (targets, capture))
+}
+
+fn load_ts_config(root: &Path, config: &str, level: usize) -> Option<TsProject> {
+    let value = read_jsonc(&root.join(config))?;
+    let dir = parent(config);
+
+    // Inherited options first; later `extends` entries and this file win
+    let mut project = TsProject::default();
+    if level < MAX_EXTENDS_DEPTH {
+        let extends: Vec<&str> = match value.get("extends") {
+            Some(Value::String(s)) => vec![s.as_str()],
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        for spec in extends {
+            let Some(base) =
+                extends_path(root, &dir, spec).and_then(|p| load_ts_config(root, &p, level + 1))
+            else {
+                continue;
+            };
+            if base.base_url.is_some() {
+                project.base_url = base.base_url;
+            }
+            if !base.paths.is_empty() {
+                project.paths = base.paths;
+            }
+        }
+    }
+
+    let options = value.get("compilerOptions");
+    if let Some(base_url) = options
+        .and_then(|o| o.get("baseUrl"))
+        .and_then(Value::as_str)
+    {
+        project.base_url = Some(join(&dir, base_url));
+    }
+    if let Some(paths) = options
+        .and_then(|o| o.get("paths"))
+        .and_then(Value::as_object)
+    {
+        // Targets are relative to baseUrl, or to this file without one
+        let base = project.base_url.clone().unwrap_or_else(|| dir.clone());
+        project.paths = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(|t| join(&base, t))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (pattern.clone(), targets)
+            })
+            .collect();
+    }
+    Some(project)
+}
+
+/// Locate an `extends` target: a relative path, or a package in `node_modules`
+fn extends_path(root: &Path, dir: &str, spec: &str) -> Option<String> {
+    let mut bases = Vec::new();
+    if spec.starts_with('.') || spec.starts_with('/') {
+        bases.push(join(dir, spec));
+    } else {
+        // node_modules in this directory or any ancestor
+        let mut current = Some(dir.to_string());
+        while let Some(d) = current {
+            bases.push(join(&d, &format!("node_modules/{}", spec)));
+            current = (!d.is_empty()).then(|| parent(&d));
+        }
+    }
+
+    bases.into_iter().find_map(|base| {
+        [
+            base.clone(),
+            format!("{}.json", base),
+            format!("{}/tsconfig.json", base),
+        ]
+        .into_iter()
+        .find(|candidate| root.join(candidate).is_file())
+    })
+}
+
+fn load_package(path: &Path, dir: String) -> Option<WorkspacePackage> {
+    let value = read_jsonc(path)?;
+    let name = value.get("name")?.as_str()?.to_string();
+
+    let exports = match value.get("exports") {
+        // Subpath map: { ".": ..., "./utils": ... }
+        Some(Value::Object(map)) if map.keys().any(|k| k.starts_with('.')) => map
+            .iter()
+            .map(|(subpath, target)| (subpath.clone(), export_targets(target)))
+            .collect(),
+        // Sugar for the "." subpath
+        Some(target @ (Value::String(_) | Value::Object(_) | Value::Array(_))) => {
+            vec![(".".to_string(), export_targets(target))]
+        }
+        _ => vec![],
+    };
+    let entries = ["source", "module", "main", "types"]
+        .iter()
+        .filter_map(|key| value.get(*key)?.as_str().map(str::to_string))
+        .collect();
+
+    Some(WorkspacePackage {
+        name,
+        dir,
+        exports,
+        entries,
+    })
+}
+
+/// Targets of one `exports` entry, preferred conditions first
+fn export_targets(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(target) => vec![target.clone()],
+        Value::Array(items) => items.iter().flat_map(export_targets).collect(),
+        Value::Object(conditions) => {
+            let mut targets: Vec<String> = EXPORT_CONDITIONS
+                .iter()
+                .filter_map(|key| conditions.get(*key))
+                .flat_map(export_targets)
+                .collect();
+            // Custom conditions after the well-known ones
+            for (key, target) in conditions {
+                if !EXPORT_CONDITIONS.contains(&key.as_str()) {
+                    targets.extend(export_targets(target));
+                }
+            }
+            targets
+        }
+        // `null` hides a subpath
+        _ => vec![],
+    }
+}
+
+/// The module declared by `<dir>/go.mod`, plus `replace` directives that
+/// point other module paths at local directories
+fn load_go_mod(root: &Path, dir: &str) -> Vec<GoModule> {
+    let Ok(text) = std::fs::read_to_string(root.join(dir).join("go.mod")) else {
+        return vec![];
+    };
+    let mut modules = Vec::new();
+    for (directive, args) in go_directives(&text) {
+        match directive {
+            "module" => modules.push(GoModule {
+                path: args.trim_matches('"').to_string(),
+                dir: dir.to_string(),
+            }),
+            // replace example.com/lib v1.2.0 => ../lib
+            "replace" => {
+                let Some((from, to)) = args.split_once("=>") else {
+                    continue;
+                };
+                let (Some(from), Some(to)) =
+                    (from.split_whitespace().next(), to.split_whitespace().next())
+                else {
+                    continue;
+                };
+                if to.starts_with("./") || to.starts_with("../") {
+                    modules.push(GoModule {
+                        path: from.trim_matches('"').to_string(),
+                        dir: join(dir, to),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    modules
+}
+
+/// Module directories listed by `use` in `<dir>/go.work`
+fn load_go_work(root: &Path, dir: &str) -> Vec<String> {
+    let Ok(text) = std::fs::read_to_string(root.join(dir).join("go.work")) else {
+        return vec![];
+    };
+    go_directives(&text)
+        .into_iter()
+        .filter(|(directive, _)| *directive == "use")
+        .map(|(_, args)| join(dir, args.trim_matches('"')))
+        .collect()
+}
+
+/// `(directive, arguments)` pairs of a go.mod/go.work file, with
+/// `directive ( ... )` blocks expanded to one pair per line
+fn go_directives(text: &str) -> Vec<(&str, &str)> {
+    let mut directives = Vec::new();
+    let mut block: Option<&str> = None;
+    for line in text.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(directive) = block {
+            if line == ")" {
+                block = None;
+            } else {
+                directives.push((directive, line));
+            }
+            continue;
+        }
+        let (directive, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        if args.trim() == "(" {
+            block = Some(directive);
+        } else {
+            directives.push((directive, args.trim()));
+        }
+    }
+    directives
+}
+
+/// A target as written, then without a compiled extension (so `.ts` sources
+/// next to it match), then with a build directory swapped for `src/`
+fn source_variants(base: &str) -> Vec<String> {
+    let mut variants = vec![base.to_string()];
+    let stem = [".d.ts", ".js", ".mjs", ".cjs", ".jsx"]
+        .iter()
+        .find_map(|ext| base.strip_suffix(ext))
+        .unwrap_or(base);
+    if stem != base {
+        variants.push(stem.to_string());
+    }
+
+    let segments: Vec<&str> = stem.split('/').collect();
+    if let Some(i) = segments
+        .iter()
+        .position(|s| matches!(*s, "dist" | "build" | "lib" | "out"))
+    {
+        let mut swapped = segments.clone();
+        swapped[i] = "src";
+        variants.push(swapped.join("/"));
+    }
+    variants
+}
+
+fn read_jsonc(path: &Path) -> Option<Value> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&text)).ok()
+}
+
+/// Drop the comments and trailing commas that tsconfig files allow
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    let mut pending_comma = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|&n| n != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+            }
+            _ if c.is_whitespace() => out.push(c),
+            _ => {
+                if pending_comma && c != '}' && c != ']' {
+                    out.push(',');
+                }
+                pending_comma = c == ',';
+                if !pending_comma {
+                    in_string = c == '"';
+                    out.push(c);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Join `path` onto the root-relative `dir`, collapsing `.` and `..`
+fn join(dir: &str, path: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+fn parent(path: &str) -> String {
+    path.rsplit_once('/')
+        .map(|(dir, _)| dir.to_string())
+        .unwrap_or_default()
+}
+
+fn depth(dir: &str) -> usize {
+    if dir.is_empty() {
+        0
+    } else {
+        dir.matches('/').count() + 1
+    }
+}
+
+fn is_under(path: &str, dir: &str) -> bool {
+    dir.is_empty()
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn is_js_like(path: &str) -> bool {
+    matches!(
+        path.rsplit('.').next(),
+        Some("ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let text = r#"{
+  // line comment
+  "a": "http://x", /* block */
+  "b": [1, 2,],
+  "c": "// not a comment",
+}"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(text)).unwrap();
+        assert_eq!(value["a"], "http://x");
+        assert_eq!(value["b"].as_array().unwrap().len(), 2);
+        assert_eq!(value["c"], "// not a comment");
+    }
+
+    #[test]
+    fn resolves_tsconfig_paths_and_workspace_packages() {
+        let root = std::env::temp_dir().join(format!("ctx-modules-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "tsconfig.base.json",
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["apps/web/src/*"], } } }"#,
+        );
+        write(
+            "apps/web/tsconfig.json",
+            r#"{ "extends": "../../tsconfig.base.json" }"#,
+        );
+        write(
+            "packages/shared/package.json",
+            r#"{ "name": "@acme/shared", "exports": {
+                ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+                "./utils/*": "./src/utils/*.ts"
+            } }"#,
+        );
+        write(
+            "packages/ui/package.json",
+            r#"{ "name": "@acme/ui", "main": "lib/index.js" }"#,
+        );
+
+        let paths: Vec<PathBuf> = [
+            "tsconfig.base.json",
+            "apps/web/tsconfig.json",
+            "packages/shared/package.json",
+            "packages/ui/package.json",
+        ]
+        .iter()
+        .map(|p| root.join(p))
+        .collect();
+        let modules = ProjectModules::load(&root, &paths);
+
+        let alias = modules.candidates("apps/web/src/App.tsx", "@/components/Button");
+        assert_eq!(alias[0], "apps/web/src/components/Button");
+        // Outside the config's directory the alias does not apply
+        assert!(modules
+            .candidates("scripts/build.ts", "@/components/Button")
+            .is_empty());
+
+        let shared = modules.candidates("apps/web/src/App.tsx", "@acme/shared");
+        assert!(shared.contains(&"packages/shared/src/index".to_string()));
+        let utils = modules.candidates("apps/web/src/App.tsx", "@acme/shared/utils/date");
+        assert_eq!(utils[0], "packages/shared/src/utils/date.ts");
+        // Subpaths missing from `exports` are not importable
+        assert!(!modules
+            .candidates("apps/web/src/App.tsx", "@acme/shared/internal")
+            .iter()
+            .any(|c| c.starts_with("packages/")));
+
+        let ui = modules.candidates("apps/web/src/App.tsx", "@acme/ui");
+        assert!(ui.contains(&"packages/ui/src/index".to_string()));
+        assert!(modules.candidates("src/lib.rs", "@acme/ui").is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn maps_go_imports_to_module_directories() {
+        let root = std::env::temp_dir().join(format!("ctx-gomod-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "go.work",
+            "go 1.22\n\nuse (\n\t./svc // service\n\t./lib\n)\n",
+        );
+        write(
+            "svc/go.mod",
+            "module github.com/acme/svc\n\nrequire github.com/acme/tools v1.0.0\n\nreplace github.com/acme/tools => ../tools\n",
+        );
+        write("lib/go.mod", "module \"github.com/acme/lib\"\n");
+
+        let modules = ProjectModules::load(&root, &[root.join("go.work")]);
+        assert_eq!(
+            modules.go_package_dir("github.com/acme/svc/internal/store"),
+            Some("svc/internal/store".to_string())
+        );
+        assert_eq!(
+            modules.go_package_dir("github.com/acme/svc"),
+            Some("svc".to_string())
+        );
+        assert_eq!(
+            modules.go_package_dir("github.com/acme/tools/fmt"),
+            Some("tools/fmt".to_string())
+        );
+        assert_eq!(
+            modules.go_package_dir("github.com/acme/lib"),
+            Some("lib".to_string())
+        );
+        // A longer module path is a different module, not a subpackage
+        assert_eq!(modules.go_package_dir("github.com/acme/svcx/api"), None);
+        assert_eq!(modules.go_package_dir("fmt"), None);
+        assert_eq!(
+            modules.go_import_paths("svc/internal/store"),
+            ["github.com/acme/svc/internal/store"]
+        );
+        assert_eq!(modules.go_import_paths("lib"), ["github.com/acme/lib"]);
+        assert!(modules.go_import_paths("svcx/api").is_empty());
+        assert!(modules.go_import_paths("").is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_ignores_include_globs_but_not_excludes() {
+        let root = std::env::temp_dir().join(format!("ctx-manifests-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("go.mod", "module github.com/acme/app\n");
+        write(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "paths": { "@/*": ["src/*"] } } }"#,
+        );
+        write("src/main.ts", "import { x } from '@/x';\n");
+        write("fixtures/lib/package.json", r#"{ "name": "lib" }"#);
+
+        let scan = ScanConfig {
+            include: vec!["src/**".to_string()],
+            exclude: vec!["fixtures/".to_string()],
+            ..ScanConfig::default()
+        };
+        let modules = ProjectModules::discover(&root, &scan).unwrap();
+        assert_eq!(
+            modules.go_package_dir("github.com/acme/app/src"),
+            Some("src".to_string())
+        );
+        assert_eq!(modules.candidates("src/main.ts", "@/x")[0], "src/x");
+        assert!(modules.candidates("src/main.ts", "lib").is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
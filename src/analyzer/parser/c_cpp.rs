@@ -199,6 +199,7 @@ fn extract_include(node: Node, source: &[u8], imports: &mut Vec<ExtractedImport>
             path: clean,
             kind: "include".to_string(),
             names: vec![],
+            line: node.start_position().row + 1,
         });
     }
 }
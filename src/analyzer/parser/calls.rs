@@ -0,0 +1,110 @@
+use tree_sitter::Node;
+
+use super::{node_text, ExtractedCall};
+
+// ===========================================================================
+// Call-site extractor (all languages)
+// ===========================================================================
+
+/// Walk the whole tree and record every function/method invocation
+pub fn extract_calls(node: Node, source: &[u8], language: &str, calls: &mut Vec<ExtractedCall>) {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if let Some(call) = extract_call(current, source, language) {
+            calls.push(call);
+        }
+
+        let mut cursor = current.walk();
+        for child in current.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+
+    calls.sort_by_key(|c| c.line);
+}
+
+/// Map a call node to its callee, per grammar
+fn extract_call(node: Node, source: &[u8], language: &str) -> Option<ExtractedCall> {
+    let (callee, kind) = match (language, node.kind()) {
+        ("typescript" | "javascript" | "tsx" | "jsx", "call_expression") => {
+            (node.child_by_field_name("function")?, "call")
+        }
+        ("typescript" | "javascript" | "tsx" | "jsx", "new_expression") => {
+            (node.child_by_field_name("constructor")?, "new")
+        }
+        // <Button /> renders a component; lowercase tags are DOM elements
+        (
+            "typescript" | "javascript" | "tsx" | "jsx",
+            "jsx_opening_element" | "jsx_self_closing_element",
+        ) => {
+            let name = node.child_by_field_name("name")?;
+            let last = node_text(name, source);
+            let last = last.rsplit('.').next().unwrap_or_default();
+            if !last.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return None;
+            }
+            (name, "render")
+        }
+        ("python", "call") => (node.child_by_field_name("function")?, "call"),
+        ("rust" | "go" | "c" | "cpp" | "cxx", "call_expression") => {
+            (node.child_by_field_name("function")?, "call")
+        }
+        ("java", "method_invocation") => (node.child_by_field_name("name")?, "call"),
+        ("java" | "c_sharp" | "csharp", "object_creation_expression") => {
+            (node.child_by_field_name("type")?, "new")
+        }
+        ("c_sharp" | "csharp", "invocation_expression") => {
+            (node.child_by_field_name("function")?, "call")
+        }
+        ("php", "function_call_expression") => (node.child_by_field_name("function")?, "call"),
+        ("php", "member_call_expression" | "scoped_call_expression") => {
+            (node.child_by_field_name("name")?, "call")
+        }
+        ("ruby", "call") => (node.child_by_field_name("method")?, "call"),
+        ("bash" | "shell" | "sh", "command") => (node.child_by_field_name("name")?, "call"),
+        // Construction looks like any other call: `Circle(radius: 1)`
+        ("swift" | "kotlin", "call_expression") => (node.named_child(0)?, "call"),
+        _ => return None,
+    };
+
+    let name = callee_name(&node_text(callee, source))?;
+    Some(ExtractedCall {
+        name,
+        kind: kind.to_string(),
+        line: node.start_position().row + 1,
+    })
+}
+
+/// Reduce a callee expression (`self.db.open`, `Foo::new`, `fmt.Println`,
+/// `parse::<T>`) to the name of the invoked symbol.
+fn callee_name(text: &str) -> Option<String> {
+    let without_generics = match text.find("::<").or_else(|| text.find('<')) {
+        Some(idx) => &text[..idx],
+        None => text,
+    };
+
+    let name = without_generics
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .find(|segment| !segment.is_empty())?;
+
+    if name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::callee_name;
+
+    #[test]
+    fn callee_name_strips_receivers_and_paths() {
+        assert_eq!(callee_name("self.db.open"), Some("open".to_string()));
+        assert_eq!(callee_name("Database::open"), Some("open".to_string()));
+        assert_eq!(callee_name("fmt.Println"), Some("Println".to_string()));
+        assert_eq!(callee_name("parse::<u32>"), Some("parse".to_string()));
+        assert_eq!(callee_name("$obj->save"), Some("save".to_string()));
+        assert_eq!(callee_name("()"), None);
+    }
+}
@@ -378,5 +378,6 @@ fn extract_import_spec(node: Node, source: &[u8]) -> Option<ExtractedImport> {
         path,
         kind: "import".to_string(),
         names,
+        line: node.start_position().row + 1,
     })
 }
@@ -220,6 +220,7 @@ fn extract_java_import(node: Node, source: &[u8], imports: &mut Vec<ExtractedImp
             path: node_text(name, source),
             kind: "import".to_string(),
             names: vec![],
+            line: node.start_position().row + 1,
         });
     }
 }
@@ -232,6 +233,7 @@ fn extract_csharp_using(node: Node, source: &[u8], imports: &mut Vec<ExtractedIm
             path: node_text(name, source),
             kind: "using".to_string(),
             names: vec![],
+            line: node.start_position().row + 1,
         });
     }
 }
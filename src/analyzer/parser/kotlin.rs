@@ -0,0 +1,234 @@
+use tree_sitter::Node;
+
+use super::{node_text, ExtractedImport, ExtractedSymbol};
+use crate::db::models::SymbolKind;
+
+// ===========================================================================
+// Kotlin extractor
+// ===========================================================================
+
+pub fn extract_kotlin(
+    node: Node,
+    source: &[u8],
+    symbols: &mut Vec<ExtractedSymbol>,
+    imports: &mut Vec<ExtractedImport>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            // class / interface / enum class share one node kind; objects have their own
+            "class_declaration" | "object_declaration" => {
+                if let Some(sym) = extract_kotlin_type(child, source) {
+                    symbols.push(sym);
+                }
+            }
+            "function_declaration" => {
+                if let Some(sym) = extract_kotlin_function(child, source, SymbolKind::Function) {
+                    symbols.push(sym);
+                }
+            }
+            "type_alias" => {
+                if let Some(name) = child.child_by_field_name("type") {
+                    symbols.push(ExtractedSymbol {
+                        name: node_text(name, source),
+                        kind: SymbolKind::TypeAlias,
+                        start_line: child.start_position().row + 1,
+                        end_line: child.end_position().row + 1,
+                        signature: declaration_head(child, source, |_| false),
+                        children: vec![],
+                    });
+                }
+            }
+            // Top-level `val`s; `var`s are mutable state, not API
+            "property_declaration" if is_val(child) => {
+                if let Some(sym) = extract_kotlin_property(child, source) {
+                    symbols.push(sym);
+                }
+            }
+            "import" => extract_kotlin_import(child, source, imports),
+            _ => {}
+        }
+    }
+}
+
+/// A class, interface, enum class or object with its members. Companion
+/// object members are listed on the enclosing type, like statics.
+fn extract_kotlin_type(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
+    let name = node_text(node.child_by_field_name("name")?, source);
+
+    let mut children = Vec::new();
+    if let Some(body) = type_body(node) {
+        extract_kotlin_members(body, source, &mut children);
+    }
+
+    let mut cursor = node.walk();
+    let kind = if node.children(&mut cursor).any(|c| c.kind() == "interface") {
+        SymbolKind::Interface
+    } else if has_modifier(node, source, "enum") {
+        SymbolKind::Enum
+    } else {
+        SymbolKind::Class
+    };
+
+    Some(ExtractedSymbol {
+        name,
+        kind,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        signature: declaration_head(node, source, |kind| {
+            matches!(kind, "class_body" | "enum_class_body")
+        }),
+        children,
+    })
+}
+
+fn extract_kotlin_members(body: Node, source: &[u8], children: &mut Vec<ExtractedSymbol>) {
+    let mut cursor = body.walk();
+    for child in body.children(&mut cursor) {
+        match child.kind() {
+            "function_declaration" => {
+                if let Some(method) = extract_kotlin_function(child, source, SymbolKind::Method) {
+                    children.push(method);
+                }
+            }
+            "secondary_constructor" => children.push(ExtractedSymbol {
+                name: "constructor".to_string(),
+                kind: SymbolKind::Method,
+                start_line: child.start_position().row + 1,
+                end_line: child.end_position().row + 1,
+                signature: declaration_head(child, source, |kind| kind == "block"),
+                children: vec![],
+            }),
+            "property_declaration" => {
+                if let Some(property) = extract_kotlin_property(child, source) {
+                    children.push(property);
+                }
+            }
+            "companion_object" => {
+                if let Some(companion_body) = type_body(child) {
+                    extract_kotlin_members(companion_body, source, children);
+                }
+            }
+            // Nested types
+            "class_declaration" | "object_declaration" => {
+                if let Some(inner) = extract_kotlin_type(child, source) {
+                    children.push(inner);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn extract_kotlin_function(node: Node, source: &[u8], kind: SymbolKind) -> Option<ExtractedSymbol> {
+    let name = node_text(node.child_by_field_name("name")?, source);
+    Some(ExtractedSymbol {
+        name,
+        kind,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        signature: declaration_head(node, source, |kind| kind == "function_body"),
+        children: vec![],
+    })
+}
+
+fn extract_kotlin_property(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
+    let mut cursor = node.walk();
+    let declaration = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "variable_declaration")?;
+    let mut name_cursor = declaration.walk();
+    let name = declaration
+        .named_children(&mut name_cursor)
+        .find(|c| c.kind() == "identifier")?;
+    Some(ExtractedSymbol {
+        name: node_text(name, source),
+        kind: SymbolKind::Constant,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        signature: declaration_head(node, source, |kind| {
+            matches!(kind, "=" | "getter" | "setter" | "property_delegate")
+        }),
+        children: vec![],
+    })
+}
+
+/// Declaration text on one line, from after its annotations up to the first
+/// child for which `stop` holds (the body or initial value)
+fn declaration_head(node: Node, source: &[u8], stop: impl Fn(&str) -> bool) -> String {
+    let mut start = node.start_byte();
+    let mut end = node.end_byte();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "modifiers" {
+            let mut modifiers = child.walk();
+            for modifier in child.children(&mut modifiers) {
+                if modifier.kind() == "annotation" {
+                    start = modifier.end_byte();
+                }
+            }
+        } else if stop(child.kind()) {
+            end = child.start_byte();
+            break;
+        }
+    }
+    let text = String::from_utf8_lossy(&source[start..end]);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn type_body(node: Node) -> Option<Node> {
+    let mut cursor = node.walk();
+    let body = node
+        .children(&mut cursor)
+        .find(|c| matches!(c.kind(), "class_body" | "enum_class_body"));
+    body
+}
+
+fn has_modifier(node: Node, source: &[u8], modifier: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "modifiers")
+        .any(|m| {
+            node_text(m, source)
+                .split_whitespace()
+                .any(|w| w == modifier)
+        });
+    found
+}
+
+fn is_val(node: Node) -> bool {
+    let mut cursor = node.walk();
+    let is_val = node.children(&mut cursor).any(|c| c.kind() == "val");
+    is_val
+}
+
+fn extract_kotlin_import(node: Node, source: &[u8], imports: &mut Vec<ExtractedImport>) {
+    // import com.acme.data.Repo
+    // import com.acme.util.*
+    // import kotlinx.coroutines.launch as go
+    let mut cursor = node.walk();
+    let Some(path) = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "qualified_identifier")
+    else {
+        return;
+    };
+    let path = node_text(path, source);
+
+    let mut star_cursor = node.walk();
+    let is_wildcard = node.children(&mut star_cursor).any(|c| c.kind() == "*");
+    let (path, names) = if is_wildcard {
+        (format!("{}.*", path), vec![])
+    } else {
+        let name = path.rsplit('.').next().unwrap_or_default().to_string();
+        (path, vec![name])
+    };
+
+    imports.push(ExtractedImport {
+        path,
+        kind: "import".to_string(),
+        names,
+        line: node.start_position().row + 1,
+    });
+}
@@ -0,0 +1,471 @@
+use tree_sitter::Node;
+
+use super::{node_text, ExtractedMetrics};
+use crate::db::models::SymbolMetrics;
+
+// ===========================================================================
+// Function complexity metrics (all languages)
+// ===========================================================================
+
+/// Walk the whole tree and measure every function, method and lambda body
+pub fn extract_metrics(
+    node: Node,
+    source: &[u8],
+    language: &str,
+    metrics: &mut Vec<ExtractedMetrics>,
+) {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if is_function(language, current.kind()) || is_lambda(language, current.kind()) {
+            metrics.push(ExtractedMetrics {
+                start_line: current.start_position().row + 1,
+                end_line: current.end_position().row + 1,
+                metrics: measure(current, source, language),
+            });
+        }
+
+        let mut cursor = current.walk();
+        for child in current.children(&mut cursor) {
+            stack.push(child);
+        }
+    }
+
+    metrics.sort_by_key(|m| m.start_line);
+}
+
+/// Complexity, nesting and size of one function. Lambdas count toward the
+/// enclosing function; nested named functions are measured on their own.
+fn measure(function: Node, source: &[u8], language: &str) -> SymbolMetrics {
+    let mut metrics = SymbolMetrics {
+        complexity: 1,
+        max_nesting: 0,
+        param_count: count_parameters(function, source, language),
+        statement_count: 0,
+    };
+
+    let mut cursor = function.walk();
+    let mut stack: Vec<(Node, i64)> = function.children(&mut cursor).map(|c| (c, 0)).collect();
+    while let Some((node, depth)) = stack.pop() {
+        if is_function(language, node.kind()) {
+            continue;
+        }
+        if is_decision(node, language) {
+            metrics.complexity += 1;
+        }
+        if is_block(language, node.kind()) {
+            let mut cursor = node.walk();
+            metrics.statement_count += node
+                .named_children(&mut cursor)
+                .filter(|c| is_statement(c.kind()))
+                .count() as i64;
+        }
+        let depth = if opens_block(language, node.kind()) && !is_else_if(node) {
+            depth + 1
+        } else {
+            depth
+        };
+        metrics.max_nesting = metrics.max_nesting.max(depth);
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push((child, depth));
+        }
+    }
+
+    metrics
+}
+
+/// Declared parameters, not counting receivers (`self`, `this`, `cls`)
+fn count_parameters(function: Node, source: &[u8], language: &str) -> i64 {
+    // `x => x * 2`
+    if function.child_by_field_name("parameter").is_some() {
+        return 1;
+    }
+    // Swift parameters hang off the declaration itself
+    if language == "swift" {
+        let mut cursor = function.walk();
+        return function
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == "parameter")
+            .count() as i64;
+    }
+    if language == "kotlin" {
+        let mut cursor = function.walk();
+        let Some(list) = function
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "function_value_parameters")
+        else {
+            return 0;
+        };
+        let mut params = list.walk();
+        return list
+            .named_children(&mut params)
+            .filter(|c| c.kind() == "parameter")
+            .count() as i64;
+    }
+
+    // C/C++ keep the list on the (possibly nested) function declarator
+    let mut node = function;
+    let list = loop {
+        if let Some(list) = node.child_by_field_name("parameters") {
+            break list;
+        }
+        match node.child_by_field_name("declarator") {
+            Some(declarator) => node = declarator,
+            None => return 0,
+        }
+    };
+
+    let mut cursor = list.walk();
+    let mut count = 0;
+    for (i, param) in list.named_children(&mut cursor).enumerate() {
+        count += match param.kind() {
+            "self_parameter"
+            | "receiver_parameter"
+            | "keyword_separator"
+            | "positional_separator" => 0,
+            kind if kind.contains("comment") => 0,
+            // `a, b int` declares two parameters
+            "parameter_declaration" if language == "go" => {
+                let mut names = param.walk();
+                param
+                    .children_by_field_name("name", &mut names)
+                    .count()
+                    .max(1) as i64
+            }
+            // `f(void)` takes none
+            "parameter_declaration" if node_text(param, source) == "void" => 0,
+            "identifier"
+                if i == 0
+                    && language == "python"
+                    && matches!(node_text(param, source).as_str(), "self" | "cls") =>
+            {
+                0
+            }
+            _ => 1,
+        };
+    }
+    count
+}
+
+/// Named function and method definitions, per grammar
+fn is_function(language: &str, kind: &str) -> bool {
+    match language {
+        "rust" => kind == "function_item",
+        "typescript" | "javascript" | "tsx" | "jsx" => matches!(
+            kind,
+            "function_declaration" | "generator_function_declaration" | "method_definition"
+        ),
+        "python" | "c" | "cpp" | "cxx" | "bash" | "shell" | "sh" => kind == "function_definition",
+        "go" => matches!(kind, "function_declaration" | "method_declaration"),
+        "java" => matches!(kind, "method_declaration" | "constructor_declaration"),
+        "c_sharp" | "csharp" => matches!(
+            kind,
+            "method_declaration" | "constructor_declaration" | "local_function_statement"
+        ),
+        "php" => matches!(kind, "function_definition" | "method_declaration"),
+        "ruby" => matches!(kind, "method" | "singleton_method"),
+        "swift" => matches!(
+            kind,
+            "function_declaration" | "init_declaration" | "deinit_declaration"
+        ),
+        "kotlin" => matches!(kind, "function_declaration" | "secondary_constructor"),
+        _ => false,
+    }
+}
+
+/// Anonymous functions, which belong to the function they appear in
+fn is_lambda(language: &str, kind: &str) -> bool {
+    match language {
+        "rust" => kind == "closure_expression",
+        "typescript" | "javascript" | "tsx" | "jsx" => matches!(
+            kind,
+            "arrow_function" | "function_expression" | "function" | "generator_function"
+        ),
+        "python" => kind == "lambda",
+        "go" => kind == "func_literal",
+        "c" | "cpp" | "cxx" | "java" | "c_sharp" | "csharp" => kind == "lambda_expression",
+        "php" => matches!(kind, "anonymous_function" | "arrow_function"),
+        "ruby" => kind == "lambda",
+        "swift" => kind == "lambda_literal",
+        "kotlin" => matches!(kind, "lambda_literal" | "anonymous_function"),
+        _ => false,
+    }
+}
+
+/// Nodes that add an independent path through the function
+fn is_decision(node: Node, language: &str) -> bool {
+    let kind = node.kind();
+    if matches!(kind, "binary_expression" | "binary" | "boolean_operator") {
+        return node
+            .child_by_field_name("operator")
+            .is_some_and(|op| matches!(op.kind(), "&&" | "||" | "??" | "?:" | "and" | "or"));
+    }
+
+    match language {
+        "rust" => matches!(
+            kind,
+            "if_expression" | "while_expression" | "for_expression" | "match_arm"
+        ),
+        "typescript" | "javascript" | "tsx" | "jsx" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "for_in_statement"
+                | "while_statement"
+                | "do_statement"
+                | "switch_case"
+                | "catch_clause"
+                | "ternary_expression"
+        ),
+        "python" => matches!(
+            kind,
+            "if_statement"
+                | "elif_clause"
+                | "for_statement"
+                | "while_statement"
+                | "except_clause"
+                | "conditional_expression"
+                | "if_clause"
+                | "case_clause"
+        ),
+        "go" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "expression_case"
+                | "type_case"
+                | "communication_case"
+        ),
+        "c" | "cpp" | "cxx" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "for_range_loop"
+                | "while_statement"
+                | "do_statement"
+                | "case_statement"
+                | "conditional_expression"
+                | "catch_clause"
+        ),
+        "java" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "enhanced_for_statement"
+                | "while_statement"
+                | "do_statement"
+                | "switch_label"
+                | "catch_clause"
+                | "ternary_expression"
+        ),
+        "c_sharp" | "csharp" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "foreach_statement"
+                | "while_statement"
+                | "do_statement"
+                | "switch_section"
+                | "catch_clause"
+                | "conditional_expression"
+        ),
+        "php" => matches!(
+            kind,
+            "if_statement"
+                | "else_if_clause"
+                | "for_statement"
+                | "foreach_statement"
+                | "while_statement"
+                | "do_statement"
+                | "case_statement"
+                | "catch_clause"
+                | "conditional_expression"
+        ),
+        "ruby" => matches!(
+            kind,
+            "if" | "elsif"
+                | "unless"
+                | "while"
+                | "until"
+                | "for"
+                | "when"
+                | "rescue"
+                | "conditional"
+                | "if_modifier"
+                | "unless_modifier"
+                | "while_modifier"
+                | "until_modifier"
+        ),
+        "bash" | "shell" | "sh" => matches!(
+            kind,
+            "if_statement"
+                | "elif_clause"
+                | "for_statement"
+                | "c_style_for_statement"
+                | "while_statement"
+                | "case_item"
+        ),
+        "swift" => matches!(
+            kind,
+            "if_statement"
+                | "guard_statement"
+                | "for_statement"
+                | "while_statement"
+                | "repeat_while_statement"
+                | "switch_entry"
+                | "catch_block"
+                | "ternary_expression"
+                | "conjunction_expression"
+                | "disjunction_expression"
+                | "nil_coalescing_expression"
+        ),
+        "kotlin" => matches!(
+            kind,
+            "if_expression"
+                | "for_statement"
+                | "while_statement"
+                | "do_while_statement"
+                | "when_entry"
+                | "catch_block"
+        ),
+        _ => false,
+    }
+}
+
+/// Control structures whose body is one level deeper
+fn opens_block(language: &str, kind: &str) -> bool {
+    match language {
+        "rust" => matches!(
+            kind,
+            "if_expression"
+                | "while_expression"
+                | "for_expression"
+                | "loop_expression"
+                | "match_expression"
+        ),
+        "typescript" | "javascript" | "tsx" | "jsx" | "c" | "cpp" | "cxx" | "php" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "for_in_statement"
+                | "for_range_loop"
+                | "foreach_statement"
+                | "while_statement"
+                | "do_statement"
+                | "switch_statement"
+                | "try_statement"
+        ),
+        "python" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "while_statement"
+                | "try_statement"
+                | "with_statement"
+                | "match_statement"
+        ),
+        "go" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "expression_switch_statement"
+                | "type_switch_statement"
+                | "select_statement"
+        ),
+        "java" | "c_sharp" | "csharp" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "enhanced_for_statement"
+                | "foreach_statement"
+                | "while_statement"
+                | "do_statement"
+                | "switch_expression"
+                | "switch_statement"
+                | "try_statement"
+                | "try_with_resources_statement"
+        ),
+        "ruby" => matches!(
+            kind,
+            "if" | "unless" | "while" | "until" | "for" | "case" | "begin"
+        ),
+        "bash" | "shell" | "sh" => matches!(
+            kind,
+            "if_statement"
+                | "for_statement"
+                | "c_style_for_statement"
+                | "while_statement"
+                | "case_statement"
+        ),
+        "swift" => matches!(
+            kind,
+            "if_statement"
+                | "guard_statement"
+                | "for_statement"
+                | "while_statement"
+                | "repeat_while_statement"
+                | "switch_statement"
+                | "do_statement"
+        ),
+        "kotlin" => matches!(
+            kind,
+            "if_expression"
+                | "for_statement"
+                | "while_statement"
+                | "do_while_statement"
+                | "when_expression"
+                | "try_expression"
+        ),
+        _ => false,
+    }
+}
+
+/// Statement lists; each of their children counts as one statement
+fn is_block(language: &str, kind: &str) -> bool {
+    match language {
+        "rust" | "python" | "go" | "c_sharp" | "csharp" => kind == "block",
+        "typescript" | "javascript" | "tsx" | "jsx" => kind == "statement_block",
+        "c" | "cpp" | "cxx" | "php" => kind == "compound_statement",
+        "java" => matches!(
+            kind,
+            "block" | "constructor_body" | "switch_block_statement_group"
+        ),
+        "ruby" => matches!(
+            kind,
+            "body_statement" | "then" | "else" | "do" | "block_body" | "begin" | "ensure"
+        ),
+        "bash" | "shell" | "sh" => matches!(kind, "compound_statement" | "do_group"),
+        "swift" => kind == "statements",
+        "kotlin" => matches!(kind, "block" | "lambda_literal"),
+        _ => false,
+    }
+}
+
+/// Block children that are not statements
+fn is_statement(kind: &str) -> bool {
+    !kind.contains("comment")
+        && !matches!(
+            kind,
+            "label"
+                | "attribute_item"
+                | "switch_label"
+                | "else"
+                | "rescue"
+                | "ensure"
+                | "lambda_parameters"
+        )
+}
+
+/// `else if` continues the chain rather than nesting inside it
+fn is_else_if(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    parent.kind() == "else_clause"
+        || (parent.kind() == node.kind()
+            && (parent.child_by_field_name("alternative") == Some(node)
+                || node
+                    .prev_sibling()
+                    .is_some_and(|prev| prev.kind() == "else")))
+}
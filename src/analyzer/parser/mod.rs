@@ -1,9 +1,13 @@
 mod c_cpp;
+mod calls;
 mod go;
 mod java_sharp;
+mod kotlin;
+mod metrics;
 mod python;
 mod rust_ext;
 mod scripting;
+mod swift;
 mod typescript;
 
 #[cfg(test)]
@@ -13,14 +17,18 @@ mod tests;
 use anyhow::Result;
 use tree_sitter::{Language, Node, Parser};
 
-use crate::db::models::SymbolKind;
+use crate::db::models::{SymbolKind, SymbolMetrics};
 
 pub use c_cpp::extract_c_cpp;
+pub use calls::extract_calls;
 pub use go::extract_go;
 pub use java_sharp::extract_java_csharp;
+pub use kotlin::extract_kotlin;
+pub use metrics::extract_metrics;
 pub use python::extract_python;
 pub use rust_ext::extract_rust;
 pub use scripting::extract_scripting;
+pub use swift::extract_swift;
 pub use typescript::extract_ts_js;
 
 /// A symbol extracted from parsing a file
@@ -40,19 +48,39 @@ pub struct ExtractedImport {
     pub path: String,
     pub kind: String, // "import", "require", "use"
     pub names: Vec<String>,
+    pub line: usize,
+}
+
+/// A call site (function/method invocation) extracted from a file
+#[derive(Debug, Clone)]
+pub struct ExtractedCall {
+    pub name: String,
+    pub kind: String, // "call", "new", "render"
+    pub line: usize,
+}
+
+/// Metrics of the function spanning `start_line..=end_line`
+#[derive(Debug, Clone)]
+pub struct ExtractedMetrics {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub metrics: SymbolMetrics,
 }
 
 /// Parse result for a single file
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ParseResult {
     pub symbols: Vec<ExtractedSymbol>,
     pub imports: Vec<ExtractedImport>,
+    pub calls: Vec<ExtractedCall>,
+    pub metrics: Vec<ExtractedMetrics>,
 }
 
 /// Get tree-sitter language for a given language name
 fn get_language(lang: &str) -> Option<Language> {
     match lang {
-        "typescript" | "tsx" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        "typescript" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        "tsx" => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
         "javascript" | "jsx" => Some(tree_sitter_javascript::LANGUAGE.into()),
         "python" => Some(tree_sitter_python::LANGUAGE.into()),
         "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
@@ -64,6 +92,8 @@ fn get_language(lang: &str) -> Option<Language> {
         "php" => Some(tree_sitter_php::LANGUAGE_PHP.into()),
         "ruby" => Some(tree_sitter_ruby::LANGUAGE.into()),
         "bash" | "shell" | "sh" => Some(tree_sitter_bash::LANGUAGE.into()),
+        "swift" => Some(tree_sitter_swift::LANGUAGE.into()),
+        "kotlin" => Some(tree_sitter_kotlin_ng::LANGUAGE.into()),
         _ => None,
     }
 }
@@ -72,12 +102,7 @@ fn get_language(lang: &str) -> Option<Language> {
 pub fn parse_file(source: &str, language: &str) -> Result<ParseResult> {
     let ts_lang = match get_language(language) {
         Some(l) => l,
-        None => {
-            return Ok(ParseResult {
-                symbols: vec![],
-                imports: vec![],
-            })
-        }
+        None => return Ok(ParseResult::default()),
     };
 
     let mut parser = Parser::new();
@@ -85,12 +110,7 @@ pub fn parse_file(source: &str, language: &str) -> Result<ParseResult> {
 
     let tree = match parser.parse(source, None) {
         Some(t) => t,
-        None => {
-            return Ok(ParseResult {
-                symbols: vec![],
-                imports: vec![],
-            })
-        }
+        None => return Ok(ParseResult::default()),
     };
 
     let root = tree.root_node();
@@ -113,10 +133,23 @@ pub fn parse_file(source: &str, language: &str) -> Result<ParseResult> {
         "php" | "ruby" | "bash" | "shell" | "sh" => {
             extract_scripting(root, source_bytes, &mut symbols, &mut imports, language)
         }
+        "swift" => extract_swift(root, source_bytes, &mut symbols, &mut imports),
+        "kotlin" => extract_kotlin(root, source_bytes, &mut symbols, &mut imports),
         _ => {}
     }
 
-    Ok(ParseResult { symbols, imports })
+    let mut calls = Vec::new();
+    extract_calls(root, source_bytes, language, &mut calls);
+
+    let mut metrics = Vec::new();
+    extract_metrics(root, source_bytes, language, &mut metrics);
+
+    Ok(ParseResult {
+        symbols,
+        imports,
+        calls,
+        metrics,
+    })
 }
 
 // ===========================================================================
@@ -205,6 +205,7 @@ fn extract_python_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
                 path,
                 kind: "import".to_string(),
                 names,
+                line: node.start_position().row + 1,
             });
         }
     } else {
@@ -217,6 +218,7 @@ fn extract_python_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
                     path,
                     kind: "import".to_string(),
                     names: vec![],
+                    line: node.start_position().row + 1,
                 });
             }
         }
@@ -230,6 +232,7 @@ fn extract_python_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
                 path: parts[1].to_string(),
                 kind: "import".to_string(),
                 names,
+                line: node.start_position().row + 1,
             });
         }
     }
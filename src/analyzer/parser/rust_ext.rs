@@ -90,15 +90,17 @@ pub fn extract_rust(
                 }
             }
             "use_declaration" => {
-                let text = node_text(child, source);
-                let path = text
-                    .trim_start_matches("use ")
-                    .trim_end_matches(';')
-                    .to_string();
+                // The argument skips `pub`/`pub(crate)` on re-exports
+                let Some(argument) = child.child_by_field_name("argument") else {
+                    continue;
+                };
+                let path = node_text(argument, source);
+                let names = rust_use_names(&path);
                 imports.push(ExtractedImport {
                     path,
                     kind: "use".to_string(),
-                    names: vec![],
+                    names,
+                    line: child.start_position().row + 1,
                 });
             }
             "mod_item" => {
@@ -119,6 +121,7 @@ pub fn extract_rust(
                             path: n,
                             kind: "mod".to_string(),
                             names: vec![],
+                            line: child.start_position().row + 1,
                         });
                     }
                 }
@@ -170,3 +173,22 @@ fn extract_rust_impl(node: Node, source: &[u8], symbols: &mut Vec<ExtractedSymbo
         }
     }
 }
+
+/// Names a `use` path imports (`a::b::{C, d as e}` → `C`, `d`). Aliases are
+/// dropped: importers are matched against the defining name.
+fn rust_use_names(path: &str) -> Vec<String> {
+    let list = match path.find('{') {
+        Some(open) => path[open + 1..].replace(['{', '}'], ","),
+        None => path.to_string(),
+    };
+
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty() && !item.ends_with("::"))
+        .filter_map(|item| {
+            let path = item.split_once(" as ").map_or(item, |(original, _)| original);
+            let name = path.rsplit("::").next().unwrap_or(path).trim();
+            (name != "self" && name != "_").then(|| name.to_string())
+        })
+        .collect()
+}
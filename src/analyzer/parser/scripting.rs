@@ -2,6 +2,8 @@ use super::{node_text, ExtractedImport, ExtractedSymbol};
 use crate::db::models::SymbolKind;
 use tree_sitter::Node;
 
+// Arms are grouped by language; guards would split each group across the match
+#[allow(clippy::collapsible_match)]
 pub fn extract_scripting(
     node: Node,
     source: &[u8],
@@ -221,6 +223,7 @@ fn extract_ruby_require(node: Node, source: &[u8], imports: &mut Vec<ExtractedIm
                     path,
                     kind: name,
                     names: vec![],
+                    line: node.start_position().row + 1,
                 })
             }
         }
@@ -0,0 +1,254 @@
+use tree_sitter::Node;
+
+use super::{node_text, ExtractedImport, ExtractedSymbol};
+use crate::db::models::SymbolKind;
+
+// ===========================================================================
+// Swift extractor
+// ===========================================================================
+
+pub fn extract_swift(
+    node: Node,
+    source: &[u8],
+    symbols: &mut Vec<ExtractedSymbol>,
+    imports: &mut Vec<ExtractedImport>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            // class / struct / enum / actor / extension share one node kind
+            "class_declaration" => extract_swift_type(child, source, symbols),
+            "protocol_declaration" => {
+                if let Some(sym) = extract_swift_protocol(child, source) {
+                    symbols.push(sym);
+                }
+            }
+            "function_declaration" => {
+                if let Some(sym) = extract_swift_function(child, source, SymbolKind::Function) {
+                    symbols.push(sym);
+                }
+            }
+            "typealias_declaration" => {
+                if let Some(name) = child.child_by_field_name("name") {
+                    let n = node_text(name, source);
+                    symbols.push(ExtractedSymbol {
+                        name: n.clone(),
+                        kind: SymbolKind::TypeAlias,
+                        start_line: child.start_position().row + 1,
+                        end_line: child.end_position().row + 1,
+                        signature: format!("typealias {}", n),
+                        children: vec![],
+                    });
+                }
+            }
+            // Top-level `let` constants; `var`s are mutable state, not API
+            "property_declaration" if is_let(child, source) => {
+                if let Some(sym) = extract_swift_property(child, source) {
+                    symbols.push(ExtractedSymbol {
+                        kind: SymbolKind::Constant,
+                        ..sym
+                    });
+                }
+            }
+            "import_declaration" => extract_swift_import(child, source, imports),
+            _ => {}
+        }
+    }
+}
+
+/// Push a type with its members, or for an `extension` its methods as
+/// `extension T :: func …`, like Rust impl blocks
+fn extract_swift_type(node: Node, source: &[u8], symbols: &mut Vec<ExtractedSymbol>) {
+    let Some(name_node) = node.child_by_field_name("name") else {
+        return;
+    };
+    let name = node_text(name_node, source);
+    let keyword = node
+        .child_by_field_name("declaration_kind")
+        .map(|k| node_text(k, source))
+        .unwrap_or_else(|| "class".to_string());
+
+    let mut children = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            match child.kind() {
+                "function_declaration" | "init_declaration" => {
+                    if let Some(method) = extract_swift_function(child, source, SymbolKind::Method)
+                    {
+                        children.push(method);
+                    }
+                }
+                "property_declaration" => {
+                    if let Some(property) = extract_swift_property(child, source) {
+                        children.push(property);
+                    }
+                }
+                // Nested types
+                "class_declaration" => extract_swift_type(child, source, &mut children),
+                "protocol_declaration" => {
+                    if let Some(inner) = extract_swift_protocol(child, source) {
+                        children.push(inner);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if keyword == "extension" {
+        for mut member in children {
+            member.signature = format!("extension {} :: {}", name, member.signature);
+            symbols.push(member);
+        }
+        return;
+    }
+
+    let kind = match keyword.as_str() {
+        "struct" => SymbolKind::Struct,
+        "enum" => SymbolKind::Enum,
+        _ => SymbolKind::Class,
+    };
+    symbols.push(ExtractedSymbol {
+        signature: format!("{} {}{}", keyword, name, inheritance(node, source)),
+        name,
+        kind,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        children,
+    });
+}
+
+fn extract_swift_protocol(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
+    let name = node_text(node.child_by_field_name("name")?, source);
+
+    let mut children = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            if child.kind() == "protocol_function_declaration" {
+                if let Some(method) = extract_swift_function(child, source, SymbolKind::Method) {
+                    children.push(method);
+                }
+            }
+        }
+    }
+
+    Some(ExtractedSymbol {
+        signature: format!("protocol {}{}", name, inheritance(node, source)),
+        name,
+        kind: SymbolKind::Interface,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        children,
+    })
+}
+
+fn extract_swift_function(node: Node, source: &[u8], kind: SymbolKind) -> Option<ExtractedSymbol> {
+    let name = node_text(node.child_by_field_name("name")?, source);
+    Some(ExtractedSymbol {
+        name,
+        kind,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        signature: declaration_head(node, source, "body"),
+        children: vec![],
+    })
+}
+
+fn extract_swift_property(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
+    let pattern = node.child_by_field_name("name")?;
+    let name = node_text(pattern.child_by_field_name("bound_identifier")?, source);
+    let field = if node.child_by_field_name("computed_value").is_some() {
+        "computed_value"
+    } else {
+        "value"
+    };
+    Some(ExtractedSymbol {
+        name,
+        kind: SymbolKind::Constant,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        signature: declaration_head(node, source, field)
+            .trim_end_matches('=')
+            .trim_end()
+            .to_string(),
+        children: vec![],
+    })
+}
+
+/// Declaration text before its body or initial value, on one line
+fn declaration_head(node: Node, source: &[u8], body_field: &str) -> String {
+    let end = node
+        .child_by_field_name(body_field)
+        .map(|body| body.start_byte())
+        .unwrap_or(node.end_byte());
+    let text = String::from_utf8_lossy(&source[node.start_byte()..end]);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `: Base, Proto` of a type or protocol declaration
+fn inheritance(node: Node, source: &[u8]) -> String {
+    let mut cursor = node.walk();
+    let parents: Vec<String> = node
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "inheritance_specifier")
+        .map(|c| node_text(c, source))
+        .collect();
+    if parents.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", parents.join(", "))
+    }
+}
+
+fn is_let(node: Node, source: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    let is_let = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "value_binding_pattern")
+        .and_then(|binding| binding.child_by_field_name("mutability"))
+        .is_some_and(|m| node_text(m, source) == "let");
+    is_let
+}
+
+fn extract_swift_import(node: Node, source: &[u8], imports: &mut Vec<ExtractedImport>) {
+    // import Foundation
+    // @testable import App
+    // import struct Geometry.Point
+    let mut cursor = node.walk();
+    let Some(path) = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "identifier")
+    else {
+        return;
+    };
+    let mut path_cursor = path.walk();
+    let parts: Vec<String> = path
+        .named_children(&mut path_cursor)
+        .map(|part| node_text(part, source))
+        .collect();
+    let Some(module) = parts.first() else {
+        return;
+    };
+
+    // A kinded import names one declaration inside the module
+    let mut kind_cursor = node.walk();
+    let is_kinded = node.children(&mut kind_cursor).any(|c| {
+        matches!(
+            c.kind(),
+            "typealias" | "struct" | "class" | "enum" | "protocol" | "let" | "var" | "func"
+        )
+    });
+    let names = match parts.last() {
+        Some(last) if is_kinded && parts.len() > 1 => vec![last.clone()],
+        _ => vec![],
+    };
+
+    imports.push(ExtractedImport {
+        path: module.clone(),
+        kind: "import".to_string(),
+        names,
+        line: node.start_position().row + 1,
+    });
+}
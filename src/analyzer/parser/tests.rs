@@ -80,6 +80,26 @@ use anyhow::{Context, Result};
         assert_eq!(result.imports.len(), 2);
         assert_eq!(result.imports[0].path, "std::path::PathBuf");
         assert_eq!(result.imports[0].kind, "use");
+        assert_eq!(result.imports[0].names, vec!["PathBuf"]);
+        assert_eq!(result.imports[1].names, vec!["Context", "Result"]);
+    }
+
+    #[test]
+    fn test_parse_rust_use_names() {
+        let source = r#"
+use crate::db::{models::{Symbol, SymbolKind}, Database as Db, self};
+use super::graph::*;
+pub use parser::parse_file;
+use std::io::{self, Write as _};
+"#;
+        let result = parse_file(source, "rust").unwrap();
+        let names: Vec<&Vec<String>> = result.imports.iter().map(|i| &i.names).collect();
+        // Aliases keep the defining name, which is what importers are matched by
+        assert_eq!(names[0], &vec!["Symbol", "SymbolKind", "Database"]);
+        assert_eq!(names[1], &vec!["*"]);
+        assert_eq!(result.imports[2].path, "parser::parse_file");
+        assert_eq!(names[2], &vec!["parse_file"]);
+        assert_eq!(names[3], &vec!["Write"]);
     }
 
     #[test]
@@ -159,6 +179,58 @@ import path from 'path';
         assert_eq!(result.imports[1].path, "path");
     }
 
+    #[test]
+    fn test_parse_ts_reexports() {
+        let source = r#"
+export { Button, Input } from './controls';
+export * from './layout';
+export * as icons from './icons';
+"#;
+        let result = parse_file(source, "typescript").unwrap();
+        assert_eq!(result.imports.len(), 3);
+        assert_eq!(result.imports[0].kind, "re-export");
+        assert_eq!(result.imports[0].names, vec!["Button", "Input"]);
+        assert_eq!(result.imports[1].kind, "re-export-all");
+        assert_eq!(result.imports[1].path, "./layout");
+        assert_eq!(result.imports[1].names, vec!["*"]);
+        assert_eq!(result.imports[2].kind, "re-export-all");
+        assert_eq!(result.imports[2].names, vec!["* as icons"]);
+    }
+
+    #[test]
+    fn test_parse_require_and_dynamic_import() {
+        let source = r#"
+const path = require('path');
+const { readFile, write: writeFile } = require("./fs-utils");
+require('./polyfills');
+import legacy = require('./legacy');
+
+async function load() {
+    const { render } = await import('./lazy');
+    return render();
+}
+const Page = lazy(() => import('./pages/Home'));
+const dynamic = import(`./locales/${lang}`);
+"#;
+        let result = parse_file(source, "typescript").unwrap();
+        let find = |path: &str| result.imports.iter().find(|i| i.path == path).unwrap();
+
+        assert_eq!(find("path").kind, "require");
+        assert_eq!(find("path").names, vec!["* as path"]);
+        assert_eq!(find("./fs-utils").names, vec!["readFile", "write"]);
+        assert!(find("./polyfills").names.is_empty());
+        assert_eq!(find("./legacy").kind, "require");
+        assert_eq!(find("./legacy").names, vec!["* as legacy"]);
+
+        let lazy = find("./lazy");
+        assert_eq!(lazy.kind, "dynamic-import");
+        assert_eq!(lazy.names, vec!["render"]);
+        assert_eq!(lazy.line, 8);
+        assert_eq!(find("./pages/Home").names, vec!["*"]);
+        // Computed specifiers cannot be resolved
+        assert_eq!(result.imports.len(), 6);
+    }
+
     #[test]
     fn test_parse_ts_arrow_function() {
         let source = r#"const add = (a, b) => a + b;"#;
@@ -440,4 +512,529 @@ import (
         let ctx = result.imports.iter().find(|i| i.path == "context").unwrap();
         assert_eq!(ctx.names[0], "*");
     }
+
+    // =====================================================================
+    // React (TSX / JSX) parser tests
+    // =====================================================================
+
+    #[test]
+    fn test_parse_tsx_components_and_hooks() {
+        let source = r#"
+import { useState } from 'react';
+
+export function Button({ label }: Props) {
+    return <button>{label}</button>;
+}
+
+const Card = ({ title }: { title: string }) => (
+    <>
+        <h2>{title}</h2>
+    </>
+);
+
+export function useCounter(start: number) {
+    const [n, setN] = useState<number>(start);
+    return { n, setN };
+}
+
+function formatLabel<T>(value: T): string {
+    return String(value);
+}
+"#;
+        let result = parse_file(source, "tsx").unwrap();
+        let kind = |name: &str| {
+            result
+                .symbols
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.kind.clone())
+                .unwrap()
+        };
+        assert!(matches!(kind("Button"), SymbolKind::Component));
+        assert!(matches!(kind("Card"), SymbolKind::Component));
+        assert!(matches!(kind("useCounter"), SymbolKind::Hook));
+        // Generic arrow syntax still parses, and non-JSX functions stay functions
+        assert!(matches!(kind("formatLabel"), SymbolKind::Function));
+    }
+
+    #[test]
+    fn test_parse_react_wrappers_and_class_components() {
+        let source = r#"
+export const Input = React.forwardRef((props, ref) => <input ref={ref} {...props} />);
+const Row = memo(function Row(props) { return <tr />; });
+const config = Object.freeze({ a: 1 });
+class Legacy extends React.PureComponent {
+    render() { return <div />; }
+}
+"#;
+        let result = parse_file(source, "javascript").unwrap();
+        let input = result.symbols.iter().find(|s| s.name == "Input").unwrap();
+        assert!(matches!(input.kind, SymbolKind::Component));
+        assert_eq!(input.signature, "export const Input = React.forwardRef");
+        let row = result.symbols.iter().find(|s| s.name == "Row").unwrap();
+        assert!(matches!(row.kind, SymbolKind::Component));
+        let config = result.symbols.iter().find(|s| s.name == "config").unwrap();
+        assert!(matches!(config.kind, SymbolKind::Constant));
+        let legacy = result.symbols.iter().find(|s| s.name == "Legacy").unwrap();
+        assert!(matches!(legacy.kind, SymbolKind::Component));
+        assert_eq!(legacy.children[0].name, "render");
+    }
+
+    #[test]
+    fn test_parse_jsx_render_references() {
+        let source = r#"
+export function Page() {
+    return (
+        <Layout>
+            <div className="body">
+                <UI.Button onClick={() => track()} />
+            </div>
+        </Layout>
+    );
+}
+"#;
+        let result = parse_file(source, "tsx").unwrap();
+        let renders: Vec<_> = result
+            .calls
+            .iter()
+            .filter(|c| c.kind == "render")
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(renders, vec!["Layout", "Button"]);
+        assert!(result
+            .calls
+            .iter()
+            .any(|c| c.name == "track" && c.kind == "call"));
+    }
+
+    // =====================================================================
+    // Swift parser tests
+    // =====================================================================
+
+    #[test]
+    fn test_parse_swift_types_and_members() {
+        let source = r#"
+protocol Shape: Equatable {
+    func area() -> Double
+}
+
+class Circle: Shape {
+    let radius: Double
+    init(radius: Double) { self.radius = radius }
+    func area() -> Double { return 3.14 * radius * radius }
+}
+
+struct Point { var x: Int }
+
+enum Direction { case north, south }
+
+actor Counter { func increment() {} }
+"#;
+        let result = parse_file(source, "swift").unwrap();
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Shape", "Circle", "Point", "Direction", "Counter"]
+        );
+
+        let shape = &result.symbols[0];
+        assert!(matches!(shape.kind, SymbolKind::Interface));
+        assert_eq!(shape.signature, "protocol Shape: Equatable");
+        assert_eq!(shape.children[0].name, "area");
+
+        let circle = &result.symbols[1];
+        assert!(matches!(circle.kind, SymbolKind::Class));
+        assert_eq!(circle.signature, "class Circle: Shape");
+        let members: Vec<_> = circle.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(members, vec!["radius", "init", "area"]);
+        assert!(matches!(circle.children[2].kind, SymbolKind::Method));
+        assert_eq!(circle.children[2].signature, "func area() -> Double");
+
+        assert!(matches!(result.symbols[2].kind, SymbolKind::Struct));
+        assert!(matches!(result.symbols[3].kind, SymbolKind::Enum));
+        assert!(matches!(result.symbols[4].kind, SymbolKind::Class));
+    }
+
+    #[test]
+    fn test_parse_swift_functions_and_extensions() {
+        let source = r#"
+typealias Meters = Double
+let maxRadius = 10.0
+var counter = 0
+
+func makeCircle(_ r: Double, label: String = "") -> Circle {
+    return Circle(radius: r)
+}
+
+extension Circle: CustomStringConvertible {
+    var description: String { "circle" }
+    func scaled(by factor: Double) -> Circle { Circle(radius: radius * factor) }
+}
+"#;
+        let result = parse_file(source, "swift").unwrap();
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Meters", "maxRadius", "makeCircle", "description", "scaled"]
+        );
+        assert!(matches!(result.symbols[0].kind, SymbolKind::TypeAlias));
+        assert!(matches!(result.symbols[1].kind, SymbolKind::Constant));
+
+        let make = &result.symbols[2];
+        assert!(matches!(make.kind, SymbolKind::Function));
+        assert_eq!(
+            make.signature,
+            r#"func makeCircle(_ r: Double, label: String = "") -> Circle"#
+        );
+        assert_eq!((make.start_line, make.end_line), (6, 8));
+
+        // Extension members are attributed to the extended type
+        let scaled = &result.symbols[4];
+        assert!(matches!(scaled.kind, SymbolKind::Method));
+        assert!(scaled
+            .signature
+            .starts_with("extension Circle :: func scaled(by factor: Double)"));
+
+        assert!(result.calls.iter().any(|c| c.name == "Circle"));
+        let metrics = result.metrics.iter().find(|m| m.start_line == 6).unwrap();
+        assert_eq!(metrics.metrics.param_count, 2);
+        assert_eq!(metrics.metrics.statement_count, 1);
+    }
+
+    #[test]
+    fn test_parse_swift_imports() {
+        let source = r#"
+import Foundation
+@testable import Models
+import struct Geometry.Point
+"#;
+        let result = parse_file(source, "swift").unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.path.as_str(), i.names.clone(), i.line))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("Foundation", vec![], 2),
+                ("Models", vec![], 3),
+                ("Geometry", vec!["Point".to_string()], 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swift_complexity() {
+        let source = r#"
+func check(x: Int?, y: Int) -> Int {
+    guard let v = x else { return 0 }
+    if y > 1 && v > 0 {
+        return 1
+    } else if y > 0 {
+        for i in 0..<y { print(i) }
+    }
+    switch y {
+    case 1: return 1
+    default: return v ?? 2
+    }
+}
+"#;
+        let result = parse_file(source, "swift").unwrap();
+        let m = &result.metrics[0].metrics;
+        // 1 + guard + if + && + else if + for + 2 cases + ??
+        assert_eq!(m.complexity, 9);
+        // else-if > for
+        assert_eq!(m.max_nesting, 2);
+    }
+
+    // =====================================================================
+    // Kotlin parser tests
+    // =====================================================================
+
+    #[test]
+    fn test_parse_kotlin_types_and_members() {
+        let source = r#"
+interface Shape {
+    fun area(): Double
+}
+
+@Serializable
+data class Circle(val radius: Double) : Shape {
+    override fun area(): Double = 3.14 * radius * radius
+    constructor() : this(1.0)
+
+    companion object {
+        const val MAX = 10.0
+    }
+}
+
+enum class Direction { NORTH, SOUTH }
+
+object Registry {
+    fun register(shape: Shape) {}
+}
+"#;
+        let result = parse_file(source, "kotlin").unwrap();
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Shape", "Circle", "Direction", "Registry"]);
+
+        let shape = &result.symbols[0];
+        assert!(matches!(shape.kind, SymbolKind::Interface));
+        assert_eq!(shape.signature, "interface Shape");
+        assert_eq!(shape.children[0].name, "area");
+
+        let circle = &result.symbols[1];
+        assert!(matches!(circle.kind, SymbolKind::Class));
+        assert_eq!(
+            circle.signature,
+            "data class Circle(val radius: Double) : Shape"
+        );
+        let members: Vec<_> = circle.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(members, vec!["area", "constructor", "MAX"]);
+        assert!(matches!(circle.children[0].kind, SymbolKind::Method));
+        assert_eq!(circle.children[0].signature, "override fun area(): Double");
+        assert_eq!(circle.children[2].signature, "const val MAX");
+
+        assert!(matches!(result.symbols[2].kind, SymbolKind::Enum));
+        assert!(matches!(result.symbols[3].kind, SymbolKind::Class));
+        assert_eq!(result.symbols[3].children[0].name, "register");
+    }
+
+    #[test]
+    fn test_parse_kotlin_functions_and_properties() {
+        let source = r#"
+typealias Meters = Double
+val MAX_RADIUS = 10.0
+var counter = 0
+
+fun makeCircle(r: Double, label: String = ""): Circle {
+    return Circle(r)
+}
+
+fun String.shout() = uppercase()
+"#;
+        let result = parse_file(source, "kotlin").unwrap();
+        let names: Vec<_> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Meters", "MAX_RADIUS", "makeCircle", "shout"]);
+        assert!(matches!(result.symbols[0].kind, SymbolKind::TypeAlias));
+        assert_eq!(result.symbols[0].signature, "typealias Meters = Double");
+        assert!(matches!(result.symbols[1].kind, SymbolKind::Constant));
+
+        let make = &result.symbols[2];
+        assert!(matches!(make.kind, SymbolKind::Function));
+        assert_eq!(
+            make.signature,
+            r#"fun makeCircle(r: Double, label: String = ""): Circle"#
+        );
+        assert_eq!((make.start_line, make.end_line), (6, 8));
+        assert_eq!(result.symbols[3].signature, "fun String.shout()");
+
+        assert!(result.calls.iter().any(|c| c.name == "Circle"));
+        assert!(result.calls.iter().any(|c| c.name == "uppercase"));
+        let metrics = result.metrics.iter().find(|m| m.start_line == 6).unwrap();
+        assert_eq!(metrics.metrics.param_count, 2);
+        assert_eq!(metrics.metrics.statement_count, 1);
+    }
+
+    #[test]
+    fn test_parse_kotlin_imports() {
+        let source = r#"
+package com.acme.app
+
+import com.acme.data.Repo
+import com.acme.util.*
+import kotlinx.coroutines.launch as go
+"#;
+        let result = parse_file(source, "kotlin").unwrap();
+        let imports: Vec<_> = result
+            .imports
+            .iter()
+            .map(|i| (i.path.as_str(), i.names.clone(), i.line))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("com.acme.data.Repo", vec!["Repo".to_string()], 4),
+                ("com.acme.util.*", vec![], 5),
+                ("kotlinx.coroutines.launch", vec!["launch".to_string()], 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kotlin_complexity() {
+        let source = r#"
+fun check(x: Int?, y: Int): Int {
+    val v = x ?: return 0
+    if (y > 1 && v > 0) {
+        return 1
+    } else if (y > 0) {
+        for (i in 0 until y) { println(i) }
+    }
+    return when (y) {
+        1 -> 1
+        else -> listOf(v).map { it * 2 }.first()
+    }
+}
+"#;
+        let result = parse_file(source, "kotlin").unwrap();
+        let m = &result.metrics[0].metrics;
+        // 1 + ?: + if + && + else if + for + 2 entries
+        assert_eq!(m.complexity, 8);
+        // else-if > for
+        assert_eq!(m.max_nesting, 2);
+    }
+
+    // =====================================================================
+    // Call site tests
+    // =====================================================================
+
+    #[test]
+    fn test_parse_rust_calls() {
+        let source = r#"
+fn run(db: &Database) {
+    let id = db.get_file_id("a");
+    helper(id);
+    let p = Database::open(root);
+}
+"#;
+        let result = parse_file(source, "rust").unwrap();
+        let names: Vec<_> = result.calls.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["get_file_id", "helper", "open"]);
+        assert_eq!(result.calls[1].line, 4);
+    }
+
+    #[test]
+    fn test_parse_ts_calls_and_new() {
+        let source = r#"
+function main() {
+    const svc = new UserService(db);
+    svc.getUser(1);
+    render();
+}
+"#;
+        let result = parse_file(source, "typescript").unwrap();
+        let svc = result
+            .calls
+            .iter()
+            .find(|c| c.name == "UserService")
+            .unwrap();
+        assert_eq!(svc.kind, "new");
+        assert!(result.calls.iter().any(|c| c.name == "getUser"));
+        assert!(result.calls.iter().any(|c| c.name == "render"));
+    }
+
+    #[test]
+    fn test_parse_python_and_go_calls() {
+        let py = parse_file("def f():\n    os.path.join(a, b)\n    g()\n", "python").unwrap();
+        let names: Vec<_> = py.calls.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["join", "g"]);
+
+        let go = parse_file(
+            "package main\nfunc main() {\n    fmt.Println(greet(\"x\"))\n}\n",
+            "go",
+        )
+        .unwrap();
+        assert!(go.calls.iter().any(|c| c.name == "Println"));
+        assert!(go.calls.iter().any(|c| c.name == "greet"));
+    }
+
+    // =====================================================================
+    // Complexity metric tests
+    // =====================================================================
+
+    #[test]
+    fn test_rust_complexity_and_nesting() {
+        let source = r#"
+fn classify(n: i32, strict: bool) -> &'static str {
+    if n < 0 && strict {
+        return "negative";
+    } else if n == 0 {
+        return "zero";
+    }
+    for i in 0..n {
+        match i {
+            1 => {}
+            _ => {
+                if i > 10 {
+                    break;
+                }
+            }
+        }
+    }
+    "positive"
+}
+"#;
+        let result = parse_file(source, "rust").unwrap();
+        assert_eq!(result.metrics.len(), 1);
+        let m = &result.metrics[0];
+        assert_eq!((m.start_line, m.end_line), (2, 19));
+        // 1 + if + && + else if + for + 2 arms + inner if
+        assert_eq!(m.metrics.complexity, 8);
+        // for > match > if
+        assert_eq!(m.metrics.max_nesting, 3);
+    }
+
+    #[test]
+    fn test_nested_functions_measured_separately() {
+        let source = r#"
+def outer(items):
+    def inner(x):
+        if x:
+            return 1
+    return [inner(i) for i in items if i]
+"#;
+        let result = parse_file(source, "python").unwrap();
+        assert_eq!(result.metrics.len(), 2);
+        // comprehension `if` counts, the nested def does not
+        assert_eq!(result.metrics[0].metrics.complexity, 2);
+        assert_eq!(result.metrics[1].metrics.complexity, 2);
+        assert_eq!(result.metrics[1].metrics.max_nesting, 1);
+    }
+
+    #[test]
+    fn test_ts_lambdas_count_toward_enclosing_function() {
+        let source = r#"
+function load(items) {
+    return items.map((x) => (x ? x.id ?? 0 : -1));
+}
+"#;
+        let result = parse_file(source, "typescript").unwrap();
+        let load = result.metrics.iter().find(|m| m.start_line == 2).unwrap();
+        // 1 + ternary + ??
+        assert_eq!(load.metrics.complexity, 3);
+        assert_eq!(load.metrics.max_nesting, 0);
+    }
+
+    #[test]
+    fn test_parameter_and_statement_counts() {
+        let rust = parse_file(
+            "impl Db {\n    fn put(&self, key: &str, value: i64) {\n        let k = key.trim();\n        if value > 0 {\n            self.write(k, value);\n        }\n    }\n}\n",
+            "rust",
+        )
+        .unwrap();
+        // receiver excluded; `let`, `if` and the nested call
+        assert_eq!(rust.metrics[0].metrics.param_count, 2);
+        assert_eq!(rust.metrics[0].metrics.statement_count, 3);
+
+        let py = parse_file(
+            "class A:\n    def f(self, a, *args, b=1, **kw):\n        x = a\n        return x\n",
+            "python",
+        )
+        .unwrap();
+        assert_eq!(py.metrics[0].metrics.param_count, 4);
+        assert_eq!(py.metrics[0].metrics.statement_count, 2);
+
+        let go = parse_file(
+            "package main\nfunc (s *S) add(a, b int, c string) int {\n    return a + b\n}\n",
+            "go",
+        )
+        .unwrap();
+        assert_eq!(go.metrics[0].metrics.param_count, 3);
+
+        let c = parse_file("int main(void) {\n    return 0;\n}\n", "c").unwrap();
+        assert_eq!(c.metrics[0].metrics.param_count, 0);
+        assert_eq!(c.metrics[0].metrics.statement_count, 1);
+    }
 }
@@ -63,6 +63,9 @@ pub fn extract_ts_js(
             _ => {}
         }
     }
+
+    // require() and import() may appear anywhere, not just at the top level
+    extract_ts_module_calls(node, source, imports);
 }
 
 /// Handle export statements — including default exports and re-exports
@@ -125,40 +128,49 @@ fn extract_ts_export(
 
     // Re-export: export { foo } from './bar'  or  export * from './bar'
     if !has_declaration {
-        let text = node_text(node, source);
-        if text.contains(" from ") {
-            // Extract the source path
-            if let Some(source_node) = node
-                .children(&mut node.walk())
-                .find(|c| c.kind() == "string")
-            {
-                let path = node_text(source_node, source)
-                    .trim_matches(|c| c == '\'' || c == '"')
-                    .to_string();
-                let mut names = Vec::new();
+        let Some(source_node) = node.child_by_field_name("source") else {
+            return;
+        };
+        let path = string_value(source_node, source);
+        let mut kind = "re-export";
+        let mut names = Vec::new();
 
-                // Extract re-exported names
-                let mut cursor2 = node.walk();
-                for child in node.children(&mut cursor2) {
-                    if child.kind() == "export_clause" {
-                        let mut ec = child.walk();
-                        for spec in child.children(&mut ec) {
-                            if spec.kind() == "export_specifier" {
-                                if let Some(name_node) = spec.child_by_field_name("name") {
-                                    names.push(node_text(name_node, source));
-                                }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "export_clause" => {
+                    let mut ec = child.walk();
+                    for spec in child.children(&mut ec) {
+                        if spec.kind() == "export_specifier" {
+                            if let Some(name_node) = spec.child_by_field_name("name") {
+                                names.push(node_text(name_node, source));
                             }
                         }
                     }
                 }
-
-                imports.push(ExtractedImport {
-                    path,
-                    kind: "re-export".to_string(),
-                    names,
-                });
+                // export * from './bar'
+                "*" => {
+                    kind = "re-export-all";
+                    names.push("*".to_string());
+                }
+                // export * as bar from './bar'
+                "namespace_export" => {
+                    kind = "re-export-all";
+                    let mut ns = child.walk();
+                    if let Some(alias) = child.named_children(&mut ns).last() {
+                        names.push(format!("* as {}", node_text(alias, source)));
+                    }
+                }
+                _ => {}
             }
         }
+
+        imports.push(ExtractedImport {
+            path,
+            kind: kind.to_string(),
+            names,
+            line: node.start_position().row + 1,
+        });
     }
 }
 
@@ -171,8 +183,8 @@ fn extract_ts_function(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
         .unwrap_or_else(|| "()".to_string());
 
     Some(ExtractedSymbol {
+        kind: function_kind(&name, node),
         name: name.clone(),
-        kind: SymbolKind::Function,
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         signature: format!("function {}{}", name, params),
@@ -209,9 +221,29 @@ fn extract_ts_class(node: Node, source: &[u8]) -> Option<ExtractedSymbol> {
         }
     }
 
+    // class Button extends React.Component<Props> { render() { ... } }
+    let mut cursor = node.walk();
+    let is_component = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == "class_heritage")
+        .is_some_and(|heritage| {
+            let text = node_text(heritage, source);
+            let base = text
+                .trim_start_matches("extends")
+                .trim()
+                .split(|c: char| c == '<' || c.is_whitespace())
+                .next()
+                .unwrap_or("");
+            matches!(base.rsplit('.').next(), Some("Component" | "PureComponent"))
+        });
+
     Some(ExtractedSymbol {
         name: name.clone(),
-        kind: SymbolKind::Class,
+        kind: if is_component {
+            SymbolKind::Component
+        } else {
+            SymbolKind::Class
+        },
         start_line: node.start_position().row + 1,
         end_line: node.end_position().row + 1,
         signature: format!("class {}", name),
@@ -256,24 +288,29 @@ fn extract_ts_lexical(
         if child.kind() == "variable_declarator" {
             if let Some(name_node) = child.child_by_field_name("name") {
                 let name = node_text(name_node, source);
-                let is_function = child
-                    .child_by_field_name("value")
-                    .map(|v| matches!(v.kind(), "arrow_function" | "function"))
-                    .unwrap_or(false);
-
-                let kind = if is_function {
-                    SymbolKind::Function
-                } else {
-                    SymbolKind::Constant
-                };
+                let value = child.child_by_field_name("value");
                 let prefix = if exported { "export " } else { "" };
+                let mut signature = format!("{}const {}", prefix, name);
+
+                let kind = match value {
+                    Some(v) if is_function_value(v) => function_kind(&name, v),
+                    // const Button = forwardRef((props, ref) => ...)
+                    Some(v) if is_component_name(&name) => match component_wrapper(v, source) {
+                        Some(wrapper) => {
+                            signature = format!("{} = {}", signature, wrapper);
+                            SymbolKind::Component
+                        }
+                        None => SymbolKind::Constant,
+                    },
+                    _ => SymbolKind::Constant,
+                };
 
                 symbols.push(ExtractedSymbol {
                     name: name.clone(),
                     kind,
                     start_line: node.start_position().row + 1,
                     end_line: node.end_position().row + 1,
-                    signature: format!("{}const {}", prefix, name),
+                    signature,
                     children: vec![],
                 });
             }
@@ -281,6 +318,60 @@ fn extract_ts_lexical(
     }
 }
 
+fn is_function_value(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "arrow_function" | "function" | "function_expression"
+    )
+}
+
+/// Classify a named function: `useX` is a hook, and a capitalised function
+/// that renders JSX is a React component
+fn function_kind(name: &str, node: Node) -> SymbolKind {
+    if is_hook_name(name) {
+        SymbolKind::Hook
+    } else if is_component_name(name) && contains_jsx(node) {
+        SymbolKind::Component
+    } else {
+        SymbolKind::Function
+    }
+}
+
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn contains_jsx(node: Node) -> bool {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        if matches!(
+            current.kind(),
+            "jsx_element" | "jsx_self_closing_element" | "jsx_fragment"
+        ) {
+            return true;
+        }
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
+    }
+    false
+}
+
+/// The wrapper of `memo(...)` / `React.forwardRef(...)`, if the value is one
+fn component_wrapper(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let callee = node_text(node.child_by_field_name("function")?, source);
+    let base = callee.strip_prefix("React.").unwrap_or(&callee);
+    matches!(base, "memo" | "forwardRef").then_some(callee)
+}
+
 fn extract_ts_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
     let mut path = String::new();
     let mut names = Vec::new();
@@ -289,9 +380,26 @@ fn extract_ts_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
     for child in node.children(&mut cursor) {
         match child.kind() {
             "string" => {
-                path = node_text(child, source)
-                    .trim_matches(|c| c == '\'' || c == '"')
-                    .to_string();
+                path = string_value(child, source);
+            }
+            // TypeScript: import fs = require('fs')
+            "import_require_clause" => {
+                let mut inner = child.walk();
+                for part in child.children(&mut inner) {
+                    match part.kind() {
+                        "identifier" => names.push(format!("* as {}", node_text(part, source))),
+                        "string" => path = string_value(part, source),
+                        _ => {}
+                    }
+                }
+                if !path.is_empty() {
+                    return Some(ExtractedImport {
+                        path,
+                        kind: "require".to_string(),
+                        names,
+                        line: node.start_position().row + 1,
+                    });
+                }
             }
             "import_clause" => {
                 let mut inner = child.walk();
@@ -331,8 +439,98 @@ fn extract_ts_import(node: Node, source: &[u8]) -> Option<ExtractedImport> {
             path,
             kind: "import".to_string(),
             names,
+            line: node.start_position().row + 1,
         })
     } else {
         None
     }
 }
+
+/// CommonJS `require('./x')` and dynamic `import('./x')` calls with a literal
+/// specifier, anywhere in the file
+fn extract_ts_module_calls(node: Node, source: &[u8], imports: &mut Vec<ExtractedImport>) {
+    let mut stack = vec![node];
+    while let Some(current) = stack.pop() {
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
+
+        if current.kind() != "call_expression" {
+            continue;
+        }
+        let Some(function) = current.child_by_field_name("function") else {
+            continue;
+        };
+        let kind = match function.kind() {
+            "import" => "dynamic-import",
+            "identifier" if node_text(function, source) == "require" => "require",
+            _ => continue,
+        };
+        let Some(specifier) = current
+            .child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0))
+            .filter(|arg| arg.kind() == "string")
+        else {
+            continue;
+        };
+
+        imports.push(ExtractedImport {
+            path: string_value(specifier, source),
+            kind: kind.to_string(),
+            names: bound_names(current, source),
+            line: current.start_position().row + 1,
+        });
+    }
+}
+
+/// Names a `require`/`import()` result is bound to: destructured keys, or
+/// `* as x` for the whole module. A bare statement is a side-effect import.
+fn bound_names(call: Node, source: &[u8]) -> Vec<String> {
+    let mut value = call;
+    while let Some(parent) = value.parent() {
+        if matches!(
+            parent.kind(),
+            "await_expression" | "parenthesized_expression"
+        ) {
+            value = parent;
+        } else {
+            break;
+        }
+    }
+    let Some(parent) = value.parent() else {
+        return vec![];
+    };
+
+    match parent.kind() {
+        "expression_statement" => vec![],
+        "variable_declarator" => match parent.child_by_field_name("name") {
+            Some(name) if name.kind() == "identifier" => {
+                vec![format!("* as {}", node_text(name, source))]
+            }
+            Some(pattern) if pattern.kind() == "object_pattern" => {
+                let mut names = Vec::new();
+                let mut cursor = pattern.walk();
+                for prop in pattern.named_children(&mut cursor) {
+                    let key = match prop.kind() {
+                        "shorthand_property_identifier_pattern" => Some(prop),
+                        "pair_pattern" => prop.child_by_field_name("key"),
+                        "object_assignment_pattern" => prop.child_by_field_name("left"),
+                        _ => None,
+                    };
+                    if let Some(key) = key {
+                        names.push(node_text(key, source));
+                    }
+                }
+                names
+            }
+            _ => vec!["*".to_string()],
+        },
+        // Passed on or chained (`lazy(() => import('./Page'))`): the whole module
+        _ => vec!["*".to_string()],
+    }
+}
+
+fn string_value(node: Node, source: &[u8]) -> String {
+    node_text(node, source)
+        .trim_matches(|c| c == '\'' || c == '"')
+        .to_string()
+}
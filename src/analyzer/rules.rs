@@ -0,0 +1,194 @@
+use anyhow::{bail, Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+
+use crate::config::ArchitectureConfig;
+use crate::db::models::ResolvedImport;
+use crate::db::Database;
+
+/// An import that crosses a forbidden module boundary
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleViolation {
+    /// 1-based position of the rule in `[[architecture.rules]]`
+    pub rule: usize,
+    /// `<from> must not import <forbidden pattern>`
+    pub description: String,
+    pub reason: Option<String>,
+    /// Importing file
+    pub path: String,
+    /// 0 when the import line is unknown
+    pub line: i64,
+    /// The import as written
+    pub import: String,
+    /// File it resolved to
+    pub target: String,
+}
+
+/// Evaluate the configured layering rules against the resolved imports.
+/// Rust `mod` declarations are skipped, as in the import graph: a parent
+/// declaring its children is containment, not an import.
+pub fn check_architecture(db: &Database) -> Result<Vec<RuleViolation>> {
+    find_violations(&db.config.architecture, &db.get_resolved_imports()?)
+}
+
+fn find_violations(
+    config: &ArchitectureConfig,
+    imports: &[ResolvedImport],
+) -> Result<Vec<RuleViolation>> {
+    let mut violations = Vec::new();
+    for (i, rule) in config.rules.iter().enumerate() {
+        let number = i + 1;
+        let from = compile(config, &rule.from, number)?;
+        let forbid = rule
+            .forbid
+            .iter()
+            .map(|pattern| Ok((pattern, compile(config, pattern, number)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for import in imports
+            .iter()
+            .filter(|imp| imp.kind != "mod" && from.is_match(&imp.path))
+        {
+            // A file inside both sides is not crossing the boundary
+            let Some((pattern, _)) = forbid
+                .iter()
+                .find(|(_, set)| set.is_match(&import.target) && !set.is_match(&import.path))
+            else {
+                continue;
+            };
+            violations.push(RuleViolation {
+                rule: number,
+                description: format!("{} must not import {}", rule.from, pattern),
+                reason: rule.reason.clone(),
+                path: import.path.clone(),
+                line: import.line,
+                import: import.import.clone(),
+                target: import.target.clone(),
+            });
+        }
+    }
+
+    violations
+        .sort_by(|a, b| (a.path.as_str(), a.line, a.rule).cmp(&(b.path.as_str(), b.line, b.rule)));
+    Ok(violations)
+}
+
+/// Compile a layer name or glob. Bare words must name a layer, so a typo
+/// fails loudly instead of silently matching nothing.
+fn compile(config: &ArchitectureConfig, pattern: &str, rule: usize) -> Result<GlobSet> {
+    let globs = match config.layers.get(pattern) {
+        Some(globs) => globs.clone(),
+        None if !pattern.contains(['/', '*', '?', '[', '.']) => bail!(
+            "Unknown layer '{}' in architecture rule {} (define it under [architecture.layers])",
+            pattern,
+            rule
+        ),
+        None => vec![pattern.to_string()],
+    };
+
+    let mut builder = GlobSetBuilder::new();
+    for glob in &globs {
+        // `src/db/` means everything below it
+        let glob = match glob.strip_suffix('/') {
+            Some(dir) => format!("{}/**", dir),
+            None => glob.clone(),
+        };
+        builder.add(
+            GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .with_context(|| {
+                    format!("Invalid glob '{}' in architecture rule {}", glob, rule)
+                })?,
+        );
+    }
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LayerRule;
+
+    fn import(path: &str, line: i64, target: &str) -> ResolvedImport {
+        ResolvedImport {
+            path: path.to_string(),
+            line,
+            import: target.to_string(),
+            kind: "use".to_string(),
+            names: Vec::new(),
+            target: target.to_string(),
+        }
+    }
+
+    fn rule(from: &str, forbid: &[&str]) -> LayerRule {
+        LayerRule {
+            from: from.to_string(),
+            forbid: forbid.iter().map(|s| s.to_string()).collect(),
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn reports_edges_crossing_forbidden_boundaries() {
+        let mut config = ArchitectureConfig::default();
+        config
+            .layers
+            .insert("domain".to_string(), vec!["src/domain/".to_string()]);
+        config
+            .layers
+            .insert("infra".to_string(), vec!["src/infra/**".to_string()]);
+        config.rules = vec![
+            rule("src/db/**", &["src/commands/**"]),
+            rule("domain", &["infra"]),
+        ];
+
+        let imports = [
+            import("src/db/stats.rs", 3, "src/commands/mod.rs"),
+            import("src/db/stats.rs", 4, "src/db/mod.rs"),
+            import("src/commands/mod.rs", 1, "src/db/mod.rs"),
+            import("src/domain/order.rs", 7, "src/infra/sql/orders.rs"),
+            import("src/domain/order.rs", 8, "src/domain/money.rs"),
+        ];
+        let violations = find_violations(&config, &imports).unwrap();
+
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.rule, v.path.as_str(), v.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(1, "src/db/stats.rs", 3), (2, "src/domain/order.rs", 7)]
+        );
+        assert_eq!(violations[1].description, "domain must not import infra");
+    }
+
+    #[test]
+    fn mod_declarations_do_not_cross_boundaries() {
+        let config = ArchitectureConfig {
+            rules: vec![rule("src/lib.rs", &["src/commands/**"])],
+            ..Default::default()
+        };
+        let imports = [
+            ResolvedImport {
+                kind: "mod".to_string(),
+                ..import("src/lib.rs", 2, "src/commands/mod.rs")
+            },
+            import("src/lib.rs", 5, "src/commands/mod.rs"),
+        ];
+        let violations = find_violations(&config, &imports).unwrap();
+
+        let lines: Vec<i64> = violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, [5]);
+    }
+
+    #[test]
+    fn unknown_layer_is_an_error() {
+        let config = ArchitectureConfig {
+            rules: vec![rule("domain", &["infra"])],
+            ..Default::default()
+        };
+        let err = find_violations(&config, &[]).unwrap_err();
+        assert!(err.to_string().contains("Unknown layer 'domain'"), "{err}");
+    }
+}
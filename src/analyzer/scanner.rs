@@ -1,8 +1,12 @@
-use anyhow::Result;
-use ignore::WalkBuilder;
+use anyhow::{Context, Result};
+use ignore::gitignore::Gitignore;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{Match, Walk, WalkBuilder};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::config::ScanConfig;
+
 /// Represents a discovered source file
 #[derive(Debug, Clone)]
 pub struct ScannedFile {
@@ -18,7 +22,9 @@ pub struct ScannedFile {
 /// Map file extension to language name
 fn detect_language(ext: &str) -> Option<&'static str> {
     match ext {
-        "ts" | "tsx" => Some("typescript"),
+        "ts" => Some("typescript"),
+        // JSX makes `<T>` ambiguous, so TSX needs its own grammar
+        "tsx" => Some("tsx"),
         "js" | "jsx" | "mjs" | "cjs" => Some("javascript"),
         "py" | "pyw" => Some("python"),
         "rs" => Some("rust"),
@@ -49,6 +55,7 @@ pub fn is_parseable(language: &str) -> bool {
     matches!(
         language,
         "typescript"
+            | "tsx"
             | "javascript"
             | "python"
             | "rust"
@@ -61,6 +68,8 @@ pub fn is_parseable(language: &str) -> bool {
             | "ruby"
             | "shell"
             | "bash"
+            | "swift"
+            | "kotlin"
     )
 }
 
@@ -69,39 +78,124 @@ fn hash_content(content: &str) -> String {
     blake3::hash(content.as_bytes()).to_hex().to_string()
 }
 
-/// Scan a project directory and return all source files
-pub fn scan_project(root: &Path) -> Result<Vec<ScannedFile>> {
-    let mut files = Vec::new();
-
-    let walker = WalkBuilder::new(root)
-        .hidden(true) // skip hidden files
-        .git_ignore(true) // respect .gitignore
-        .git_global(true)
-        .git_exclude(true)
-        .filter_entry(|entry| {
-            let name = entry.file_name().to_string_lossy();
-            // Skip common non-source directories
-            !matches!(
-                name.as_ref(),
-                "node_modules"
-                    | ".git"
-                    | ".ctx"
-                    | "target"
-                    | "__pycache__"
-                    | ".next"
-                    | "dist"
-                    | "build"
-                    | ".venv"
-                    | "venv"
-                    | ".tox"
-                    | "vendor"
-                    | "coverage"
-                    | ".cache"
-            )
+/// Gitignore-syntax file listing paths ctx-agent should not index, honored in
+/// any directory like `.gitignore`
+pub const CTXIGNORE_FILE: &str = ".ctxignore";
+
+/// Decides which project paths are indexed.
+///
+/// A path is skipped when it is hidden, lies in an ignored directory, matches
+/// `.gitignore` or `.ctxignore`, matches an exclude glob, or (for files) misses
+/// every include glob.
+#[derive(Clone)]
+pub struct PathFilter {
+    root: PathBuf,
+    config: ScanConfig,
+    globs: Override,
+}
+
+impl PathFilter {
+    pub fn new(root: &Path, config: &ScanConfig) -> Result<Self> {
+        let mut builder = OverrideBuilder::new(root);
+        for glob in &config.include {
+            builder
+                .add(glob)
+                .with_context(|| format!("Invalid include glob '{}'", glob))?;
+        }
+        for glob in &config.exclude {
+            builder
+                .add(&format!("!{}", glob))
+                .with_context(|| format!("Invalid exclude glob '{}'", glob))?;
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            config: config.clone(),
+            globs: builder.build()?,
         })
-        .build();
+    }
+
+    /// Whether a single project-relative path (e.g. from a watcher event) is
+    /// skipped. Ignore files are looked up in every ancestor directory; the
+    /// deepest match wins, as in a directory walk.
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        self.is_filtered(relative, is_dir) || self.is_ignored_by_files(relative, is_dir)
+    }
+
+    /// Walk a directory inside the project, applying every filter
+    pub fn walk(&self, dir: &Path) -> Walk {
+        let filter = self.clone();
+        WalkBuilder::new(dir)
+            .hidden(true) // skip hidden files
+            .git_ignore(true) // respect .gitignore
+            .git_global(true)
+            .git_exclude(true)
+            .add_custom_ignore_filename(CTXIGNORE_FILE)
+            // Skip configured directories and glob mismatches
+            .filter_entry(move |entry| {
+                let Ok(relative) = entry.path().strip_prefix(&filter.root) else {
+                    return true;
+                };
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                entry.depth() == 0 || !filter.is_filtered(relative, is_dir)
+            })
+            .build()
+    }
+
+    /// Hidden components, ignored directories and globs
+    fn is_filtered(&self, relative: &Path, is_dir: bool) -> bool {
+        let in_skipped_dir = relative.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            name.starts_with('.') || self.config.is_ignored_dir(&name)
+        });
+        if in_skipped_dir {
+            return true;
+        }
+
+        // Excluding a directory excludes everything below it
+        relative
+            .ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| self.globs.matched(dir, true).is_ignore())
+            || self.globs.matched(relative, is_dir).is_ignore()
+    }
+
+    fn is_ignored_by_files(&self, relative: &Path, is_dir: bool) -> bool {
+        let absolute = self.root.join(relative);
+        for dir in relative.ancestors().skip(1) {
+            // .ctxignore takes precedence over .gitignore in the same directory
+            for name in [CTXIGNORE_FILE, ".gitignore"] {
+                let (matcher, _) = Gitignore::new(self.root.join(dir).join(name));
+                match matcher.matched_path_or_any_parents(&absolute, is_dir) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Scan a project directory and return all source files
+pub fn scan_project(root: &Path, filter: &PathFilter) -> Result<Vec<ScannedFile>> {
+    scan_directory(root, root, filter)
+}
 
-    for entry in walker {
+/// Scan a directory inside the project, with paths relative to `root`
+pub fn scan_directory(root: &Path, dir: &Path, filter: &PathFilter) -> Result<Vec<ScannedFile>> {
+    Ok(discover_files(dir, filter)?
+        .iter()
+        .filter_map(|path| scan_file(root, path))
+        .collect())
+}
+
+/// Walk a directory and list candidate files without reading them
+pub fn discover_files(dir: &Path, filter: &PathFilter) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in filter.walk(dir) {
         let entry = match entry {
             Ok(e) => e,
             Err(_) => continue,
@@ -111,47 +205,46 @@ pub fn scan_project(root: &Path) -> Result<Vec<ScannedFile>> {
             continue;
         }
 
-        let path = entry.path();
-        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        paths.push(entry.into_path());
+    }
 
-        let language = if file_name.eq_ignore_ascii_case("dockerfile") {
-            "dockerfile"
-        } else {
-            match detect_language(ext) {
-                Some(lang) => lang,
-                None => continue, // skip unknown file types
-            }
-        };
+    Ok(paths)
+}
 
-        // Read file content
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => continue, // skip binary/unreadable files
-        };
+/// Read a single source file. Returns `None` for unknown file types and
+/// binary or unreadable files.
+pub fn scan_file(root: &Path, path: &Path) -> Option<ScannedFile> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-        let relative_path = path
-            .strip_prefix(root)
-            .unwrap_or(path)
-            .to_string_lossy()
-            .to_string();
-
-        let size_bytes = content.len() as u64;
-        let line_count = content.lines().count();
-        let hash = hash_content(&content);
-
-        files.push(ScannedFile {
-            relative_path,
-            absolute_path: path.to_path_buf(),
-            language: language.to_string(),
-            size_bytes,
-            content,
-            line_count,
-            hash,
-        });
-    }
+    let language = if file_name.eq_ignore_ascii_case("dockerfile") {
+        "dockerfile"
+    } else {
+        detect_language(ext)?
+    };
+
+    // Read file content
+    let content = std::fs::read_to_string(path).ok()?;
+
+    let relative_path = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string();
+
+    let size_bytes = content.len() as u64;
+    let line_count = content.lines().count();
+    let hash = hash_content(&content);
 
-    Ok(files)
+    Some(ScannedFile {
+        relative_path,
+        absolute_path: path.to_path_buf(),
+        language: language.to_string(),
+        size_bytes,
+        content,
+        line_count,
+        hash,
+    })
 }
 
 /// Get project stats summary
@@ -164,3 +257,51 @@ pub fn project_stats(files: &[ScannedFile]) -> HashMap<String, (usize, usize)> {
     }
     stats
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(root: &Path, include: &[&str], exclude: &[&str]) -> PathFilter {
+        let config = ScanConfig {
+            include: include.iter().map(|g| g.to_string()).collect(),
+            exclude: exclude.iter().map(|g| g.to_string()).collect(),
+            ..Default::default()
+        };
+        PathFilter::new(root, &config).unwrap()
+    }
+
+    #[test]
+    fn globs_include_files_and_exclude_trees() {
+        let f = filter(
+            Path::new("/project"),
+            &["*.rs"],
+            &["generated", "*_test.rs"],
+        );
+
+        assert!(!f.is_filtered(Path::new("src/lib.rs"), false));
+        assert!(f.is_filtered(Path::new("src/app.ts"), false));
+        assert!(f.is_filtered(Path::new("src/lib_test.rs"), false));
+        assert!(f.is_filtered(Path::new("src/generated/api.rs"), false));
+        // Include globs never prune directories
+        assert!(!f.is_filtered(Path::new("src"), true));
+        // Hidden and ignored directories stay skipped
+        assert!(f.is_filtered(Path::new("target/debug/build.rs"), false));
+        assert!(f.is_filtered(Path::new(".cargo/config.rs"), false));
+    }
+
+    #[test]
+    fn ctxignore_applies_to_single_paths() {
+        let root = std::env::temp_dir().join(format!("ctx-scanner-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/fixtures")).unwrap();
+        std::fs::write(root.join(CTXIGNORE_FILE), "*.snap\n").unwrap();
+        std::fs::write(root.join("src/.ctxignore"), "fixtures/\n").unwrap();
+
+        let f = filter(&root, &[], &[]);
+        assert!(f.is_excluded(Path::new("src/out.snap"), false));
+        assert!(f.is_excluded(Path::new("src/fixtures/case.rs"), false));
+        assert!(!f.is_excluded(Path::new("src/lib.rs"), false));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -24,10 +24,20 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize ctx-agent in the current project
-    Init,
+    Init {
+        #[command(flatten)]
+        filters: ScanFilters,
+    },
 
     /// Scan/re-scan the project
-    Scan,
+    Scan {
+        #[command(flatten)]
+        filters: ScanFilters,
+
+        /// Forget the --include/--exclude globs saved by earlier runs
+        #[arg(long, conflicts_with_all = ["include", "exclude"])]
+        clear_filters: bool,
+    },
 
     /// Display codebase map with structure and stats
     Map,
@@ -49,12 +59,135 @@ pub enum Commands {
         /// Maximum results to return
         #[arg(short, long, default_value_t = 60)]
         max_results: usize,
+
+        #[command(flatten)]
+        filters: ScanFilters,
     },
 
-    /// Show blast radius of changing a file
+    /// Show blast radius of changing a file or symbol
     BlastRadius {
+        /// File path (relative to project root)
+        #[arg(required_unless_present = "symbol")]
+        path: Option<String>,
+
+        /// Analyze a symbol instead of a file (`name` or `path::name`)
+        #[arg(short, long, conflicts_with = "path")]
+        symbol: Option<String>,
+
+        /// Maximum call depth to follow (symbol mode)
+        #[arg(short, long)]
+        depth: Option<usize>,
+
+        /// Also include files that historically change together with this one
+        #[arg(long, conflicts_with = "symbol")]
+        coupled: bool,
+    },
+
+    /// Show files that change in the same commits as a file
+    Coupled {
         /// File path (relative to project root)
         path: String,
+
+        /// Only show pairs with at least this confidence (0.0-1.0)
+        #[arg(long, default_value_t = 0.0)]
+        min_confidence: f64,
+    },
+
+    /// Check imports against the architecture rules (exits 1 on violations)
+    Check {
+        /// Print a SARIF 2.1.0 report, for code scanning in CI
+        #[arg(long)]
+        sarif: bool,
+    },
+
+    /// List import cycles and the imports that close them
+    Cycles,
+
+    /// Show the import chain connecting one file to another
+    Path {
+        /// Importing file (relative to project root)
+        from: String,
+
+        /// Imported file (relative to project root)
+        to: String,
+
+        /// List every chain, shortest first, instead of one shortest chain
+        #[arg(long)]
+        all: bool,
+
+        /// With --all, skip chains longer than this many imports
+        #[arg(long, default_value_t = 6)]
+        max_hops: usize,
+
+        /// With --all, maximum number of chains
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Export the import graph as DOT, Mermaid, GraphML or JSON
+    Graph {
+        /// Only the files around this file or directory
+        #[arg(long, value_name = "PATH")]
+        root: Option<String>,
+
+        /// With --root, the most import hops to follow in either direction
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Merge files into directories, keeping this many leading path components
+        #[arg(long, value_name = "LEVELS")]
+        collapse: Option<usize>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+
+    /// Rank functions by complexity × change frequency
+    Hotspots {
+        /// Number of functions to show
+        #[arg(short, long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Show churn, authors and per-symbol blame for a file or symbol
+    Owners {
+        /// File path (relative to project root) or symbol (`name` or `path::name`)
+        target: String,
+    },
+
+    /// Show a file's commit history, following renames
+    History {
+        /// File path (relative to project root); former names are accepted
+        path: String,
+    },
+
+    /// Show call sites that invoke a symbol
+    Callers {
+        /// Symbol name
+        symbol: String,
+    },
+
+    /// Show calls made from inside a symbol
+    Callees {
+        /// Symbol name
+        symbol: String,
+    },
+
+    /// Show the combined blast radius of uncommitted changes or a git range
+    Impact {
+        /// Revision to compare against (`<rev>`), or a range (`<rev>..<rev>`, or
+        /// `<rev>...<rev>` for changes since the branches forked)
+        #[arg(conflicts_with_all = ["staged", "since"])]
+        range: Option<String>,
+
+        /// Only consider staged changes
+        #[arg(long, conflicts_with = "since")]
+        staged: bool,
+
+        /// Include everything changed since this revision (plus uncommitted changes)
+        #[arg(long)]
+        since: Option<String>,
     },
 
     /// Show recorded decisions
@@ -74,5 +207,34 @@ pub enum Commands {
     Warnings,
 
     /// Watch for file changes and re-analyze
-    Watch,
+    Watch {
+        #[command(flatten)]
+        filters: ScanFilters,
+    },
+
+    /// Run an MCP server over stdio (JSON-RPC) for agent integrations
+    Mcp,
+}
+
+/// Output format of `graph`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Graphml,
+    Json,
+}
+
+/// Glob overrides on top of `.gitignore`, `.ctxignore` and the config file.
+/// Except for `grep`, they are saved with the project and replace the globs
+/// saved before.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ScanFilters {
+    /// Only index files matching this glob (repeatable)
+    #[arg(long = "include", value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files and directories matching this glob (repeatable)
+    #[arg(long = "exclude", value_name = "GLOB")]
+    pub exclude: Vec<String>,
 }
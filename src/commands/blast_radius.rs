@@ -1,78 +1,145 @@
 use super::*;
 
-pub(super) fn cmd_blast_radius(root: &Path, path: &str, json_mode: bool) -> Result<()> {
+pub(super) fn cmd_blast_radius(
+    root: &Path,
+    path: &str,
+    include_coupled: bool,
+    json_mode: bool,
+) -> Result<()> {
     let db = ensure_initialized(root)?;
 
     if json_mode {
-        let file_id = match db.get_file_id(path)? {
-            Some(id) => id,
-            None => {
-                println!(
-                    "{}",
-                    json!({
-                        "command": "blast_radius",
-                        "error": format!("File not found: {}", path),
-                    })
-                );
-                return Ok(());
-            }
-        };
-
-        let deps = db.get_dependencies_of(file_id)?;
-        let dependents = db.get_dependents(file_id)?;
-
-        let dep_list: Vec<_> = deps
-            .iter()
-            .map(|(_, dep_path)| {
-                json!({
-                    "target": dep_path,
-                })
+        println!("{}", blast_radius_report(&db, path, include_coupled)?);
+    } else {
+        ctx::query::execute_blast_radius(&db, path, include_coupled)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+pub(super) fn cmd_symbol_blast_radius(
+    root: &Path,
+    symbol: &str,
+    max_depth: Option<usize>,
+    json_mode: bool,
+) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", symbol_blast_radius_report(&db, symbol, max_depth)?);
+    } else {
+        ctx::query::execute_symbol_blast_radius(&db, symbol, max_depth)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Build the `blast-radius --json` payload
+pub(super) fn blast_radius_report(
+    db: &Database,
+    path: &str,
+    include_coupled: bool,
+) -> Result<serde_json::Value> {
+    let file_id = match db.get_file_id(path)? {
+        Some(id) => id,
+        None => {
+            return Ok(json!({
+                "command": "blast_radius",
+                "error": format!("File not found: {}", path),
+            }));
+        }
+    };
+
+    let deps = db.get_dependencies_of(file_id)?;
+    let dependents = db.get_dependents(file_id)?;
+
+    let dep_list: Vec<_> = deps
+        .iter()
+        .map(|(_, dep_path)| {
+            json!({
+                "target": dep_path,
             })
-            .collect();
-
-        let dep_of_list: Vec<_> = dependents
-            .iter()
-            .map(|(_, dep_path)| {
-                json!({
-                    "source": dep_path,
-                })
+        })
+        .collect();
+
+    let dep_of_list: Vec<_> = dependents
+        .iter()
+        .map(|(_, dep_path)| {
+            json!({
+                "source": dep_path,
             })
-            .collect();
-
-        // Transitive blast radius
-        let radius = ctx::analyzer::graph::blast_radius(&db, file_id)?;
-        let radius_list: Vec<_> = radius
-            .iter()
-            .map(|(_, rpath, depth)| {
-                json!({
-                    "path": rpath,
-                    "depth": depth,
-                })
+        })
+        .collect();
+
+    // Transitive blast radius
+    let radius = ctx::analyzer::graph::blast_radius(db, file_id)?;
+    let radius_list: Vec<_> = radius
+        .iter()
+        .map(|(_, rpath, depth)| {
+            json!({
+                "path": rpath,
+                "depth": depth,
             })
-            .collect();
+        })
+        .collect();
+
+    let mut report = json!({
+        "command": "blast_radius",
+        "file": path,
+        "imports": dep_list,
+        "imported_by": dep_of_list,
+        "transitive_impact": radius_list,
+        "risk": db.config.risk.level(radius.len()),
+    });
+
+    if include_coupled {
+        let historical = ctx::analyzer::graph::historical_blast_radius(db, path, &radius)?;
+        report["risk"] = json!(db.config.risk.level(radius.len() + historical.len()));
+        report["historical_impact"] = json!(historical);
+    }
 
-        let risk = match radius.len() {
-            0 => "low",
-            1..=5 => "medium",
-            6..=20 => "high",
-            _ => "critical",
-        };
+    Ok(report)
+}
+
+/// Build the `blast-radius --symbol --json` payload
+pub(super) fn symbol_blast_radius_report(
+    db: &Database,
+    symbol: &str,
+    max_depth: Option<usize>,
+) -> Result<serde_json::Value> {
+    let definitions = ctx::query::find_symbol_targets(db, symbol)?;
+    if definitions.is_empty() {
+        return Ok(json!({
+            "command": "blast_radius",
+            "error": format!("Symbol not found: {}", symbol),
+        }));
+    }
 
-        println!(
-            "{}",
+    let definition_list: Vec<_> = definitions
+        .iter()
+        .map(|(sym, path)| {
             json!({
-                "command": "blast_radius",
+                "name": sym.name,
+                "kind": sym.kind.as_str(),
                 "file": path,
-                "imports": dep_list,
-                "imported_by": dep_of_list,
-                "transitive_impact": radius_list,
-                "risk": risk,
+                "line": sym.start_line,
+                "signature": sym.signature,
             })
-        );
-    } else {
-        ctx::query::execute_blast_radius(&db, path)?;
-        println!();
-    }
+        })
+        .collect();
 
-    Ok(())
+    let symbols: Vec<_> = definitions.into_iter().map(|(s, _)| s).collect();
+    let impacts = ctx::analyzer::graph::symbol_blast_radius(db, &symbols, max_depth)?;
+    let files: std::collections::BTreeSet<&str> = impacts.iter().map(|i| i.path.as_str()).collect();
+
+    Ok(json!({
+        "command": "blast_radius",
+        "symbol": symbol,
+        "definitions": definition_list,
+        "affected_symbols": impacts,
+        "affected_files": files,
+        "risk": db.config.risk.level(files.len()),
+    }))
 }
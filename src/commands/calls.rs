@@ -0,0 +1,114 @@
+use super::*;
+use ctx::db::models::SymbolReference;
+
+pub(super) fn cmd_callers(root: &Path, symbol: &str, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", callers_report(&db, symbol)?);
+        return Ok(());
+    }
+
+    let refs = db.get_callers(symbol)?;
+    println!();
+    if refs.is_empty() {
+        println!(
+            "  {} No callers found for \"{}\".",
+            "INFO".cyan().bold(),
+            symbol
+        );
+    } else {
+        println!(
+            "  {} call sites invoke \"{}\":\n",
+            refs.len().to_string().cyan().bold(),
+            symbol.yellow()
+        );
+        for r in &refs {
+            let caller = r.caller.as_deref().unwrap_or("<top-level>");
+            // JSX usages and constructions are labelled; plain calls are not
+            let kind = if r.kind == "call" {
+                String::new()
+            } else {
+                format!(" [{}]", r.kind)
+            };
+            println!(
+                "    {}:{}  {} {} {}{}",
+                r.file.cyan(),
+                r.line,
+                caller.white().bold(),
+                "→".dimmed(),
+                target_label(r).dimmed(),
+                kind.magenta(),
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+pub(super) fn cmd_callees(root: &Path, symbol: &str, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", callees_report(&db, symbol)?);
+        return Ok(());
+    }
+
+    let refs = db.get_callees(symbol)?;
+    println!();
+    if refs.is_empty() {
+        println!(
+            "  {} No calls found inside \"{}\".",
+            "INFO".cyan().bold(),
+            symbol
+        );
+    } else {
+        println!(
+            "  {} calls made by \"{}\":\n",
+            refs.len().to_string().cyan().bold(),
+            symbol.yellow()
+        );
+        for r in &refs {
+            println!(
+                "    {}:{}  {} {}",
+                r.file.cyan(),
+                r.line,
+                "→".dimmed(),
+                target_label(r),
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build the `callers --json` payload
+pub(super) fn callers_report(db: &Database, symbol: &str) -> Result<serde_json::Value> {
+    let refs = db.get_callers(symbol)?;
+    Ok(json!({
+        "command": "callers",
+        "symbol": symbol,
+        "count": refs.len(),
+        "results": refs,
+    }))
+}
+
+/// Build the `callees --json` payload
+pub(super) fn callees_report(db: &Database, symbol: &str) -> Result<serde_json::Value> {
+    let refs = db.get_callees(symbol)?;
+    Ok(json!({
+        "command": "callees",
+        "symbol": symbol,
+        "count": refs.len(),
+        "results": refs,
+    }))
+}
+
+fn target_label(r: &SymbolReference) -> String {
+    match &r.callee_file {
+        Some(file) => format!("{} ({})", r.callee, file),
+        None => r.callee.clone(),
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+use ctx::analyzer::rules::{check_architecture, RuleViolation};
+use ctx::config::ArchitectureConfig;
+
+/// Evaluate the architecture rules; returns whether the check passed
+pub(super) fn cmd_check(root: &Path, sarif: bool, json_mode: bool) -> Result<bool> {
+    let db = ensure_initialized(root)?;
+    // CI runs check on a fresh checkout: bring the index up to date first
+    analyzer::analyze_project(&db, root)?;
+    let violations = check_architecture(&db)?;
+
+    if sarif {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&sarif_report(&db.config.architecture, &violations))?
+        );
+        return Ok(violations.is_empty());
+    }
+    if json_mode {
+        println!("{}", check_json(&db.config.architecture, &violations));
+        return Ok(violations.is_empty());
+    }
+
+    println!();
+    let rules = &db.config.architecture.rules;
+    if rules.is_empty() {
+        println!(
+            "  {} No architecture rules configured. Add [[architecture.rules]] to {}.",
+            "INFO".cyan().bold(),
+            ctx::config::PROJECT_CONFIG_FILE
+        );
+        println!();
+        return Ok(true);
+    }
+    if violations.is_empty() {
+        println!(
+            "  {} {} architecture rules, no violations",
+            "OK".green().bold(),
+            rules.len()
+        );
+        println!();
+        return Ok(true);
+    }
+
+    println!(
+        "  {} {} architecture violations\n",
+        "FAIL".red().bold(),
+        violations.len().to_string().red().bold()
+    );
+    for v in &violations {
+        println!(
+            "  {}  {} {} {}",
+            location(v).white().bold(),
+            v.import.yellow(),
+            "→".dimmed(),
+            v.target
+        );
+        match &v.reason {
+            Some(reason) => println!(
+                "    {} {} — {}",
+                format!("rule {}:", v.rule).dimmed(),
+                v.description.red(),
+                reason
+            ),
+            None => println!(
+                "    {} {}",
+                format!("rule {}:", v.rule).dimmed(),
+                v.description.red()
+            ),
+        }
+    }
+    println!();
+
+    Ok(false)
+}
+
+/// Build the `check --json` payload without refreshing the index
+pub(super) fn check_report(db: &Database) -> Result<serde_json::Value> {
+    let violations = check_architecture(db)?;
+    Ok(check_json(&db.config.architecture, &violations))
+}
+
+fn check_json(config: &ArchitectureConfig, violations: &[RuleViolation]) -> serde_json::Value {
+    json!({
+        "command": "check",
+        "rules": config.rules.len(),
+        "passed": violations.is_empty(),
+        "count": violations.len(),
+        "violations": violations,
+    })
+}
+
+fn location(v: &RuleViolation) -> String {
+    if v.line > 0 {
+        format!("{}:{}", v.path, v.line)
+    } else {
+        v.path.clone()
+    }
+}
+
+/// SARIF 2.1.0 log with one result per violating import
+fn sarif_report(config: &ArchitectureConfig, violations: &[RuleViolation]) -> serde_json::Value {
+    let rules: Vec<_> = config
+        .rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let description = format!("{} must not import {}", rule.from, rule.forbid.join(", "));
+            json!({
+                "id": format!("architecture/{}", i + 1),
+                "shortDescription": { "text": description },
+                "fullDescription": { "text": rule.reason.as_deref().unwrap_or(&description) },
+                "defaultConfiguration": { "level": "error" },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = violations
+        .iter()
+        .map(|v| {
+            let mut message = format!("{} ({} → {})", v.description, v.import, v.target);
+            if let Some(reason) = &v.reason {
+                message.push_str(": ");
+                message.push_str(reason);
+            }
+            let mut location = json!({ "artifactLocation": { "uri": v.path } });
+            if v.line > 0 {
+                location["region"] = json!({ "startLine": v.line });
+            }
+            json!({
+                "ruleId": format!("architecture/{}", v.rule),
+                "ruleIndex": v.rule - 1,
+                "level": "error",
+                "message": { "text": message },
+                "locations": [{ "physicalLocation": location }],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ctx-agent",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+pub(super) fn cmd_coupled(
+    root: &Path,
+    path: &str,
+    min_confidence: f64,
+    json_mode: bool,
+) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", coupled_report(&db, path, min_confidence)?);
+        return Ok(());
+    }
+
+    let coupled = db.get_coupled_files(path, min_confidence)?;
+    println!();
+    if coupled.is_empty() {
+        println!(
+            "  {} No files change together with \"{}\".",
+            "INFO".cyan().bold(),
+            path
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} {} ({} files)\n",
+        "Coupled:".yellow().bold(),
+        path.white().bold(),
+        coupled.len().to_string().cyan()
+    );
+    for file in &coupled {
+        let link = if file.imports {
+            "imports".dimmed()
+        } else {
+            "no import".magenta()
+        };
+        println!(
+            "    {:>4}  {:>3} commits  {}  {}",
+            format!("{:.0}%", file.confidence * 100.0).yellow(),
+            file.support,
+            file.path,
+            link
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build the `coupled --json` payload
+pub(super) fn coupled_report(
+    db: &Database,
+    path: &str,
+    min_confidence: f64,
+) -> Result<serde_json::Value> {
+    let coupled = db.get_coupled_files(path, min_confidence)?;
+    Ok(json!({
+        "command": "coupled",
+        "path": path,
+        "count": coupled.len(),
+        "coupled": coupled,
+    }))
+}
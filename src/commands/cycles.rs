@@ -0,0 +1,58 @@
+use super::*;
+
+pub(super) fn cmd_cycles(root: &Path, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", cycles_report(&db)?);
+        return Ok(());
+    }
+
+    let cycles = ctx::analyzer::graph::dependency_cycles(&db)?;
+    println!();
+    if cycles.is_empty() {
+        println!("  {} No import cycles.", "OK".green().bold());
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} {} import cycles\n",
+        "Cycles".yellow().bold(),
+        cycles.len().to_string().cyan()
+    );
+    for (i, cycle) in cycles.iter().enumerate() {
+        println!(
+            "  {}. {} files",
+            i + 1,
+            cycle.files.len().to_string().cyan()
+        );
+        for file in &cycle.files {
+            println!("     {} {}", "·".dimmed(), file);
+        }
+        println!("     closed by:");
+        for edge in &cycle.closing_edges {
+            println!(
+                "     {} {} {} {}",
+                "WARN".yellow(),
+                edge.from.red(),
+                "→".dimmed(),
+                edge.to.red()
+            );
+            println!("       {}", edge.cycle.join(" → ").dimmed());
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Build the `cycles --json` payload
+pub(super) fn cycles_report(db: &Database) -> Result<serde_json::Value> {
+    let cycles = ctx::analyzer::graph::dependency_cycles(db)?;
+    Ok(json!({
+        "command": "cycles",
+        "count": cycles.len(),
+        "cycles": cycles,
+    }))
+}
@@ -5,25 +5,7 @@ pub(super) fn cmd_decisions(root: &Path, json_mode: bool) -> Result<()> {
     let decisions = db.get_decisions()?;
 
     if json_mode {
-        let entries: Vec<_> = decisions
-            .iter()
-            .map(|d| {
-                json!({
-                    "timestamp": d.timestamp,
-                    "source": d.source,
-                    "description": d.description,
-                    "commit_hash": d.commit_hash,
-                })
-            })
-            .collect();
-        println!(
-            "{}",
-            json!({
-                "command": "decisions",
-                "count": entries.len(),
-                "decisions": entries,
-            })
-        );
+        println!("{}", decisions_report(&db)?);
     } else {
         if decisions.is_empty() {
             println!("\n  {} No decisions recorded yet.", "·".dimmed());
@@ -83,3 +65,26 @@ pub(super) fn cmd_decisions(root: &Path, json_mode: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Build the `decisions --json` payload
+pub(super) fn decisions_report(db: &Database) -> Result<serde_json::Value> {
+    let entries: Vec<_> = db
+        .get_decisions()?
+        .iter()
+        .map(|d| {
+            json!({
+                "timestamp": d.timestamp,
+                "source": d.source,
+                "description": d.description,
+                "commit_hash": d.commit_hash,
+                "related_files": serde_json::from_str::<serde_json::Value>(&d.related_files)
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+    Ok(json!({
+        "command": "decisions",
+        "count": entries.len(),
+        "decisions": entries,
+    }))
+}
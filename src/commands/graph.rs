@@ -0,0 +1,217 @@
+use super::*;
+use crate::cli::GraphFormat;
+use ctx::analyzer::graph::{export_graph, GraphOptions};
+use ctx::db::models::{DependencyGraph, GraphNode};
+use std::collections::HashMap;
+
+pub(super) fn cmd_graph(
+    root: &Path,
+    options: &GraphOptions,
+    format: GraphFormat,
+    json_mode: bool,
+) -> Result<()> {
+    let db = ensure_initialized(root)?;
+    let format = if json_mode { GraphFormat::Json } else { format };
+
+    if format == GraphFormat::Json {
+        println!("{}", graph_report(&db, options, format)?);
+        return Ok(());
+    }
+
+    if let Some(path) = &options.root {
+        if db.get_files_under(path.trim_end_matches('/'))?.is_empty() {
+            eprintln!("  {} File or directory not found: {}", "ERROR".red(), path);
+            return Ok(());
+        }
+    }
+    let graph = export_graph(&db, options)?;
+    print!("{}", render(&graph, format, db.config.health.fragile_churn));
+
+    Ok(())
+}
+
+/// Build the `graph --json` payload; other formats are returned as text
+/// under `output`
+pub(super) fn graph_report(
+    db: &Database,
+    options: &GraphOptions,
+    format: GraphFormat,
+) -> Result<serde_json::Value> {
+    if let Some(path) = &options.root {
+        if db.get_files_under(path.trim_end_matches('/'))?.is_empty() {
+            return Ok(json!({
+                "command": "graph",
+                "error": format!("File or directory not found: {}", path),
+            }));
+        }
+    }
+
+    let graph = export_graph(db, options)?;
+    let mut report = json!({
+        "command": "graph",
+        "root": options.root,
+        "depth": options.depth,
+        "collapse": options.collapse,
+        "node_count": graph.nodes.len(),
+        "edge_count": graph.edges.len(),
+    });
+    if format == GraphFormat::Json {
+        report["nodes"] = json!(graph.nodes);
+        report["edges"] = json!(graph.edges);
+    } else {
+        report["format"] = json!(format!("{:?}", format).to_lowercase());
+        report["output"] = json!(render(&graph, format, db.config.health.fragile_churn));
+    }
+    Ok(report)
+}
+
+fn render(graph: &DependencyGraph, format: GraphFormat, hot_churn: f64) -> String {
+    match format {
+        GraphFormat::Dot => to_dot(graph, hot_churn),
+        GraphFormat::Mermaid => to_mermaid(graph, hot_churn),
+        GraphFormat::Graphml => to_graphml(graph),
+        GraphFormat::Json => serde_json::to_string_pretty(graph).unwrap_or_default() + "\n",
+    }
+}
+
+/// Short caption under the node name: size, and file count when collapsed
+fn caption(node: &GraphNode) -> String {
+    if node.files > 1 {
+        format!("{} files, {} lines", node.files, node.lines)
+    } else {
+        format!("{} lines", node.lines)
+    }
+}
+
+fn to_dot(graph: &DependencyGraph, hot_churn: f64) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let quote = |s: &str| format!("\"{}\"", escape(s));
+    let mut out = String::from("digraph ctx {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fillcolor=\"#eef3fb\"];\n");
+    for node in &graph.nodes {
+        let fill = if node.churn > hot_churn {
+            ", fillcolor=\"#f8d7da\""
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "  {} [label=\"{}\\n{}\", language={}, lines={}, commits={}, churn={:.2}, files={}{}];\n",
+            quote(&node.id),
+            escape(&node.id),
+            caption(node),
+            quote(&node.language),
+            node.lines,
+            node.commits,
+            node.churn,
+            node.files,
+            fill
+        ));
+    }
+    for edge in &graph.edges {
+        let label = if edge.weight > 1 {
+            format!(" [label=\"{}\", weight={}]", edge.weight, edge.weight)
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "  {} -> {}{};\n",
+            quote(&edge.from),
+            quote(&edge.to),
+            label
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn to_mermaid(graph: &DependencyGraph, hot_churn: f64) -> String {
+    // Paths are not valid Mermaid ids; number the nodes instead
+    let ids: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), format!("n{}", i)))
+        .collect();
+    let mut out = String::from("graph LR\n");
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "  {}[\"{}<br/><small>{}</small>\"]\n",
+            ids[node.id.as_str()],
+            node.id.replace('"', "#quot;"),
+            caption(node)
+        ));
+    }
+    for edge in &graph.edges {
+        let arrow = if edge.weight > 1 {
+            format!("-->|{}|", edge.weight)
+        } else {
+            "-->".to_string()
+        };
+        out.push_str(&format!(
+            "  {} {} {}\n",
+            ids[edge.from.as_str()],
+            arrow,
+            ids[edge.to.as_str()]
+        ));
+    }
+
+    let hot: Vec<&str> = graph
+        .nodes
+        .iter()
+        .filter(|node| node.churn > hot_churn)
+        .map(|node| ids[node.id.as_str()].as_str())
+        .collect();
+    if !hot.is_empty() {
+        out.push_str("  classDef hot fill:#f8d7da,stroke:#c0392b\n");
+        out.push_str(&format!("  class {} hot\n", hot.join(",")));
+    }
+    out
+}
+
+fn to_graphml(graph: &DependencyGraph) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+         \x20 <key id=\"language\" for=\"node\" attr.name=\"language\" attr.type=\"string\"/>\n\
+         \x20 <key id=\"lines\" for=\"node\" attr.name=\"lines\" attr.type=\"long\"/>\n\
+         \x20 <key id=\"commits\" for=\"node\" attr.name=\"commits\" attr.type=\"long\"/>\n\
+         \x20 <key id=\"churn\" for=\"node\" attr.name=\"churn\" attr.type=\"double\"/>\n\
+         \x20 <key id=\"files\" for=\"node\" attr.name=\"files\" attr.type=\"int\"/>\n\
+         \x20 <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n\
+         \x20 <graph id=\"ctx\" edgedefault=\"directed\">\n",
+    );
+    for node in &graph.nodes {
+        out.push_str(&format!(
+            "    <node id=\"{}\">\
+             <data key=\"language\">{}</data>\
+             <data key=\"lines\">{}</data>\
+             <data key=\"commits\">{}</data>\
+             <data key=\"churn\">{:.4}</data>\
+             <data key=\"files\">{}</data>\
+             </node>\n",
+            escape(&node.id),
+            escape(&node.language),
+            node.lines,
+            node.commits,
+            node.churn,
+            node.files
+        ));
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"weight\">{}</data></edge>\n",
+            escape(&edge.from),
+            escape(&edge.to),
+            edge.weight
+        ));
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
@@ -1,13 +1,14 @@
 use super::*;
 use anyhow::anyhow;
+use ctx::analyzer::scanner::PathFilter;
+use ctx::config::ScanConfig;
 use grep_regex::RegexMatcherBuilder;
 use grep_searcher::sinks::UTF8;
 use grep_searcher::SearcherBuilder;
-use ignore::WalkBuilder;
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
-struct GrepHit {
+pub(super) struct GrepHit {
     file: String,
     line: u64,
     text: String,
@@ -17,45 +18,71 @@ pub(super) fn cmd_grep(
     root: &Path,
     pattern: &str,
     max_results: usize,
+    filters: &ScanFilters,
     json_mode: bool,
 ) -> Result<()> {
-    let _db = ensure_initialized(root)?;
+    let mut db = ensure_initialized(root)?;
+    db.config.scan.add_globs(&filters.include, &filters.exclude);
     let limit = max_results.clamp(1, 200);
+    let hits = search_text(root, &db.config.scan, pattern, limit)?;
 
+    if json_mode {
+        println!("{}", grep_report(pattern, limit, &hits));
+        return Ok(());
+    }
+
+    println!();
+    if hits.is_empty() {
+        println!("  {} No text matches found.", "INFO".cyan().bold());
+    } else {
+        println!(
+            "  {} {} matches for \"{}\"",
+            "OK".green().bold(),
+            hits.len().to_string().cyan().bold(),
+            pattern.white()
+        );
+        for h in &hits {
+            println!("    {}:{}  {}", h.file.cyan(), h.line, h.text);
+        }
+        if hits.len() >= limit {
+            println!(
+                "  {} result limit reached ({limit})",
+                "INFO".yellow().bold()
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build the `grep --json` payload
+pub(super) fn grep_report(pattern: &str, limit: usize, hits: &[GrepHit]) -> serde_json::Value {
+    json!({
+        "command": "grep",
+        "pattern": pattern,
+        "count": hits.len(),
+        "limit": limit,
+        "results": hits,
+    })
+}
+
+/// Search repository files for `pattern`, stopping after `limit` hits
+pub(super) fn search_text(
+    root: &Path,
+    scan: &ScanConfig,
+    pattern: &str,
+    limit: usize,
+) -> Result<Vec<GrepHit>> {
     let matcher = RegexMatcherBuilder::new()
         .case_insensitive(false)
         .build(pattern)
         .map_err(|e| anyhow!("invalid grep pattern: {e}"))?;
 
     let mut hits: Vec<GrepHit> = Vec::new();
-    let walker = WalkBuilder::new(root)
-        .hidden(true)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .filter_entry(|entry| {
-            let name = entry.file_name().to_string_lossy();
-            !matches!(
-                name.as_ref(),
-                "node_modules"
-                    | ".git"
-                    | ".ctx"
-                    | "target"
-                    | "__pycache__"
-                    | ".next"
-                    | "dist"
-                    | "build"
-                    | ".venv"
-                    | "venv"
-                    | ".tox"
-                    | "vendor"
-                    | "coverage"
-                    | ".cache"
-            )
-        })
-        .build();
-
-    for entry in walker {
+    let filter = PathFilter::new(root, scan)?;
+
+    for entry in filter.walk(root) {
         if hits.len() >= limit {
             break;
         }
@@ -96,38 +123,5 @@ pub(super) fn cmd_grep(
         let _ = searcher.search_path(&matcher, path, &mut sink);
     }
 
-    if json_mode {
-        println!(
-            "{}",
-            json!({
-                "command": "grep",
-                "pattern": pattern,
-                "count": hits.len(),
-                "limit": limit,
-                "results": hits,
-            })
-        );
-        return Ok(());
-    }
-
-    println!();
-    if hits.is_empty() {
-        println!("  {} No text matches found.", "INFO".cyan().bold());
-    } else {
-        println!(
-            "  {} {} matches for \"{}\"",
-            "OK".green().bold(),
-            hits.len().to_string().cyan().bold(),
-            pattern.white()
-        );
-        for h in &hits {
-            println!("    {}:{}  {}", h.file.cyan(), h.line, h.text);
-        }
-        if hits.len() >= limit {
-            println!("  {} result limit reached ({limit})", "INFO".yellow().bold());
-        }
-    }
-    println!();
-
-    Ok(())
+    Ok(hits)
 }
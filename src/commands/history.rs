@@ -0,0 +1,103 @@
+use super::*;
+use ctx::db::models::FileHistoryEntry;
+use std::collections::HashSet;
+
+pub(super) fn cmd_history(root: &Path, path: &str, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", history_report(&db, path)?);
+        return Ok(());
+    }
+
+    let (current, entries) = file_history(&db, path)?;
+    println!();
+    if entries.is_empty() {
+        println!(
+            "  {} No git history recorded for \"{}\".",
+            "INFO".cyan().bold(),
+            path
+        );
+        println!();
+        return Ok(());
+    }
+
+    let authors: HashSet<&str> = entries.iter().map(|e| e.author.as_str()).collect();
+    println!(
+        "  {} {} ({} commits, {} authors)",
+        "History:".yellow().bold(),
+        current.white().bold(),
+        entries.len().to_string().cyan(),
+        authors.len().to_string().cyan()
+    );
+    let names = lineage(&entries);
+    if names.len() > 1 {
+        println!("  Lineage: {}", names.join(&" → ".dimmed().to_string()));
+    }
+    println!();
+
+    for entry in &entries {
+        let date = entry
+            .timestamp
+            .as_deref()
+            .and_then(|t| t.get(..10))
+            .unwrap_or("");
+        let note = match &entry.from_path {
+            Some(from) => format!(" ({} from {})", entry.status, from),
+            None if entry.path != current => format!(" ({} as {})", entry.status, entry.path),
+            None if entry.status != "modified" => format!(" ({})", entry.status),
+            None => String::new(),
+        };
+        println!(
+            "    {}  {}  {}  {}{}",
+            date.dimmed(),
+            entry.hash.get(..8).unwrap_or(&entry.hash).yellow(),
+            entry.author.cyan(),
+            entry.summary,
+            note.dimmed()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build the `history --json` payload
+pub(super) fn history_report(db: &Database, path: &str) -> Result<serde_json::Value> {
+    let (current, entries) = file_history(db, path)?;
+    Ok(json!({
+        "command": "history",
+        "path": current,
+        "lineage": lineage(&entries),
+        "count": entries.len(),
+        "commits": entries,
+    }))
+}
+
+/// History of `path`, or of the file it was renamed to
+fn file_history(db: &Database, path: &str) -> Result<(String, Vec<FileHistoryEntry>)> {
+    let entries = db.get_file_history(path)?;
+    if !entries.is_empty() {
+        return Ok((path.to_string(), entries));
+    }
+    match db.get_renamed_path(path)? {
+        Some(current) if current != path => {
+            let entries = db.get_file_history(&current)?;
+            Ok((current, entries))
+        }
+        _ => Ok((path.to_string(), entries)),
+    }
+}
+
+/// Names the file has had, oldest first
+fn lineage(entries: &[FileHistoryEntry]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for entry in entries.iter().rev() {
+        for name in entry.from_path.iter().chain([&entry.path]) {
+            if names.last() != Some(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+pub(super) fn cmd_hotspots(root: &Path, top: usize, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", hotspots_report(&db, top)?);
+        return Ok(());
+    }
+
+    let hotspots = db.get_hotspots(top)?;
+    println!();
+    if hotspots.is_empty() {
+        println!(
+            "  {} No hotspots: no parsed functions in files with git history.",
+            "INFO".cyan().bold()
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} top {} by complexity × commits\n",
+        "Hotspots".yellow().bold(),
+        hotspots.len().to_string().cyan()
+    );
+    println!(
+        "    {:>5}  {:>4}  {:>4}  {:>5}  {:>7}  {}",
+        "score".dimmed(),
+        "cc".dimmed(),
+        "nest".dimmed(),
+        "lines".dimmed(),
+        "commits".dimmed(),
+        "function".dimmed()
+    );
+    for h in &hotspots {
+        println!(
+            "    {:>5}  {:>4}  {:>4}  {:>5}  {:>7}  {} {}",
+            h.score.to_string().red().bold(),
+            h.complexity,
+            h.max_nesting,
+            h.end_line - h.start_line + 1,
+            h.commit_count,
+            h.name.white().bold(),
+            format!("{}:{}", h.path, h.start_line).dimmed()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Build the `hotspots --json` payload
+pub(super) fn hotspots_report(db: &Database, top: usize) -> Result<serde_json::Value> {
+    let hotspots: Vec<serde_json::Value> = db
+        .get_hotspots(top)?
+        .into_iter()
+        .map(|h| {
+            let lines = h.end_line - h.start_line + 1;
+            let mut value = json!(h);
+            value["lines"] = json!(lines);
+            value
+        })
+        .collect();
+    Ok(json!({
+        "command": "hotspots",
+        "count": hotspots.len(),
+        "hotspots": hotspots,
+    }))
+}
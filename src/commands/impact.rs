@@ -0,0 +1,123 @@
+use super::*;
+use ctx::analyzer::impact::{change_impact, ChangeImpact};
+use ctx::git::diff::{changed_ranges, DiffSpec};
+
+pub(super) fn cmd_impact(root: &Path, spec: &DiffSpec, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", impact_report(&db, root, spec)?);
+        return Ok(());
+    }
+
+    let changes = changed_ranges(root, spec)?;
+    println!();
+    if changes.is_empty() {
+        println!(
+            "  {} No changes in {}.",
+            "INFO".cyan().bold(),
+            spec_label(spec)
+        );
+        println!();
+        return Ok(());
+    }
+
+    let impact = change_impact(&db, &changes)?;
+    print_impact(spec, &impact);
+    println!();
+
+    Ok(())
+}
+
+/// Build the `impact --json` payload
+pub(super) fn impact_report(
+    db: &Database,
+    root: &Path,
+    spec: &DiffSpec,
+) -> Result<serde_json::Value> {
+    let changes = changed_ranges(root, spec)?;
+    let impact = change_impact(db, &changes)?;
+
+    let affected_files: Vec<_> = impact
+        .affected_files
+        .iter()
+        .map(|(path, depth)| json!({ "path": path, "depth": depth }))
+        .collect();
+
+    Ok(json!({
+        "command": "impact",
+        "diff": spec_label(spec),
+        "changed_files": impact.changed_files,
+        "affected_symbols": impact.affected_symbols,
+        "affected_files": affected_files,
+        "risk": impact.risk,
+    }))
+}
+
+fn print_impact(spec: &DiffSpec, impact: &ChangeImpact) {
+    println!(
+        "  {} {} ({} files changed)\n",
+        "Impact:".yellow().bold(),
+        spec_label(spec).white().bold(),
+        impact.changed_files.len().to_string().cyan()
+    );
+
+    for file in &impact.changed_files {
+        println!("    {} {} {}", "•".blue(), file.path, file.status.dimmed());
+        for sym in &file.symbols {
+            println!(
+                "        {} {} {}",
+                "~".yellow(),
+                sym.name,
+                format!("({} L{}-{})", sym.kind, sym.start_line, sym.end_line).dimmed()
+            );
+        }
+    }
+    println!();
+
+    if impact.affected_files.is_empty() {
+        println!(
+            "  {} Nothing outside the change depends on it",
+            "OK".green()
+        );
+        return;
+    }
+
+    println!(
+        "  {} {} files affected:",
+        "IMPACT".red(),
+        impact.affected_files.len().to_string().red().bold()
+    );
+    for (path, depth) in &impact.affected_files {
+        let marker = if *depth <= 1 { "→" } else { "↳" };
+        let callers: Vec<&str> = impact
+            .affected_symbols
+            .iter()
+            .filter(|i| &i.path == path)
+            .filter_map(|i| i.symbol.as_deref())
+            .collect();
+        if callers.is_empty() {
+            println!("    {} {}", marker.dimmed(), path);
+        } else {
+            println!(
+                "    {} {} {}",
+                marker.dimmed(),
+                path,
+                format!("({})", callers.join(", ")).dimmed()
+            );
+        }
+    }
+    println!();
+
+    println!("  Risk: {}", ctx::query::risk_label(impact.risk));
+}
+
+fn spec_label(spec: &DiffSpec) -> String {
+    match spec {
+        DiffSpec::WorkingTree => "working tree".to_string(),
+        DiffSpec::Staged => "staged changes".to_string(),
+        DiffSpec::Since(rev) => format!("changes since {}", rev),
+        DiffSpec::Range(from, to) => format!("{}..{}", from, to),
+        DiffSpec::MergeBase(from, to) => format!("{}...{}", from, to),
+    }
+}
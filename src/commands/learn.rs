@@ -10,15 +10,7 @@ pub(super) fn cmd_learn(
     db.insert_knowledge(note, "manual", file)?;
 
     if json_mode {
-        println!(
-            "{}",
-            json!({
-                "command": "learn",
-                "note": note,
-                "file": file,
-                "status": "recorded",
-            })
-        );
+        println!("{}", learn_report(note, file));
     } else {
         println!("\n  {} Knowledge recorded", "OK".green().bold());
         if let Some(f) = file {
@@ -30,3 +22,13 @@ pub(super) fn cmd_learn(
 
     Ok(())
 }
+
+/// Build the `learn --json` payload for a recorded note
+pub(super) fn learn_report(note: &str, file: Option<&str>) -> serde_json::Value {
+    json!({
+        "command": "learn",
+        "note": note,
+        "file": file,
+        "status": "recorded",
+    })
+}
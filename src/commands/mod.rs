@@ -7,16 +7,30 @@ use std::time::Instant;
 use ctx::analyzer;
 use ctx::db::Database;
 use ctx::git;
+use ctx::git::diff::DiffSpec;
 use ctx::watcher;
 
-use crate::cli::Commands;
+use crate::cli::{Commands, ScanFilters};
 
 mod blast_radius;
+mod calls;
+mod check;
+mod coupled;
+mod cycles;
 mod decisions;
+mod graph;
 mod grep;
+mod guard;
+mod history;
+mod hotspots;
+mod impact;
 mod init;
 mod learn;
 mod map;
+mod mcp;
+mod overview;
+mod owners;
+mod path;
 mod query;
 mod scan;
 mod status;
@@ -24,25 +38,90 @@ mod warnings;
 mod watch;
 
 pub fn run(command: Commands, root: &Path, json_mode: bool) -> Result<()> {
-    let is_watch_command = matches!(&command, Commands::Watch);
+    // Long-running commands manage their own watcher (or are the watcher).
+    let is_watch_command = matches!(&command, Commands::Watch { .. } | Commands::Mcp);
+    // Only `check` can fail without an error; it still exits non-zero
+    let mut passed = true;
 
     match command {
-        Commands::Init => init::cmd_init(root, json_mode)?,
-        Commands::Scan => scan::cmd_scan(root, json_mode)?,
+        Commands::Init { filters } => init::cmd_init(root, &filters, json_mode)?,
+        Commands::Scan {
+            filters,
+            clear_filters,
+        } => scan::cmd_scan(root, &filters, clear_filters, json_mode)?,
         Commands::Map => map::cmd_map(root, json_mode)?,
         Commands::Status => status::cmd_status(root, json_mode)?,
         Commands::Query { term } => query::cmd_query(root, &term, json_mode)?,
         Commands::Grep {
             pattern,
             max_results,
-        } => grep::cmd_grep(root, &pattern, max_results, json_mode)?,
-        Commands::BlastRadius { path } => blast_radius::cmd_blast_radius(root, &path, json_mode)?,
+            filters,
+        } => grep::cmd_grep(root, &pattern, max_results, &filters, json_mode)?,
+        Commands::BlastRadius {
+            path,
+            symbol,
+            depth,
+            coupled,
+        } => match symbol {
+            Some(symbol) => blast_radius::cmd_symbol_blast_radius(root, &symbol, depth, json_mode)?,
+            None => blast_radius::cmd_blast_radius(
+                root,
+                path.as_deref().unwrap_or_default(),
+                coupled,
+                json_mode,
+            )?,
+        },
+        Commands::Coupled {
+            path,
+            min_confidence,
+        } => coupled::cmd_coupled(root, &path, min_confidence, json_mode)?,
+        Commands::Check { sarif } => passed = check::cmd_check(root, sarif, json_mode)?,
+        Commands::Cycles => cycles::cmd_cycles(root, json_mode)?,
+        Commands::Path {
+            from,
+            to,
+            all,
+            max_hops,
+            limit,
+        } => path::cmd_path(root, &from, &to, all, max_hops, limit, json_mode)?,
+        Commands::Graph {
+            root: graph_root,
+            depth,
+            collapse,
+            format,
+        } => {
+            let options = analyzer::graph::GraphOptions {
+                root: graph_root,
+                depth,
+                collapse,
+            };
+            graph::cmd_graph(root, &options, format, json_mode)?
+        }
+        Commands::Hotspots { top } => hotspots::cmd_hotspots(root, top, json_mode)?,
+        Commands::Owners { target } => owners::cmd_owners(root, &target, json_mode)?,
+        Commands::History { path } => history::cmd_history(root, &path, json_mode)?,
+        Commands::Callers { symbol } => calls::cmd_callers(root, &symbol, json_mode)?,
+        Commands::Callees { symbol } => calls::cmd_callees(root, &symbol, json_mode)?,
+        Commands::Impact {
+            range,
+            staged,
+            since,
+        } => {
+            let spec = match (range, since) {
+                (Some(range), _) => DiffSpec::from_rev(&range),
+                (None, Some(since)) => DiffSpec::Since(since),
+                (None, None) if staged => DiffSpec::Staged,
+                (None, None) => DiffSpec::WorkingTree,
+            };
+            impact::cmd_impact(root, &spec, json_mode)?
+        }
         Commands::Decisions => decisions::cmd_decisions(root, json_mode)?,
         Commands::Learn { note, file } => {
             learn::cmd_learn(root, &note, file.as_deref(), json_mode)?
         }
         Commands::Warnings => warnings::cmd_warnings(root, json_mode)?,
-        Commands::Watch => watch::cmd_watch(root)?,
+        Commands::Watch { filters } => watch::cmd_watch(root, &filters)?,
+        Commands::Mcp => mcp::cmd_mcp(root)?,
     }
 
     // Agent-first default: keep context fresh in background unless this invocation is already `watch`.
@@ -50,6 +129,9 @@ pub fn run(command: Commands, root: &Path, json_mode: bool) -> Result<()> {
         watcher::ensure_background_watch(root).ok();
     }
 
+    if !passed {
+        anyhow::bail!("Architecture check failed");
+    }
     Ok(())
 }
 
@@ -62,3 +144,13 @@ fn ensure_initialized(root: &Path) -> Result<Database> {
     }
     Database::open(root)
 }
+
+/// Save `--include`/`--exclude` flags with the project, so the background
+/// watcher, MCP and later scans index the same files. Without flags the
+/// previously saved globs stay in effect.
+fn save_filters(db: &mut Database, filters: &ScanFilters) -> Result<()> {
+    if filters.include.is_empty() && filters.exclude.is_empty() {
+        return Ok(());
+    }
+    db.save_scan_globs(&filters.include, &filters.exclude)
+}
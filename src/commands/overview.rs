@@ -0,0 +1,230 @@
+use super::*;
+use regex::Regex;
+
+/// Docs and entry points the overview is drawn from, when present
+const OVERVIEW_SOURCES: &[&str] = &[
+    "README.md",
+    "readme.md",
+    "ARCHITECTURE.md",
+    "architecture.md",
+    "domain-integration.md",
+    "bot.md",
+    "apps/core/main.go",
+    "apps/core/routes.go",
+];
+
+/// Build the `ctx_overview` payload: an agent-ready project brief from the
+/// repository docs and layout. With `save_note`, it is also stored as the
+/// first knowledge note when the project has none yet.
+pub(super) fn overview_report(
+    db: &Database,
+    root: &Path,
+    save_note: bool,
+) -> Result<serde_json::Value> {
+    let sources: Vec<&str> = OVERVIEW_SOURCES
+        .iter()
+        .copied()
+        .filter(|p| root.join(p).exists())
+        .collect();
+    let docs = sources
+        .iter()
+        .map(|p| read_prefix(&root.join(p)))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    let mentions = |words: &[&str]| words.iter().any(|w| docs.contains(w));
+
+    let readme = Some(read_prefix(&root.join("README.md")))
+        .filter(|r| !r.is_empty())
+        .unwrap_or_else(|| read_prefix(&root.join("readme.md")));
+    let purpose = purpose_from_readme(&readme);
+    let modules = top_modules(root);
+
+    let has_apps = root.join("apps").exists();
+    let has_core = root.join("apps/core").exists();
+    let has_panel = root.join("apps/panel").exists();
+    let has_client = root.join("apps/client").exists();
+    let has_bot = root.join("bot").exists() || mentions(&["telegram"]);
+    let has_ws = mentions(&["websocket", "ws hub", "realtime"]);
+    let has_tenant = mentions(&["tenant"]);
+    let has_domain = mentions(&["domain", "dns"]);
+    let has_auth = mentions(&["auth", "jwt", "csrf", "totp"]);
+
+    let pick = |cond: bool, yes: &str, no: &str| if cond { yes } else { no }.to_string();
+    let bullets = vec![
+        format!(
+            "1) Product purpose: {}",
+            if purpose.is_empty() {
+                "This repository is a production-oriented software platform with a modular architecture."
+            } else {
+                &purpose
+            }
+        ),
+        format!(
+            "2) Primary users: {}{}{}.",
+            pick(has_panel, "admin and operations teams via the panel", "internal operators"),
+            pick(has_client, ", plus tenant/client end users", ""),
+            pick(has_bot, ", with bot-based remote operation support", ""),
+        ),
+        format!(
+            "3) Main modules: {}{}{}{}.",
+            pick(has_apps, "multi-app workspace under `apps/`", "monolithic project layout"),
+            pick(has_core, " with a backend core service", ""),
+            pick(has_panel, ", admin panel", ""),
+            pick(has_client, ", and client frontend", ""),
+        ),
+        format!(
+            "4) Backend responsibility: central API routing, service orchestration, and business logic{}.",
+            pick(has_tenant, " with tenant-aware isolation", ""),
+        ),
+        "5) Critical runtime flow: request handling across auth, admin operations, and domain-specific endpoints via backend route registration.".to_string(),
+        format!(
+            "6) Security posture: {}.",
+            pick(
+                has_auth,
+                "auth/session hardening with mechanisms like JWT/cookies/CSRF/TOTP and audit controls",
+                "access control and session management implemented in application services",
+            ),
+        ),
+        format!(
+            "7) Operational flow: {}.",
+            pick(
+                has_domain,
+                "domain, DNS, and SSL-related lifecycle management appears to be integrated into platform workflows",
+                "deployment and runtime operations are documented in project-specific integration docs",
+            ),
+        ),
+        format!(
+            "8) Realtime and integrations: {}{}",
+            pick(
+                has_ws,
+                "realtime event transport (WebSocket-style) is part of the platform architecture",
+                "realtime transport is not explicit in sampled docs",
+            ),
+            pick(
+                has_bot,
+                ", and bot integration is present for operational automation.",
+                ".",
+            ),
+        ),
+    ];
+
+    let knowledge_note = if !save_note {
+        "skipped"
+    } else if db.get_knowledge()?.is_empty() {
+        let mut note = vec![
+            "Project overview (auto-generated by ctx MCP):".to_string(),
+            String::new(),
+        ];
+        note.extend(bullets.iter().cloned());
+        note.push(String::new());
+        note.push(format!("Sources: {}", sources_label(&sources)));
+        if !modules.is_empty() {
+            note.push(format!("Top-level modules: {}", modules.join(", ")));
+        }
+        db.insert_knowledge(&note.join("\n"), "manual", None)?;
+        "saved"
+    } else {
+        "skipped"
+    };
+
+    Ok(json!({
+        "command": "overview",
+        "bullets": bullets,
+        "sources": sources,
+        "modules": modules,
+        "knowledge_note": knowledge_note,
+    }))
+}
+
+fn sources_label(sources: &[&str]) -> String {
+    if sources.is_empty() {
+        "none detected".to_string()
+    } else {
+        sources.join(", ")
+    }
+}
+
+/// The first 10,000 characters of a file, or "" if it cannot be read
+fn read_prefix(path: &Path) -> String {
+    std::fs::read_to_string(path)
+        .map(|text| text.chars().take(10_000).collect())
+        .unwrap_or_default()
+}
+
+/// Visible top-level directories, alphabetically, at most 8
+fn top_modules(root: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return vec![];
+    };
+    let mut modules: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    modules.sort();
+    modules.truncate(8);
+    modules
+}
+
+/// The first README line that reads like a description (not a heading,
+/// badge or license line), else its first substantial paragraph
+fn purpose_from_readme(readme: &str) -> String {
+    for line in readme.lines() {
+        let cleaned = compact(&strip_markdown_inline(line), 260);
+        if !is_bad_purpose(line, &cleaned) {
+            return compact(&cleaned, 220);
+        }
+    }
+
+    readme
+        .split("\n\n")
+        .map(|p| compact(&strip_markdown_inline(p), 260))
+        .find(|p| p.chars().count() >= 60 && p.chars().any(|c| c.is_ascii_alphabetic()))
+        .map(|p| compact(&p, 220))
+        .unwrap_or_default()
+}
+
+fn strip_markdown_inline(text: &str) -> String {
+    let image = Regex::new(r"!\[[^\]]*\]\([^)]+\)").unwrap();
+    let link = Regex::new(r"\[([^\]]+)\]\([^)]+\)").unwrap();
+    let code = Regex::new(r"`([^`]+)`").unwrap();
+    let text = image.replace_all(text, " ");
+    let text = link.replace_all(&text, "$1");
+    let text = code.replace_all(&text, "$1");
+    text.replace(['*', '_', '~'], " ")
+}
+
+/// Collapse whitespace and markdown punctuation, truncating to `max_len`
+fn compact(text: &str, max_len: usize) -> String {
+    let fences = Regex::new(r"(?s)```.*?```").unwrap();
+    let cleaned = fences
+        .replace_all(text, " ")
+        .replace(['#', '>', '*', '`', '|'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if cleaned.chars().count() > max_len {
+        let cut: String = cleaned.chars().take(max_len - 3).collect();
+        format!("{cut}...")
+    } else {
+        cleaned
+    }
+}
+
+fn is_bad_purpose(raw: &str, cleaned: &str) -> bool {
+    let raw = raw.trim();
+    let lower_raw = raw.to_lowercase();
+    let lower_clean = cleaned.to_lowercase();
+    raw.is_empty()
+        || cleaned.is_empty()
+        || raw.starts_with('#')
+        || raw.starts_with("![")
+        || lower_raw.contains("shields.io")
+        || ["license", "build status", "coverage", "badge"]
+            .iter()
+            .any(|w| lower_clean.contains(w))
+        || !cleaned.chars().any(|c| c.is_ascii_alphabetic())
+        || cleaned.chars().count() < 40
+}
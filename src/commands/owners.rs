@@ -0,0 +1,172 @@
+use super::*;
+use ctx::git::blame::{symbol_ownership, SymbolOwnership};
+
+/// Churn windows reported for a file, in days
+const CHURN_WINDOWS: [i64; 3] = [30, 90, 365];
+
+pub(super) fn cmd_owners(root: &Path, target: &str, json_mode: bool) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", owners_report(&db, root, target)?);
+        return Ok(());
+    }
+
+    println!();
+    if let Some(file_id) = db.get_file_id(target)? {
+        print_file_owners(&db, root, target, file_id)?;
+        return Ok(());
+    }
+
+    let definitions = ctx::query::find_symbol_targets(&db, target)?;
+    if definitions.is_empty() {
+        println!("  {} File or symbol not found: {}", "ERROR".red(), target);
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} {}\n",
+        "Owners:".yellow().bold(),
+        target.white().bold()
+    );
+    for (sym, path) in &definitions {
+        match symbol_ownership(root, path, std::slice::from_ref(sym)) {
+            Ok(owned) => {
+                for o in &owned {
+                    print_symbol(o, Some(path));
+                }
+            }
+            Err(e) => println!("    {} {}: {}", "WARN".yellow(), path, e),
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+fn print_file_owners(db: &Database, root: &Path, path: &str, file_id: i64) -> Result<()> {
+    println!("  {} {}\n", "Owners:".yellow().bold(), path.white().bold());
+
+    println!("  Churn:");
+    for window in db.get_file_churn(path, &CHURN_WINDOWS)? {
+        println!(
+            "    {:>4}  {} commits  {} {}",
+            format!("{}d", window.days).dimmed(),
+            window.commits.to_string().cyan(),
+            format!("+{}", window.additions).green(),
+            format!("-{}", window.deletions).red(),
+        );
+    }
+    println!();
+
+    let authors = db.get_file_authors(path)?;
+    if !authors.is_empty() {
+        println!("  Authors:");
+        for a in &authors {
+            println!(
+                "    {:>4}  {}  {}",
+                format!("{:.0}%", a.share * 100.0).yellow(),
+                a.author.cyan(),
+                format!("({} commits, +{} -{})", a.commits, a.additions, a.deletions).dimmed()
+            );
+        }
+        println!();
+    }
+
+    let symbols = db.get_symbols_for_file(file_id)?;
+    if symbols.is_empty() {
+        return Ok(());
+    }
+    match symbol_ownership(root, path, &symbols) {
+        Ok(owned) => {
+            println!("  Symbols (last touched):");
+            for o in owned.iter().take(30) {
+                print_symbol(o, None);
+            }
+            if owned.len() > 30 {
+                println!("    {} ... and {} more", "·".dimmed(), owned.len() - 30);
+            }
+        }
+        Err(e) => println!("  {} {}", "WARN".yellow(), e),
+    }
+    println!();
+
+    Ok(())
+}
+
+fn print_symbol(owned: &SymbolOwnership, path: Option<&str>) {
+    let location = match path {
+        Some(path) => format!("{}:{}-{}", path, owned.start_line, owned.end_line),
+        None => format!("L{}-{}", owned.start_line, owned.end_line),
+    };
+    let last = match &owned.last_touched {
+        Some(touch) => format!(
+            "{} {}{}",
+            touch.author,
+            touch.date.as_deref().unwrap_or(""),
+            touch
+                .commit
+                .as_deref()
+                .map(|c| format!(" ({})", c.get(..8).unwrap_or(c)))
+                .unwrap_or_default()
+        ),
+        None => "not in git".to_string(),
+    };
+    let authors: Vec<String> = owned
+        .authors
+        .iter()
+        .map(|a| format!("{} {}", a.author, a.lines))
+        .collect();
+    println!(
+        "    {} {} {}  {}  {}",
+        owned.kind.dimmed(),
+        owned.name.white().bold(),
+        location.dimmed(),
+        last.cyan(),
+        authors.join(", ").dimmed()
+    );
+}
+
+/// Build the `owners --json` payload
+pub(super) fn owners_report(db: &Database, root: &Path, target: &str) -> Result<serde_json::Value> {
+    if let Some(file_id) = db.get_file_id(target)? {
+        let symbols = db.get_symbols_for_file(file_id)?;
+        let (owned, blame_error) = match symbol_ownership(root, target, &symbols) {
+            Ok(owned) => (owned, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        return Ok(json!({
+            "command": "owners",
+            "path": target,
+            "churn": db.get_file_churn(target, &CHURN_WINDOWS)?,
+            "authors": db.get_file_authors(target)?,
+            "symbols": owned,
+            "blame_error": blame_error,
+        }));
+    }
+
+    let definitions = ctx::query::find_symbol_targets(db, target)?;
+    if definitions.is_empty() {
+        return Ok(json!({
+            "command": "owners",
+            "error": format!("File or symbol not found: {}", target),
+        }));
+    }
+
+    let mut entries = Vec::new();
+    for (sym, path) in &definitions {
+        let mut entry = match symbol_ownership(root, path, std::slice::from_ref(sym)) {
+            Ok(owned) => json!(owned.first()),
+            Err(e) => json!({ "name": sym.name, "blame_error": e.to_string() }),
+        };
+        entry["file"] = json!(path);
+        entries.push(entry);
+    }
+
+    Ok(json!({
+        "command": "owners",
+        "symbol": target,
+        "definitions": entries,
+    }))
+}
@@ -0,0 +1,130 @@
+use super::*;
+use ctx::analyzer::graph::import_paths;
+use ctx::db::models::ResolvedImport;
+
+pub(super) fn cmd_path(
+    root: &Path,
+    from: &str,
+    to: &str,
+    all: bool,
+    max_hops: usize,
+    limit: usize,
+    json_mode: bool,
+) -> Result<()> {
+    let db = ensure_initialized(root)?;
+
+    if json_mode {
+        println!("{}", path_report(&db, from, to, all, max_hops, limit)?);
+        return Ok(());
+    }
+
+    println!();
+    for path in [from, to] {
+        if db.get_file_id(path)?.is_none() {
+            println!("  {} File not found: {}", "ERROR".red(), path);
+            println!();
+            return Ok(());
+        }
+    }
+
+    let paths = import_paths(&db, from, to, all, max_hops, limit)?;
+    if paths.is_empty() {
+        println!(
+            "  {} No import chain from {} to {}",
+            "INFO".cyan().bold(),
+            from.white().bold(),
+            to.white().bold()
+        );
+        // The blast radius runs the other way; point at it if that is the link
+        if let Some(reverse) = import_paths(&db, to, from, false, 0, 1)?.first() {
+            println!(
+                "  {} {} imports {} ({} hops): ctx-agent path {} {}",
+                "HINT".yellow(),
+                to,
+                from,
+                reverse.len(),
+                to,
+                from
+            );
+        }
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "  {} {} {} {}\n",
+        "Path:".yellow().bold(),
+        from.white().bold(),
+        "→".dimmed(),
+        to.white().bold()
+    );
+    for (i, hops) in paths.iter().enumerate() {
+        if all {
+            println!(
+                "  {}",
+                format!("Chain {} of {} ({} hops)", i + 1, paths.len(), hops.len()).dimmed()
+            );
+        }
+        println!("    {}", from.cyan());
+        for hop in hops {
+            print_hop(hop);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn print_hop(hop: &ResolvedImport) {
+    let line = if hop.line > 0 {
+        format!("L{}", hop.line)
+    } else {
+        "L?".to_string()
+    };
+    let names = if hop.names.is_empty() {
+        String::new()
+    } else {
+        format!("  {{{}}}", hop.names.join(", "))
+    };
+    println!(
+        "      {} {}  {} {}{}",
+        "↓".dimmed(),
+        line.dimmed(),
+        hop.kind.dimmed(),
+        hop.import.yellow(),
+        names.dimmed()
+    );
+    println!("    {}", hop.target.cyan());
+}
+
+/// Build the `path --json` payload
+pub(super) fn path_report(
+    db: &Database,
+    from: &str,
+    to: &str,
+    all: bool,
+    max_hops: usize,
+    limit: usize,
+) -> Result<serde_json::Value> {
+    for path in [from, to] {
+        if db.get_file_id(path)?.is_none() {
+            return Ok(json!({
+                "command": "path",
+                "error": format!("File not found: {}", path),
+            }));
+        }
+    }
+
+    let paths: Vec<_> = import_paths(db, from, to, all, max_hops, limit)?
+        .into_iter()
+        .map(|hops| json!({ "hops": hops.len(), "chain": hops }))
+        .collect();
+    Ok(json!({
+        "command": "path",
+        "from": from,
+        "to": to,
+        "found": !paths.is_empty(),
+        "count": paths.len(),
+        "paths": paths,
+    }))
+}
@@ -4,27 +4,7 @@ pub(super) fn cmd_query(root: &Path, term: &str, json_mode: bool) -> Result<()>
     let db = ensure_initialized(root)?;
 
     if json_mode {
-        let results = db.search(term)?;
-        let entries: Vec<_> = results
-            .iter()
-            .map(|(name, path, kind, signature)| {
-                json!({
-                    "name": name,
-                    "kind": kind,
-                    "signature": signature,
-                    "file": path,
-                })
-            })
-            .collect();
-        println!(
-            "{}",
-            json!({
-                "command": "query",
-                "term": term,
-                "count": entries.len(),
-                "results": entries,
-            })
-        );
+        println!("{}", query_report(&db, term)?);
     } else {
         println!();
         ctx::query::execute_search(&db, term)?;
@@ -33,3 +13,28 @@ pub(super) fn cmd_query(root: &Path, term: &str, json_mode: bool) -> Result<()>
 
     Ok(())
 }
+
+/// Build the `query --json` payload
+pub(super) fn query_report(db: &Database, term: &str) -> Result<serde_json::Value> {
+    let results = db.search(term)?;
+    let entries: Vec<_> = results
+        .iter()
+        .map(|r| {
+            json!({
+                "name": r.name,
+                "kind": r.kind,
+                "signature": r.signature,
+                "file": r.path,
+                "start_line": r.start_line,
+                "end_line": r.end_line,
+                "metrics": r.metrics,
+            })
+        })
+        .collect();
+    Ok(json!({
+        "command": "query",
+        "term": term,
+        "count": entries.len(),
+        "results": entries,
+    }))
+}
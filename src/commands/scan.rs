@@ -1,67 +1,89 @@
 use super::*;
 
-pub(super) fn cmd_scan(root: &Path, json_mode: bool) -> Result<()> {
-    let db = ensure_initialized(root)?;
-    let start = Instant::now();
+pub(super) fn cmd_scan(
+    root: &Path,
+    filters: &ScanFilters,
+    clear_filters: bool,
+    json_mode: bool,
+) -> Result<()> {
+    let mut db = ensure_initialized(root)?;
+    if clear_filters {
+        db.save_scan_globs(&[], &[])?;
+    }
+    super::save_filters(&mut db, filters)?;
 
-    if !json_mode {
-        print!("  Scanning...");
+    if json_mode {
+        println!("{}", scan_report(&db, root)?);
+        return Ok(());
     }
 
+    let start = Instant::now();
+    print!("  Scanning...");
+
     let result = analyzer::analyze_project(&db, root)?;
     let git_result = git::analyze_git_history(&db, root)?;
     let total_symbols = db.count_symbols()?;
     let total_dependencies = db.count_dependencies()?;
     let elapsed = start.elapsed();
 
-    if json_mode {
-        println!(
-            "{}",
-            json!({
-                "command": "scan",
-                "total_files": result.total_files,
-                "analyzed_files": result.analyzed_files,
-                "skipped_files": result.skipped_files,
-                "removed_files": result.removed_files,
-                "parsed_symbols": result.total_symbols,
-                "parsed_dependencies": result.total_imports,
-                "symbols": total_symbols,
-                "dependencies": total_dependencies,
-                "commits_analyzed": git_result.commits_analyzed,
-                "elapsed_ms": elapsed.as_millis(),
-            })
-        );
-    } else {
-        println!(" {}", "done".green());
-        println!(
-            "    {} files ({} analyzed, {} unchanged, {} removed)",
-            result.total_files.to_string().cyan(),
-            result.analyzed_files.to_string().green(),
-            result.skipped_files.to_string().dimmed(),
-            result.removed_files.to_string().red(),
-        );
-        println!(
-            "    {} symbols parsed, {} dependencies parsed",
-            result.total_symbols.to_string().cyan(),
-            result.total_imports.to_string().cyan(),
-        );
-        println!(
-            "    {} total symbols, {} total dependencies",
-            total_symbols.to_string().cyan(),
-            total_dependencies.to_string().cyan(),
-        );
-        if git_result.error.is_none() {
-            println!(
-                "    {} git commits analyzed",
-                git_result.commits_analyzed.to_string().cyan()
-            );
-        }
+    println!(" {}", "done".green());
+    println!(
+        "    {} files ({} analyzed, {} unchanged, {} removed)",
+        result.total_files.to_string().cyan(),
+        result.analyzed_files.to_string().green(),
+        result.skipped_files.to_string().dimmed(),
+        result.removed_files.to_string().red(),
+    );
+    println!(
+        "    {} symbols parsed, {} dependencies parsed",
+        result.total_symbols.to_string().cyan(),
+        result.total_imports.to_string().cyan(),
+    );
+    println!(
+        "    {} total symbols, {} total dependencies",
+        total_symbols.to_string().cyan(),
+        total_dependencies.to_string().cyan(),
+    );
+    if git_result.error.is_none() {
         println!(
-            "  {} Completed in {:.1}s\n",
-            "OK".green(),
-            elapsed.as_secs_f64()
+            "    {} new git commits analyzed{}",
+            git_result.commits_analyzed.to_string().cyan(),
+            if git_result.rebuilt {
+                " (history rebuilt)".dimmed().to_string()
+            } else {
+                String::new()
+            }
         );
     }
+    println!(
+        "  {} Completed in {:.1}s\n",
+        "OK".green(),
+        elapsed.as_secs_f64()
+    );
 
     Ok(())
 }
+
+/// Re-scan the project and build the `scan --json` payload
+pub(super) fn scan_report(db: &Database, root: &Path) -> Result<serde_json::Value> {
+    let start = Instant::now();
+    let result = analyzer::analyze_project(db, root)?;
+    let git_result = git::analyze_git_history(db, root)?;
+
+    Ok(json!({
+        "command": "scan",
+        "total_files": result.total_files,
+        "analyzed_files": result.analyzed_files,
+        "skipped_files": result.skipped_files,
+        "removed_files": result.removed_files,
+        "parsed_symbols": result.total_symbols,
+        "parsed_dependencies": result.total_imports,
+        "parsed_calls": result.total_calls,
+        "symbols": db.count_symbols()?,
+        "dependencies": db.count_dependencies()?,
+        "references": db.count_references()?,
+        "commits_analyzed": git_result.commits_analyzed,
+        "history_rebuilt": git_result.rebuilt,
+        "elapsed_ms": start.elapsed().as_millis(),
+    }))
+}
@@ -3,11 +3,12 @@ use super::*;
 pub(super) fn cmd_status(root: &Path, json_mode: bool) -> Result<()> {
     let db = ensure_initialized(root)?;
 
-    let project_name = root
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "project".to_string());
+    if json_mode {
+        println!("{}", status_report(&db, root)?);
+        return Ok(());
+    }
 
+    let project_name = project_name(root);
     let total_files = db.count_files()?;
     let total_lines = db.total_lines()?;
     let total_symbols = db.count_symbols()?;
@@ -17,94 +18,105 @@ pub(super) fn cmd_status(root: &Path, json_mode: bool) -> Result<()> {
     let decisions = db.get_decisions()?;
     let knowledge = db.get_knowledge()?;
 
-    if json_mode {
-        let health = db.get_file_health()?;
-        let fragile_count = health.iter().filter(|h| h.is_fragile).count();
-        let dead_count = health.iter().filter(|h| h.is_dead).count();
-
-        let kinds: serde_json::Map<String, serde_json::Value> = symbol_kinds
-            .iter()
-            .map(|(k, v)| (k.clone(), json!(v)))
-            .collect();
-
-        let langs: Vec<_> = lang_stats
-            .iter()
-            .map(|(l, c, lines)| json!({"language": l, "files": c, "lines": lines}))
-            .collect();
-
-        println!(
-            "{}",
-            json!({
-                "command": "status",
-                "project": project_name,
-                "files": total_files,
-                "lines": total_lines,
-                "symbols": total_symbols,
-                "dependencies": total_deps,
-                "decisions": decisions.len(),
-                "knowledge_notes": knowledge.len(),
-                "symbol_kinds": kinds,
-                "languages": langs,
-                "fragile_files": fragile_count,
-                "dead_files": dead_count,
-            })
-        );
-    } else {
-        println!(
-            "\n  {} {} {}\n",
-            "ctx-agent".cyan().bold(),
-            "—".dimmed(),
-            project_name.white().bold(),
-        );
-
-        println!("  Files: {}", total_files.to_string().cyan().bold());
-        println!("  Lines: {}", total_lines.to_string().cyan().bold());
-        println!("  Symbols: {}", total_symbols.to_string().cyan().bold());
-        println!("  Dependencies: {}", total_deps.to_string().cyan().bold());
-        println!(
-            "  Decisions: {} tracked",
-            decisions.len().to_string().cyan().bold()
-        );
-        println!("  Notes: {}", knowledge.len().to_string().cyan().bold());
-
-        if !symbol_kinds.is_empty() {
-            println!("\n  {}", "Symbols:".white().bold());
-            for (kind, count) in &symbol_kinds {
-                println!("    {:>12}: {}", kind, count.to_string().cyan());
-            }
-        }
+    println!(
+        "\n  {} {} {}\n",
+        "ctx-agent".cyan().bold(),
+        "—".dimmed(),
+        project_name.white().bold(),
+    );
+
+    println!("  Files: {}", total_files.to_string().cyan().bold());
+    println!("  Lines: {}", total_lines.to_string().cyan().bold());
+    println!("  Symbols: {}", total_symbols.to_string().cyan().bold());
+    println!("  Dependencies: {}", total_deps.to_string().cyan().bold());
+    println!(
+        "  Decisions: {} tracked",
+        decisions.len().to_string().cyan().bold()
+    );
+    println!("  Notes: {}", knowledge.len().to_string().cyan().bold());
 
-        if !lang_stats.is_empty() {
-            println!("\n  {}", "Languages:".white().bold());
-            for (lang, count, lines) in &lang_stats {
-                println!("    {:>12}: {} files, {} lines", lang.cyan(), count, lines);
-            }
+    if !symbol_kinds.is_empty() {
+        println!("\n  {}", "Symbols:".white().bold());
+        for (kind, count) in &symbol_kinds {
+            println!("    {:>12}: {}", kind, count.to_string().cyan());
         }
+    }
 
-        let health = db.get_file_health()?;
-        let fragile: Vec<_> = health.iter().filter(|h| h.is_fragile).collect();
-        let dead: Vec<_> = health.iter().filter(|h| h.is_dead).collect();
-
-        if !fragile.is_empty() || !dead.is_empty() {
-            println!("\n  {}", "Health:".white().bold());
-            if !fragile.is_empty() {
-                println!(
-                    "    {} {} fragile files (high churn + many dependents)",
-                    "WARN".yellow(),
-                    fragile.len()
-                );
-            }
-            if !dead.is_empty() {
-                println!(
-                    "    {} {} potentially dead files (no commits, no dependents)",
-                    "WARN".dimmed(),
-                    dead.len()
-                );
-            }
+    if !lang_stats.is_empty() {
+        println!("\n  {}", "Languages:".white().bold());
+        for (lang, count, lines) in &lang_stats {
+            println!("    {:>12}: {} files, {} lines", lang.cyan(), count, lines);
         }
+    }
+
+    let health = db.get_file_health()?;
+    let fragile: Vec<_> = health.iter().filter(|h| h.is_fragile).collect();
+    let dead: Vec<_> = health.iter().filter(|h| h.is_dead).collect();
 
-        println!();
+    if !fragile.is_empty() || !dead.is_empty() {
+        println!("\n  {}", "Health:".white().bold());
+        if !fragile.is_empty() {
+            println!(
+                "    {} {} fragile files (high churn + many dependents)",
+                "WARN".yellow(),
+                fragile.len()
+            );
+        }
+        if !dead.is_empty() {
+            println!(
+                "    {} {} potentially dead files (no commits, no dependents)",
+                "WARN".dimmed(),
+                dead.len()
+            );
+        }
     }
 
+    println!();
+
     Ok(())
 }
+
+/// Build the `status --json` payload
+pub(super) fn status_report(db: &Database, root: &Path) -> Result<serde_json::Value> {
+    let symbol_kinds = db.count_symbols_by_kind()?;
+    let lang_stats = db.language_stats()?;
+    let health = db.get_file_health()?;
+    let fragile_count = health.iter().filter(|h| h.is_fragile).count();
+    let dead_count = health.iter().filter(|h| h.is_dead).count();
+
+    let kinds: serde_json::Map<String, serde_json::Value> = symbol_kinds
+        .iter()
+        .map(|(k, v)| (k.clone(), json!(v)))
+        .collect();
+
+    let langs: Vec<_> = lang_stats
+        .iter()
+        .map(|(l, c, lines)| json!({"language": l, "files": c, "lines": lines}))
+        .collect();
+
+    Ok(json!({
+        "command": "status",
+        "project": project_name(root),
+        "files": db.count_files()?,
+        "lines": db.total_lines()?,
+        "symbols": db.count_symbols()?,
+        "dependencies": db.count_dependencies()?,
+        "references": db.count_references()?,
+        "decisions": db.get_decisions()?.len(),
+        "knowledge_notes": db.get_knowledge()?.len(),
+        "symbol_kinds": kinds,
+        "languages": langs,
+        "fragile_files": fragile_count,
+        "dead_files": dead_count,
+        "config": {
+            "sources": db.config.sources,
+            "settings": db.config,
+        },
+    }))
+}
+
+fn project_name(root: &Path) -> String {
+    root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string())
+}
@@ -7,65 +7,21 @@ pub(super) fn cmd_warnings(root: &Path, json_mode: bool) -> Result<()> {
 
     let fragile: Vec<_> = health.iter().filter(|h| h.is_fragile).collect();
     let dead: Vec<_> = health.iter().filter(|h| h.is_dead).collect();
-    let large: Vec<_> = health.iter().filter(|h| h.line_count > 500).collect();
+    let large: Vec<_> = health.iter().filter(|h| h.is_large).collect();
+    let single_owner: Vec<_> = health.iter().filter(|h| h.is_single_owner).collect();
+    let oversized = db.get_oversized_functions()?;
+    let cycles = ctx::analyzer::graph::dependency_cycles(&db)?;
 
     if json_mode {
-        let fragile_entries: Vec<_> = fragile
-            .iter()
-            .map(|f| {
-                json!({
-                    "path": f.path,
-                    "commit_count": f.commit_count,
-                    "dependents": f.dependents_count,
-                    "churn_score": f.churn_score,
-                })
-            })
-            .collect();
-
-        let large_entries: Vec<_> = large
-            .iter()
-            .map(|f| {
-                json!({
-                    "path": f.path,
-                    "lines": f.line_count,
-                    "language": f.language,
-                })
-            })
-            .collect();
-
-        let dead_entries: Vec<_> = dead
-            .iter()
-            .map(|f| {
-                json!({
-                    "path": f.path,
-                    "language": f.language,
-                })
-            })
-            .collect();
-
-        let knowledge_entries: Vec<_> = knowledge
-            .iter()
-            .map(|k| {
-                json!({
-                    "content": k.content,
-                    "file": k.related_file,
-                })
-            })
-            .collect();
-
-        println!(
-            "{}",
-            json!({
-                "command": "warnings",
-                "total_warnings": fragile.len() + dead.len() + large.len() + knowledge.len(),
-                "fragile_files": fragile_entries,
-                "large_files": large_entries,
-                "dead_files": dead_entries,
-                "knowledge_warnings": knowledge_entries,
-            })
-        );
+        println!("{}", warnings_report(&db)?);
     } else {
-        let total_warnings = fragile.len() + dead.len() + large.len() + knowledge.len();
+        let total_warnings = fragile.len()
+            + cycles.len()
+            + dead.len()
+            + large.len()
+            + oversized.len()
+            + single_owner.len()
+            + knowledge.len();
 
         if total_warnings == 0 {
             println!("\n  {} No warnings - looking good!\n", "OK".green().bold());
@@ -93,8 +49,32 @@ pub(super) fn cmd_warnings(root: &Path, json_mode: bool) -> Result<()> {
             println!();
         }
 
+        if !cycles.is_empty() {
+            println!("  Import cycles:");
+            for cycle in cycles.iter().take(10) {
+                let loop_path = cycle
+                    .closing_edges
+                    .first()
+                    .map(|e| e.cycle.join(" → "))
+                    .unwrap_or_default();
+                println!(
+                    "    {} {} files — {}",
+                    "WARN".yellow(),
+                    cycle.files.len().to_string().cyan(),
+                    loop_path.red()
+                );
+            }
+            if cycles.len() > 10 {
+                println!("    {} ... and {} more", "·".dimmed(), cycles.len() - 10);
+            }
+            println!();
+        }
+
         if !large.is_empty() {
-            println!("  Large files (>500 lines):");
+            println!(
+                "  Large files (>{} lines):",
+                db.config.health.large_file_lines
+            );
             for f in &large {
                 println!(
                     "    {} {} — {} lines ({})",
@@ -107,6 +87,54 @@ pub(super) fn cmd_warnings(root: &Path, json_mode: bool) -> Result<()> {
             println!();
         }
 
+        if !oversized.is_empty() {
+            println!("  Oversized functions:");
+            for f in oversized.iter().take(10) {
+                let exceeds = |name: &str, text: String| {
+                    if f.exceeds.iter().any(|e| e == name) {
+                        text.red().to_string()
+                    } else {
+                        text.dimmed().to_string()
+                    }
+                };
+                println!(
+                    "    {} {} {} — {}, {}, {}, {}",
+                    "·".dimmed(),
+                    f.name.yellow(),
+                    format!("{}:{}", f.path, f.start_line).dimmed(),
+                    exceeds("lines", format!("{} lines", f.lines)),
+                    exceeds("complexity", format!("complexity {}", f.metrics.complexity)),
+                    exceeds("nesting", format!("nesting {}", f.metrics.max_nesting)),
+                    exceeds("params", format!("{} params", f.metrics.param_count)),
+                );
+            }
+            if oversized.len() > 10 {
+                println!("    {} ... and {} more", "·".dimmed(), oversized.len() - 10);
+            }
+            println!();
+        }
+
+        if !single_owner.is_empty() {
+            println!("  Bus-factor risk (single contributor):");
+            for f in single_owner.iter().take(10) {
+                println!(
+                    "    {} {} — {} commits, all by {}",
+                    "·".dimmed(),
+                    f.path.yellow(),
+                    f.commit_count.to_string().cyan(),
+                    f.owner.as_deref().unwrap_or("one author").cyan(),
+                );
+            }
+            if single_owner.len() > 10 {
+                println!(
+                    "    {} ... and {} more",
+                    "·".dimmed(),
+                    single_owner.len() - 10
+                );
+            }
+            println!();
+        }
+
         if !dead.is_empty() {
             println!("  Potentially dead files (no git history, no dependents):");
             for f in dead.iter().take(10) {
@@ -144,3 +172,88 @@ pub(super) fn cmd_warnings(root: &Path, json_mode: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Build the `warnings --json` payload
+pub(super) fn warnings_report(db: &Database) -> Result<serde_json::Value> {
+    let health = db.get_file_health()?;
+    let knowledge = db.get_warnings_knowledge()?;
+
+    let fragile: Vec<_> = health.iter().filter(|h| h.is_fragile).collect();
+    let dead: Vec<_> = health.iter().filter(|h| h.is_dead).collect();
+    let large: Vec<_> = health.iter().filter(|h| h.is_large).collect();
+    let single_owner: Vec<_> = health.iter().filter(|h| h.is_single_owner).collect();
+    let oversized = db.get_oversized_functions()?;
+    let cycles = ctx::analyzer::graph::dependency_cycles(db)?;
+
+    let fragile_entries: Vec<_> = fragile
+        .iter()
+        .map(|f| {
+            json!({
+                "path": f.path,
+                "commit_count": f.commit_count,
+                "dependents": f.dependents_count,
+                "churn_score": f.churn_score,
+            })
+        })
+        .collect();
+
+    let large_entries: Vec<_> = large
+        .iter()
+        .map(|f| {
+            json!({
+                "path": f.path,
+                "lines": f.line_count,
+                "language": f.language,
+            })
+        })
+        .collect();
+
+    let single_owner_entries: Vec<_> = single_owner
+        .iter()
+        .map(|f| {
+            json!({
+                "path": f.path,
+                "commit_count": f.commit_count,
+                "owner": f.owner,
+            })
+        })
+        .collect();
+
+    let dead_entries: Vec<_> = dead
+        .iter()
+        .map(|f| {
+            json!({
+                "path": f.path,
+                "language": f.language,
+            })
+        })
+        .collect();
+
+    let knowledge_entries: Vec<_> = knowledge
+        .iter()
+        .map(|k| {
+            json!({
+                "content": k.content,
+                "file": k.related_file,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "command": "warnings",
+        "total_warnings": fragile.len()
+            + cycles.len()
+            + dead.len()
+            + large.len()
+            + oversized.len()
+            + single_owner.len()
+            + knowledge.len(),
+        "fragile_files": fragile_entries,
+        "import_cycles": cycles,
+        "large_files": large_entries,
+        "oversized_functions": oversized,
+        "bus_factor_files": single_owner_entries,
+        "dead_files": dead_entries,
+        "knowledge_warnings": knowledge_entries,
+    }))
+}
@@ -1,8 +1,9 @@
 use super::*;
 
-pub(super) fn cmd_watch(root: &Path) -> Result<()> {
-    let db = ensure_initialized(root)?;
-    drop(db); // Close db before watcher opens its own
+pub(super) fn cmd_watch(root: &Path, filters: &ScanFilters) -> Result<()> {
+    let mut db = ensure_initialized(root)?;
+    super::save_filters(&mut db, filters)?;
+    drop(db); // Close db before watcher opens its own, which reads the saved globs
 
     println!("\n  {} — Watch Mode\n", "ctx-agent".cyan().bold());
     watcher::watch_project(root)?;
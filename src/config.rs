@@ -0,0 +1,357 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::db::models::SymbolMetrics;
+
+/// Per-project configuration file, at the project root
+pub const PROJECT_CONFIG_FILE: &str = ".ctx-agent.toml";
+
+/// Tunable thresholds and lists.
+///
+/// Loaded from `~/.ctx-agent/config.toml` (user defaults) overlaid with
+/// `<project>/.ctx-agent.toml`. Every key is optional; missing keys keep the
+/// built-in defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub scan: ScanConfig,
+    pub git: GitConfig,
+    pub health: HealthConfig,
+    pub risk: RiskConfig,
+    pub coupling: CouplingConfig,
+    pub architecture: ArchitectureConfig,
+    /// Config files that were applied, lowest precedence first
+    #[serde(skip)]
+    pub sources: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanConfig {
+    /// Directory names skipped by the scanner, watcher and grep.
+    /// Setting this replaces the default list.
+    pub ignored_dirs: Vec<String>,
+    /// Gitignore-style globs; when set, only matching files are indexed
+    pub include: Vec<String>,
+    /// Gitignore-style globs for files and directories to skip
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// Most recent commits mined for file stats and decisions
+    pub max_commits: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Files with more lines than this are reported as large
+    pub large_file_lines: i64,
+    /// A file is fragile when its churn score exceeds this...
+    pub fragile_churn: f64,
+    /// ...and more than this many files depend on it
+    pub fragile_dependents: i64,
+    /// Single-contributor files with at least this many commits are a bus-factor risk
+    pub bus_factor_min_commits: i64,
+    /// A function is oversized when it exceeds any of these
+    pub large_function_lines: i64,
+    pub large_function_complexity: i64,
+    pub large_function_nesting: i64,
+    pub large_function_params: i64,
+}
+
+/// Minimum number of affected files for each blast-radius risk level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskConfig {
+    pub medium: usize,
+    pub high: usize,
+    pub critical: usize,
+}
+
+/// Co-change coupling mined from git history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CouplingConfig {
+    /// Commits touching more files than this (mass renames, reformats) are ignored
+    pub max_commit_files: usize,
+    /// Minimum number of shared commits for a pair to be recorded
+    pub min_support: usize,
+    /// Minimum confidence for a coupled file to join the historical blast radius
+    pub min_confidence: f64,
+}
+
+/// Module boundaries evaluated by `ctx-agent check`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchitectureConfig {
+    /// Named groups of files, e.g. `domain = ["src/domain/**"]`
+    pub layers: BTreeMap<String, Vec<String>>,
+    pub rules: Vec<LayerRule>,
+}
+
+/// Files matching `from` must not import files matching any of `forbid`.
+/// Each pattern is a layer name or a glob relative to the project root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayerRule {
+    pub from: String,
+    pub forbid: Vec<String>,
+    /// Shown with every violation of this rule
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            ignored_dirs: [
+                "node_modules",
+                ".git",
+                ".ctx",
+                "target",
+                "__pycache__",
+                ".next",
+                "dist",
+                "build",
+                ".venv",
+                "venv",
+                ".tox",
+                "vendor",
+                "coverage",
+                ".cache",
+            ]
+            .iter()
+            .map(|d| d.to_string())
+            .collect(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self { max_commits: 1000 }
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            large_file_lines: 500,
+            fragile_churn: 0.7,
+            fragile_dependents: 3,
+            bus_factor_min_commits: 3,
+            large_function_lines: 80,
+            large_function_complexity: 15,
+            large_function_nesting: 4,
+            large_function_params: 6,
+        }
+    }
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            medium: 1,
+            high: 6,
+            critical: 21,
+        }
+    }
+}
+
+impl Default for CouplingConfig {
+    fn default() -> Self {
+        Self {
+            max_commit_files: 30,
+            min_support: 2,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+impl Config {
+    /// Load the user-level defaults and the project file, if present
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let mut candidates = Vec::new();
+        if let Ok(home) = std::env::var("HOME") {
+            candidates.push(PathBuf::from(home).join(".ctx-agent").join("config.toml"));
+        }
+        candidates.push(project_root.join(PROJECT_CONFIG_FILE));
+
+        let mut merged = toml::Table::new();
+        let mut sources = Vec::new();
+        for path in candidates {
+            if !path.is_file() {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let table: toml::Table = toml::from_str(&text)
+                .with_context(|| format!("Invalid config file {}", path.display()))?;
+            merge_tables(&mut merged, table);
+            sources.push(path);
+        }
+
+        let mut config: Config = toml::Value::Table(merged)
+            .try_into()
+            .context("Invalid ctx-agent configuration")?;
+        config.sources = sources;
+        Ok(config)
+    }
+}
+
+impl ScanConfig {
+    /// Whether a directory name is skipped
+    pub fn is_ignored_dir(&self, name: &str) -> bool {
+        self.ignored_dirs.iter().any(|d| d == name)
+    }
+
+    /// Add `--include`/`--exclude` globs given on the command line
+    pub fn add_globs(&mut self, include: &[String], exclude: &[String]) {
+        self.include.extend(include.iter().cloned());
+        self.exclude.extend(exclude.iter().cloned());
+    }
+}
+
+impl HealthConfig {
+    pub fn is_fragile(&self, churn_score: f64, dependents: i64) -> bool {
+        churn_score > self.fragile_churn && dependents > self.fragile_dependents
+    }
+
+    pub fn is_large(&self, line_count: i64) -> bool {
+        line_count > self.large_file_lines
+    }
+
+    pub fn is_single_owner(&self, contributors: i64, commit_count: i64) -> bool {
+        contributors == 1 && commit_count >= self.bus_factor_min_commits
+    }
+
+    /// Which function thresholds are exceeded: "lines", "complexity",
+    /// "nesting", "params"
+    pub fn oversized_function(&self, lines: i64, metrics: &SymbolMetrics) -> Vec<&'static str> {
+        [
+            ("lines", lines > self.large_function_lines),
+            (
+                "complexity",
+                metrics.complexity > self.large_function_complexity,
+            ),
+            ("nesting", metrics.max_nesting > self.large_function_nesting),
+            ("params", metrics.param_count > self.large_function_params),
+        ]
+        .into_iter()
+        .filter(|(_, exceeded)| *exceeded)
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+impl RiskConfig {
+    /// Risk label for a blast radius touching `affected_files` files
+    pub fn level(&self, affected_files: usize) -> &'static str {
+        if affected_files >= self.critical {
+            "critical"
+        } else if affected_files >= self.high {
+            "high"
+        } else if affected_files >= self.medium {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+}
+
+/// Overlay `overlay` onto `base`, merging nested tables key by key
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(nested)) => {
+                merge_tables(existing, nested)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_match_builtin_thresholds() {
+        let config = Config::default();
+        assert!(config.scan.is_ignored_dir("node_modules"));
+        assert_eq!(config.git.max_commits, 1000);
+        assert!(config.health.is_large(501));
+        assert!(!config.health.is_large(500));
+        assert!(config.health.is_fragile(0.8, 4));
+        assert!(config.health.is_single_owner(1, 3));
+        assert!(!config.health.is_single_owner(1, 2));
+        assert!(!config.health.is_single_owner(2, 10));
+        let metrics = SymbolMetrics {
+            complexity: 16,
+            max_nesting: 4,
+            param_count: 2,
+            statement_count: 30,
+        };
+        assert_eq!(
+            config.health.oversized_function(81, &metrics),
+            vec!["lines", "complexity"]
+        );
+        assert!(config
+            .health
+            .oversized_function(
+                80,
+                &SymbolMetrics {
+                    complexity: 15,
+                    ..metrics
+                }
+            )
+            .is_empty());
+        assert_eq!(config.risk.level(0), "low");
+        assert_eq!(config.risk.level(5), "medium");
+        assert_eq!(config.risk.level(6), "high");
+        assert_eq!(config.risk.level(21), "critical");
+    }
+
+    #[test]
+    fn project_overrides_user_defaults_per_key() {
+        let mut merged: toml::Table =
+            toml::from_str("[git]\nmax_commits = 200\n[health]\nlarge_file_lines = 800\n").unwrap();
+        merge_tables(
+            &mut merged,
+            toml::from_str("[health]\nfragile_dependents = 10\n").unwrap(),
+        );
+
+        let config: Config = toml::Value::Table(merged).try_into().unwrap();
+        assert_eq!(config.git.max_commits, 200);
+        assert_eq!(config.health.large_file_lines, 800);
+        assert_eq!(config.health.fragile_dependents, 10);
+        assert_eq!(config.health.fragile_churn, 0.7);
+    }
+
+    #[test]
+    fn architecture_rules_parse() {
+        let config: Config = toml::from_str(
+            "[architecture.layers]\ndomain = [\"src/domain/**\"]\n\n\
+             [[architecture.rules]]\nfrom = \"domain\"\nforbid = [\"src/infra/**\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.architecture.layers["domain"], vec!["src/domain/**"]);
+        assert_eq!(config.architecture.rules[0].forbid, vec!["src/infra/**"]);
+        assert!(config.architecture.rules[0].reason.is_none());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let result: Result<Config, _> = toml::from_str("[scan]\nignored = []\n");
+        assert!(result.is_err());
+    }
+}
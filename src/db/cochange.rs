@@ -0,0 +1,132 @@
+use super::*;
+
+impl Database {
+    // =================================================================
+    // Co-change operations
+    // =================================================================
+
+    /// Recompute co-change pairs from the stored commits and return how many
+    /// directed pairs were recorded. Commits touching more than
+    /// `max_commit_files` files are ignored; single-file commits couple
+    /// nothing but still count toward a file's total.
+    pub fn refresh_cochange(&self, max_commit_files: usize, min_support: usize) -> Result<usize> {
+        self.conn.execute("DELETE FROM cochange", [])?;
+        let inserted = self.conn.execute(
+            "WITH commits AS (
+                 SELECT commit_id FROM commit_files
+                 GROUP BY commit_id HAVING COUNT(DISTINCT path) <= ?1
+             ),
+             touches AS (
+                 SELECT DISTINCT commit_id, path FROM commit_files
+                 WHERE commit_id IN (SELECT commit_id FROM commits)
+             ),
+             totals AS (
+                 SELECT path, COUNT(*) AS commit_count FROM touches GROUP BY path
+             )
+             INSERT INTO cochange (path, coupled_path, support, confidence)
+             SELECT a.path, b.path, COUNT(*), CAST(COUNT(*) AS REAL) / t.commit_count
+             FROM touches a
+             JOIN touches b ON b.commit_id = a.commit_id AND b.path <> a.path
+             JOIN totals t ON t.path = a.path
+             GROUP BY a.path, b.path
+             HAVING COUNT(*) >= ?2",
+            rusqlite::params![max_commit_files as i64, min_support as i64],
+        )?;
+        Ok(inserted)
+    }
+
+    /// Indexed files that change together with `path`, strongest first
+    pub fn get_coupled_files(&self, path: &str, min_confidence: f64) -> Result<Vec<CoupledFile>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.coupled_path, c.support, c.confidence,
+                    EXISTS (
+                        SELECT 1 FROM dependencies d
+                        JOIN files fa ON fa.id = d.from_file_id
+                        JOIN files fb ON fb.id = d.to_file_id
+                        WHERE (fa.path = c.path AND fb.path = c.coupled_path)
+                           OR (fa.path = c.coupled_path AND fb.path = c.path)
+                    )
+             FROM cochange c
+             JOIN files f ON f.path = c.coupled_path
+             WHERE c.path = ?1 AND c.confidence >= ?2
+             ORDER BY c.confidence DESC, c.support DESC, c.coupled_path",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![path, min_confidence], |row| {
+            Ok(CoupledFile {
+                path: row.get(0)?,
+                support: row.get(1)?,
+                confidence: row.get(2)?,
+                imports: row.get(3)?,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Coupled paths of `path` as (path, support, confidence)
+    fn coupled(db: &Database, path: &str) -> Vec<(String, i64, f64)> {
+        db.get_coupled_files(path, 0.0)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.path, c.support, c.confidence))
+            .collect()
+    }
+
+    #[test]
+    fn support_and_confidence_from_shared_commits() {
+        let db = Database::open_in_memory().unwrap();
+        for path in ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"] {
+            db.upsert_file(path, "rust", 0, "", 1).unwrap();
+        }
+        let commits: [&[&str]; 6] = [
+            &["a.rs", "b.rs"],
+            &["a.rs", "b.rs"],
+            &["a.rs", "c.rs"],
+            // Sweeping change, over the max_commit_files cutoff of 3
+            &["a.rs", "b.rs", "c.rs", "d.rs", "e.rs"],
+            // Single-file commits couple nothing, but dilute confidence
+            &["d.rs"],
+            &["a.rs"],
+        ];
+        for (n, files) in commits.iter().enumerate() {
+            let changes: Vec<CommitFileChange> = files
+                .iter()
+                .map(|path| CommitFileChange {
+                    path: path.to_string(),
+                    from_path: None,
+                    status: "modified".to_string(),
+                    additions: 1,
+                    deletions: 0,
+                })
+                .collect();
+            db.insert_commit(&format!("c{n}"), "ann", None, "edit", &changes)
+                .unwrap();
+        }
+
+        // a.rs is in 4 counted commits, b.rs in 2 and c.rs in 1; a↔c has
+        // support 1, below min_support
+        assert_eq!(db.refresh_cochange(3, 2).unwrap(), 2);
+        assert_eq!(coupled(&db, "a.rs"), [("b.rs".into(), 2, 0.5)]);
+        assert_eq!(coupled(&db, "b.rs"), [("a.rs".into(), 2, 1.0)]);
+        assert!(coupled(&db, "c.rs").is_empty());
+
+        assert_eq!(db.refresh_cochange(3, 1).unwrap(), 4);
+        assert_eq!(
+            coupled(&db, "a.rs"),
+            [("b.rs".into(), 2, 0.5), ("c.rs".into(), 1, 0.25)]
+        );
+        assert_eq!(coupled(&db, "c.rs"), [("a.rs".into(), 1, 1.0)]);
+
+        // Raising the cutoff counts the sweeping commit too
+        db.refresh_cochange(5, 2).unwrap();
+        assert_eq!(
+            coupled(&db, "a.rs"),
+            [("b.rs".into(), 3, 0.6), ("c.rs".into(), 2, 0.4)]
+        );
+        assert_eq!(coupled(&db, "d.rs"), []);
+    }
+}
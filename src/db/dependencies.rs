@@ -1,7 +1,13 @@
 use super::*;
-use std::collections::HashSet;
+use crate::analyzer::modules::ProjectModules;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
+/// Dependency rows (`d` joined to its importer `f`) whose target a tsconfig
+/// alias or workspace package may decide
+const BARE_JS_IMPORT: &str = "f.language IN ('typescript', 'tsx', 'javascript')
+    AND d.to_path NOT LIKE '.%' AND d.to_path NOT LIKE '/%'";
+
 impl Database {
     // =================================================================
     // Dependency operations
@@ -9,10 +15,9 @@ impl Database {
 
     /// Clear dependencies for a file
     pub fn clear_dependencies(&self, file_id: i64) -> Result<()> {
-        self.conn.execute(
-            "DELETE FROM dependencies WHERE from_file_id = ?1",
-            [file_id],
-        )?;
+        self.conn
+            .prepare_cached("DELETE FROM dependencies WHERE from_file_id = ?1")?
+            .execute([file_id])?;
         Ok(())
     }
 
@@ -23,24 +28,91 @@ impl Database {
         to_path: &str,
         kind: &str,
         imported_names: &str,
+        line: i64,
     ) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT INTO dependencies (from_file_id, to_path, kind, imported_names, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(rusqlite::params![
+                from_file_id,
+                to_path,
+                kind,
+                imported_names,
+                line
+            ])?;
+        Ok(())
+    }
+
+    /// Resolve dependency to_file_id based on path matching, trying the
+    /// project's import aliases and workspace packages first
+    pub fn resolve_dependencies(&self, modules: &ProjectModules) -> Result<()> {
+        self.resolve_dependency_rows(modules, "", &[])?;
+        self.link_go_packages(modules, None)
+    }
+
+    /// Resolve only the unresolved dependencies declared by `file_ids`, plus any
+    /// whose import path mentions one of `stems` (newly added files). Go
+    /// imports are relinked for `file_ids` and for the packages in
+    /// `package_dirs` (directories that gained or lost files).
+    pub fn resolve_dependencies_for(
+        &self,
+        modules: &ProjectModules,
+        file_ids: &[i64],
+        stems: &[String],
+        package_dirs: &[String],
+    ) -> Result<()> {
+        if !file_ids.is_empty() || !stems.is_empty() {
+            let mut params: Vec<&dyn rusqlite::types::ToSql> = Vec::new();
+            let mut conditions = Vec::new();
+            for id in file_ids {
+                params.push(id);
+                conditions.push(format!("d.from_file_id = ?{}", params.len()));
+            }
+            for stem in stems {
+                params.push(stem);
+                conditions.push(format!("instr(d.to_path, ?{}) > 0", params.len()));
+            }
+            self.resolve_dependency_rows(
+                modules,
+                &format!("AND ({})", conditions.join(" OR ")),
+                &params,
+            )?;
+        }
+        self.link_go_packages(modules, Some((file_ids, package_dirs)))
+    }
+
+    /// Re-resolve every bare JS/TS import specifier (`@/lib/x`, `@acme/ui`)
+    /// after a tsconfig/jsconfig or package.json changed. Resolution only
+    /// fills in unresolved rows, so links made through the old aliases and
+    /// packages are reset first.
+    pub fn reresolve_module_imports(&self, modules: &ProjectModules) -> Result<()> {
         self.conn.execute(
-            "INSERT INTO dependencies (from_file_id, to_path, kind, imported_names)
-             VALUES (?1, ?2, ?3, ?4)",
-            rusqlite::params![from_file_id, to_path, kind, imported_names],
+            &format!(
+                "UPDATE dependencies SET to_file_id = NULL
+                 WHERE id IN (SELECT d.id FROM dependencies d
+                              JOIN files f ON f.id = d.from_file_id
+                              WHERE {BARE_JS_IMPORT})"
+            ),
+            [],
         )?;
-        Ok(())
+        self.resolve_dependency_rows(modules, &format!("AND {BARE_JS_IMPORT}"), &[])
     }
 
-    /// Resolve dependency to_file_id based on path matching
-    pub fn resolve_dependencies(&self) -> Result<()> {
-        let mut stmt = self.conn.prepare(
+    fn resolve_dependency_rows(
+        &self,
+        modules: &ProjectModules,
+        filter: &str,
+        params: &[&dyn rusqlite::types::ToSql],
+    ) -> Result<()> {
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT d.id, d.to_path, f.path
              FROM dependencies d
              JOIN files f ON f.id = d.from_file_id
-             WHERE d.to_file_id IS NULL",
-        )?;
-        let rows = stmt.query_map([], |row| {
+             WHERE d.to_file_id IS NULL {filter}"
+        ))?;
+        let rows = stmt.query_map(params, |row| {
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, String>(1)?,
@@ -51,11 +123,121 @@ impl Database {
         drop(stmt);
 
         for (dep_id, to_path, from_path) in unresolved {
-            if let Some(target_id) = self.resolve_dependency_target(&from_path, &to_path)? {
-                self.conn.execute(
-                    "UPDATE dependencies SET to_file_id = ?1 WHERE id = ?2",
-                    rusqlite::params![target_id, dep_id],
-                )?;
+            if let Some(target_id) =
+                self.resolve_dependency_target(modules, &from_path, &to_path)?
+            {
+                self.conn
+                    .prepare_cached("UPDATE dependencies SET to_file_id = ?1 WHERE id = ?2")?
+                    .execute(rusqlite::params![target_id, dep_id])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Link each Go import of a project package to every non-test file in the
+    /// package directory, one dependency row per file.
+    ///
+    /// `changed` limits this to the imports of some files and to the imports
+    /// of packages in some directories, so that files added to or removed
+    /// from a package are reconciled even when the importing file did not
+    /// change. `None` relinks every import.
+    fn link_go_packages(
+        &self,
+        modules: &ProjectModules,
+        changed: Option<(&[i64], &[String])>,
+    ) -> Result<()> {
+        let import_paths: Vec<String>;
+        let mut params: Vec<&dyn rusqlite::types::ToSql> = Vec::new();
+        let mut filter = String::new();
+        if let Some((file_ids, dirs)) = changed {
+            import_paths = dirs
+                .iter()
+                .flat_map(|dir| modules.go_import_paths(dir))
+                .collect();
+            if file_ids.is_empty() && import_paths.is_empty() {
+                return Ok(());
+            }
+            let mut conditions = Vec::new();
+            for id in file_ids {
+                params.push(id);
+                conditions.push(format!("d.from_file_id = ?{}", params.len()));
+            }
+            for import in &import_paths {
+                params.push(import);
+                conditions.push(format!("d.to_path = ?{}", params.len()));
+            }
+            filter = format!("AND ({})", conditions.join(" OR "));
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path FROM files
+             WHERE path LIKE '%.go' AND path NOT LIKE '%\\_test.go' ESCAPE '\\'",
+        )?;
+        let mut packages: HashMap<String, Vec<i64>> = HashMap::new();
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (id, path) = row?;
+            let dir = path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+            packages.entry(dir.to_string()).or_default().push(id);
+        }
+        drop(stmt);
+
+        // Rows of one import statement as (row id, linked file), keyed by
+        // (importer, line, import path)
+        type Links = Vec<(i64, Option<i64>)>;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT d.id, d.from_file_id, d.line, d.to_path, d.to_file_id FROM dependencies d
+             JOIN files f ON f.id = d.from_file_id
+             WHERE f.path LIKE '%.go' {filter}
+             ORDER BY d.id"
+        ))?;
+        let mut imports: BTreeMap<(i64, i64, String), Links> = BTreeMap::new();
+        for row in stmt.query_map(params.as_slice(), |row| {
+            Ok((
+                (row.get(1)?, row.get(2)?, row.get(3)?),
+                (row.get(0)?, row.get(4)?),
+            ))
+        })? {
+            let (key, link) = row?;
+            imports.entry(key).or_default().push(link);
+        }
+        drop(stmt);
+
+        for ((from_file_id, _, to_path), links) in imports {
+            let Some(dir) = modules.go_package_dir(&to_path) else {
+                continue;
+            };
+            let files = packages.get(&dir).map(Vec::as_slice).unwrap_or_default();
+            let linked: HashSet<i64> = links.iter().filter_map(|(_, to)| *to).collect();
+            let template = links[0].0;
+            for &file_id in files {
+                if file_id != from_file_id && !linked.contains(&file_id) {
+                    self.conn
+                        .prepare_cached(
+                            "INSERT INTO dependencies
+                                 (from_file_id, to_path, kind, imported_names, line, to_file_id)
+                             SELECT from_file_id, to_path, kind, imported_names, line, ?2
+                             FROM dependencies WHERE id = ?1",
+                        )?
+                        .execute([template, file_id])?;
+                }
+            }
+
+            // Links to removed files were nulled; keep one unresolved row only
+            // while the package has no files at all
+            let keep_unresolved = files.is_empty() && linked.is_empty();
+            let mut stale = links
+                .iter()
+                .filter(|(_, to)| to.is_none())
+                .map(|(id, _)| *id);
+            if keep_unresolved {
+                stale.next();
+            }
+            for id in stale {
+                self.conn
+                    .prepare_cached("DELETE FROM dependencies WHERE id = ?1")?
+                    .execute([id])?;
             }
         }
         Ok(())
@@ -81,6 +263,42 @@ impl Database {
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
 
+    /// Every import that resolved to an indexed file
+    pub fn get_resolved_imports(&self) -> Result<Vec<ResolvedImport>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT fa.path, d.line, d.to_path, d.kind, d.imported_names, fb.path FROM dependencies d
+             JOIN files fa ON fa.id = d.from_file_id
+             JOIN files fb ON fb.id = d.to_file_id
+             ORDER BY fa.path, d.line",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let names: String = row.get(4)?;
+            Ok(ResolvedImport {
+                path: row.get(0)?,
+                line: row.get(1)?,
+                import: row.get(2)?,
+                kind: row.get(3)?,
+                names: serde_json::from_str(&names).unwrap_or_default(),
+                target: row.get(5)?,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Resolved file-to-file import edges, without `mod` declarations (a
+    /// parent declaring its children is containment, not an import)
+    pub fn get_import_edges(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT fa.path, fb.path FROM dependencies d
+             JOIN files fa ON fa.id = d.from_file_id
+             JOIN files fb ON fb.id = d.to_file_id
+             WHERE d.kind <> 'mod' AND d.from_file_id <> d.to_file_id
+             ORDER BY fa.path, fb.path",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
     /// Count total dependencies
     pub fn count_dependencies(&self) -> Result<i64> {
         Ok(self
@@ -88,14 +306,123 @@ impl Database {
             .query_row("SELECT COUNT(*) FROM dependencies", [], |row| row.get(0))?)
     }
 
-    fn resolve_dependency_target(&self, from_file: &str, raw_target: &str) -> Result<Option<i64>> {
-        for candidate in dependency_path_candidates(from_file, raw_target) {
+    fn resolve_dependency_target(
+        &self,
+        modules: &ProjectModules,
+        from_file: &str,
+        raw_target: &str,
+    ) -> Result<Option<i64>> {
+        if from_file.ends_with(".swift") {
+            return self.resolve_swift_module(raw_target);
+        }
+        if from_file.ends_with(".kt") || from_file.ends_with(".kts") {
+            return self.resolve_kotlin_import(raw_target);
+        }
+        // Project packages are linked file by file in `link_go_packages`
+        if from_file.ends_with(".go") && modules.go_package_dir(raw_target).is_some() {
+            return Ok(None);
+        }
+        let aliased = alias_candidates(modules.candidates(from_file, raw_target));
+        for candidate in aliased
+            .into_iter()
+            .chain(dependency_path_candidates(from_file, raw_target))
+        {
             if let Some(file_id) = self.get_file_id(&candidate)? {
                 return Ok(Some(file_id));
             }
         }
         Ok(None)
     }
+
+    /// Swift imports name a module, i.e. a directory of sources: SwiftPM's
+    /// `Sources/<Module>/` (possibly in a nested package) or an Xcode target
+    /// folder. Resolve to the file named after the module, else the first one.
+    fn resolve_swift_module(&self, module: &str) -> Result<Option<i64>> {
+        if module.is_empty() || module.contains(['/', '.']) {
+            return Ok(None);
+        }
+        let id = self
+            .conn
+            .prepare_cached(
+                "SELECT id FROM files
+                 WHERE path LIKE '%.swift'
+                   AND (substr(path, 1, length(?1) + 9) = 'Sources/' || ?1 || '/'
+                        OR instr(path, '/Sources/' || ?1 || '/') > 0
+                        OR substr(path, 1, length(?1) + 1) = ?1 || '/')
+                 ORDER BY substr(path, -length(?1) - 7) = '/' || ?1 || '.swift' DESC,
+                          length(path), path
+                 LIMIT 1",
+            )?
+            .query_row([module], |row| row.get(0))
+            .optional()?;
+        Ok(id)
+    }
+
+    /// Kotlin imports name a declaration by package: `com.acme.data.Repo` is
+    /// `Repo` declared in a file directly under `…/com/acme/data/`, usually
+    /// `Repo.kt`. A wildcard import resolves to the first file of the package,
+    /// and a nested class (`Outer.Inner`) to the file declaring `Outer`.
+    fn resolve_kotlin_import(&self, import: &str) -> Result<Option<i64>> {
+        if let Some(package) = import.strip_suffix(".*") {
+            return self.resolve_kotlin_declaration(&package.replace('.', "/"), None);
+        }
+        let mut parts: Vec<&str> = import.split('.').collect();
+        while parts.len() > 1 {
+            let name = parts.pop().unwrap_or_default();
+            if let Some(id) = self.resolve_kotlin_declaration(&parts.join("/"), Some(name))? {
+                return Ok(Some(id));
+            }
+            // Only a class can enclose another declaration
+            if !parts
+                .last()
+                .is_some_and(|p| p.starts_with(|c: char| c.is_ascii_uppercase()))
+            {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// A `.kt` file directly in `package_dir` (at the root or below a source
+    /// root) that is named after or declares `name`; any file there if None
+    fn resolve_kotlin_declaration(
+        &self,
+        package_dir: &str,
+        name: Option<&str>,
+    ) -> Result<Option<i64>> {
+        let id = self
+            .conn
+            .prepare_cached(
+                "SELECT f.id FROM files f
+                 WHERE f.path LIKE '%.kt'
+                   AND (substr(f.path, 1, length(?1) + 1) = ?1 || '/'
+                        OR instr(f.path, '/' || ?1 || '/') > 0)
+                   AND instr(substr(f.path, instr(f.path, ?1 || '/') + length(?1) + 1), '/') = 0
+                   AND (?2 IS NULL
+                        OR substr(f.path, -length(?2) - 4) = '/' || ?2 || '.kt'
+                        OR EXISTS (SELECT 1 FROM symbols s
+                                   WHERE s.file_id = f.id AND s.name = ?2
+                                     AND s.parent_symbol_id IS NULL))
+                 ORDER BY substr(f.path, -length(?2) - 4) = '/' || ?2 || '.kt' DESC,
+                          length(f.path), f.path
+                 LIMIT 1",
+            )?
+            .query_row(rusqlite::params![package_dir, name], |row| row.get(0))
+            .optional()?;
+        Ok(id)
+    }
+}
+
+/// Files an aliased import may name: each base as written, then with module
+/// extensions and `index` files
+fn alias_candidates(bases: Vec<String>) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut seen = HashSet::new();
+    for base in bases {
+        add_candidate(&mut candidates, &mut seen, base.clone());
+        add_module_candidates(&mut candidates, &mut seen, base);
+    }
+    candidates
 }
 
 fn dependency_path_candidates(from_file: &str, raw_target: &str) -> Vec<String> {
@@ -149,12 +476,27 @@ fn add_candidate(candidates: &mut Vec<String>, seen: &mut HashSet<String>, path:
     if path.is_empty() {
         return;
     }
-    let normalized = path.replace('\\', "/");
+    let normalized = normalize_relative(&path.replace('\\', "/"));
     if seen.insert(normalized.clone()) {
         candidates.push(normalized);
     }
 }
 
+/// Collapse `.` and `..` segments (`src/./b` → `src/b`, `src/x/../b` → `src/b`)
+fn normalize_relative(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if parts.last().is_some_and(|p| *p != "..") => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
 fn add_module_candidates(candidates: &mut Vec<String>, seen: &mut HashSet<String>, base: String) {
     if base.is_empty() {
         return;
@@ -229,6 +571,9 @@ fn normalize_import_target(raw_target: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::{dependency_path_candidates, normalize_import_target};
+    use crate::analyzer::modules::ProjectModules;
+    use crate::db::models::SymbolKind;
+    use crate::db::Database;
 
     #[test]
     fn normalize_rust_use_targets() {
@@ -256,4 +601,148 @@ mod tests {
             .iter()
             .any(|c| c == "src/analyzer/parser/mod.rs"));
     }
+
+    #[test]
+    fn resolve_candidates_normalize_relative_paths() {
+        let candidates = dependency_path_candidates("src/app/a.ts", "./b");
+        assert!(candidates.iter().any(|c| c == "src/app/b.ts"));
+        let parent = dependency_path_candidates("src/app/a.ts", "../lib/util");
+        assert!(parent.iter().any(|c| c == "src/lib/util.ts"));
+    }
+
+    #[test]
+    fn resolve_kotlin_imports_by_package() {
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "kotlin", 0, "", 0).unwrap();
+        let app = file("app/src/main/kotlin/com/acme/app/Main.kt");
+        let repo = file("data/src/main/kotlin/com/acme/data/Repo.kt");
+        let util = file("data/src/main/kotlin/com/acme/util/Strings.kt");
+        let outer = file("data/src/main/kotlin/com/acme/data/Models.kt");
+        db.insert_symbol(util, "shout", &SymbolKind::Function, 1, 1, "", None)
+            .unwrap();
+        db.insert_symbol(outer, "Outer", &SymbolKind::Class, 1, 3, "", None)
+            .unwrap();
+
+        for (line, import) in [
+            "com.acme.data.Repo",
+            "com.acme.util.shout",
+            "com.acme.util.*",
+            "com.acme.data.Outer.Inner",
+            "kotlinx.coroutines.launch",
+        ]
+        .iter()
+        .enumerate()
+        {
+            db.insert_dependency(app, import, "import", "[]", line as i64)
+                .unwrap();
+        }
+        db.resolve_dependencies(&ProjectModules::default()).unwrap();
+
+        let targets: Vec<_> = db
+            .get_dependencies_of(app)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(
+            targets,
+            vec![Some(repo), Some(util), Some(util), Some(outer), None]
+        );
+    }
+
+    #[test]
+    fn changed_alias_re_resolves_linked_imports() {
+        let root = std::env::temp_dir().join(format!("ctx-alias-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let load_alias = |target: &str| {
+            std::fs::write(
+                root.join("tsconfig.json"),
+                format!(r#"{{ "compilerOptions": {{ "paths": {{ "@/*": ["{target}/*"] }} }} }}"#),
+            )
+            .unwrap();
+            ProjectModules::load(&root, &[root.join("tsconfig.json")])
+        };
+
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "typescript", 0, "", 0).unwrap();
+        let app = file("src/app.ts");
+        let old_util = file("src/util.ts");
+        let new_util = file("lib/util.ts");
+        let sibling = file("src/sibling.ts");
+        db.insert_dependency(app, "@/util", "import", "[]", 1)
+            .unwrap();
+        db.insert_dependency(app, "./sibling", "import", "[]", 2)
+            .unwrap();
+        db.resolve_dependencies(&load_alias("src")).unwrap();
+        let targets = |id| -> Vec<Option<i64>> {
+            db.get_dependencies_of(id)
+                .unwrap()
+                .into_iter()
+                .map(|(to, _)| to)
+                .collect()
+        };
+        assert_eq!(targets(app), vec![Some(old_util), Some(sibling)]);
+
+        // A plain pass keeps the stale link; the manifest pass replaces it
+        let modules = load_alias("lib");
+        db.resolve_dependencies(&modules).unwrap();
+        assert_eq!(targets(app), vec![Some(old_util), Some(sibling)]);
+        db.reresolve_module_imports(&modules).unwrap();
+        assert_eq!(targets(app), vec![Some(new_util), Some(sibling)]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn go_package_links_follow_package_files() {
+        let root = std::env::temp_dir().join(format!("ctx-gopkg-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("go.mod"), "module example.com/app\n").unwrap();
+        let modules = ProjectModules::load(&root, &[root.join("go.mod")]);
+
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "go", 0, "", 0).unwrap();
+        let main = file("main.go");
+        let api = file("api/api.go");
+        let store_a = file("store/a.go");
+        for importer in [main, api] {
+            db.insert_dependency(importer, "example.com/app/store", "import", "[]", 3)
+                .unwrap();
+        }
+        db.resolve_dependencies(&modules).unwrap();
+        let targets = |id| -> Vec<Option<i64>> {
+            let mut targets: Vec<Option<i64>> = db
+                .get_dependencies_of(id)
+                .unwrap()
+                .into_iter()
+                .map(|(to, _)| to)
+                .collect();
+            targets.sort();
+            targets
+        };
+        assert_eq!(targets(main), vec![Some(store_a)]);
+
+        // A new package file is linked from importers that did not change
+        let store_b = file("store/b.go");
+        file("store/b_test.go");
+        let dirs = ["store".to_string()];
+        db.resolve_dependencies_for(&modules, &[store_b], &["b".to_string()], &dirs)
+            .unwrap();
+        assert_eq!(targets(main), vec![Some(store_a), Some(store_b)]);
+        assert_eq!(targets(api), vec![Some(store_a), Some(store_b)]);
+
+        // A deleted one is unlinked, down to one unresolved row for an
+        // empty package
+        db.remove_file(store_a).unwrap();
+        db.resolve_dependencies_for(&modules, &[], &[], &dirs)
+            .unwrap();
+        assert_eq!(targets(main), vec![Some(store_b)]);
+        db.remove_file(store_b).unwrap();
+        db.resolve_dependencies_for(&modules, &[], &[], &dirs)
+            .unwrap();
+        assert_eq!(targets(main), vec![None]);
+        assert_eq!(targets(api), vec![None]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 }
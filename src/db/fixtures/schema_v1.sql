@@ -0,0 +1,93 @@
+-- schema_version 1: initial schema
+
+CREATE TABLE files (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    path            TEXT NOT NULL UNIQUE,
+    language        TEXT NOT NULL DEFAULT 'unknown',
+    size_bytes      INTEGER NOT NULL DEFAULT 0,
+    hash            TEXT NOT NULL DEFAULT '',
+    line_count      INTEGER NOT NULL DEFAULT 0,
+    last_analyzed   DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE symbols (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_id         INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    kind            TEXT NOT NULL,
+    start_line      INTEGER NOT NULL,
+    end_line        INTEGER NOT NULL,
+    signature       TEXT NOT NULL DEFAULT '',
+    parent_symbol_id INTEGER REFERENCES symbols(id) ON DELETE SET NULL
+);
+
+CREATE TABLE dependencies (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    from_file_id    INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    to_path         TEXT NOT NULL,
+    to_file_id      INTEGER REFERENCES files(id) ON DELETE SET NULL,
+    kind            TEXT NOT NULL DEFAULT 'import',
+    imported_names  TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE TABLE decisions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    description     TEXT NOT NULL,
+    source          TEXT NOT NULL DEFAULT 'manual',
+    commit_hash     TEXT,
+    related_files   TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE TABLE meta (
+    key             TEXT PRIMARY KEY,
+    value           TEXT NOT NULL
+);
+
+CREATE TABLE knowledge (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    content         TEXT NOT NULL,
+    source          TEXT NOT NULL DEFAULT 'manual',
+    related_file    TEXT,
+    timestamp       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE file_stats (
+    file_id         INTEGER PRIMARY KEY REFERENCES files(id) ON DELETE CASCADE,
+    commit_count    INTEGER NOT NULL DEFAULT 0,
+    last_modified   DATETIME,
+    churn_score     REAL NOT NULL DEFAULT 0.0,
+    contributors    INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_symbols_file_id ON symbols(file_id);
+CREATE INDEX idx_symbols_name ON symbols(name);
+CREATE INDEX idx_symbols_kind ON symbols(kind);
+CREATE INDEX idx_deps_from ON dependencies(from_file_id);
+CREATE INDEX idx_deps_to ON dependencies(to_file_id);
+CREATE INDEX idx_knowledge_file ON knowledge(related_file);
+
+CREATE UNIQUE INDEX idx_decisions_commit_hash_unique
+    ON decisions(commit_hash)
+    WHERE source = 'commit' AND commit_hash IS NOT NULL;
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    name,
+    path,
+    kind,
+    signature,
+    tokenize='porter unicode61'
+);
+
+INSERT INTO meta (key, value) VALUES ('project_root', '/tmp/fixture-project');
+INSERT INTO meta (key, value) VALUES ('schema_version', '1');
+
+INSERT INTO files (id, path, language, size_bytes, hash, line_count)
+    VALUES (1, 'src/main.rs', 'rust', 42, 'abc123', 3);
+INSERT INTO symbols (id, file_id, name, kind, start_line, end_line, signature)
+    VALUES (1, 1, 'main', 'function', 1, 3, 'fn main()');
+INSERT INTO knowledge (content, source, related_file)
+    VALUES ('Entry point lives in main.rs', 'manual', 'src/main.rs');
+-- Search rows were not keyed by symbol id before schema_version 3
+INSERT INTO search_index (rowid, name, path, kind, signature)
+    VALUES (17, 'main', 'src/main.rs', 'function', 'fn main()');
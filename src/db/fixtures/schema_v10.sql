@@ -0,0 +1,153 @@
+-- schema_version 10: import line numbers
+
+CREATE TABLE files (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    path            TEXT NOT NULL UNIQUE,
+    language        TEXT NOT NULL DEFAULT 'unknown',
+    size_bytes      INTEGER NOT NULL DEFAULT 0,
+    hash            TEXT NOT NULL DEFAULT '',
+    line_count      INTEGER NOT NULL DEFAULT 0,
+    last_analyzed   DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE symbols (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_id         INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    kind            TEXT NOT NULL,
+    start_line      INTEGER NOT NULL,
+    end_line        INTEGER NOT NULL,
+    signature       TEXT NOT NULL DEFAULT '',
+    parent_symbol_id INTEGER REFERENCES symbols(id) ON DELETE SET NULL,
+    complexity      INTEGER,
+    max_nesting     INTEGER,
+    param_count     INTEGER,
+    statement_count INTEGER
+);
+
+CREATE TABLE dependencies (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    from_file_id    INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    to_path         TEXT NOT NULL,
+    to_file_id      INTEGER REFERENCES files(id) ON DELETE SET NULL,
+    kind            TEXT NOT NULL DEFAULT 'import',
+    imported_names  TEXT NOT NULL DEFAULT '[]',
+    line            INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE "references" (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_id         INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    from_symbol_id  INTEGER REFERENCES symbols(id) ON DELETE CASCADE,
+    to_name         TEXT NOT NULL,
+    to_symbol_id    INTEGER REFERENCES symbols(id) ON DELETE SET NULL,
+    kind            TEXT NOT NULL DEFAULT 'call',
+    line            INTEGER NOT NULL
+);
+
+CREATE TABLE decisions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    description     TEXT NOT NULL,
+    source          TEXT NOT NULL DEFAULT 'manual',
+    commit_hash     TEXT,
+    related_files   TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE TABLE meta (
+    key             TEXT PRIMARY KEY,
+    value           TEXT NOT NULL
+);
+
+CREATE TABLE knowledge (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    content         TEXT NOT NULL,
+    source          TEXT NOT NULL DEFAULT 'manual',
+    related_file    TEXT,
+    timestamp       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE file_stats (
+    file_id         INTEGER PRIMARY KEY REFERENCES files(id) ON DELETE CASCADE,
+    commit_count    INTEGER NOT NULL DEFAULT 0,
+    last_modified   DATETIME,
+    churn_score     REAL NOT NULL DEFAULT 0.0,
+    contributors    INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE git_commits (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    hash            TEXT NOT NULL UNIQUE,
+    author          TEXT NOT NULL,
+    timestamp       DATETIME,
+    summary         TEXT NOT NULL DEFAULT ''
+);
+
+CREATE TABLE commit_files (
+    commit_id       INTEGER NOT NULL REFERENCES git_commits(id) ON DELETE CASCADE,
+    path            TEXT NOT NULL,
+    original_path   TEXT NOT NULL DEFAULT '',
+    from_path       TEXT,
+    status          TEXT NOT NULL DEFAULT 'modified',
+    additions       INTEGER NOT NULL DEFAULT 0,
+    deletions       INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE cochange (
+    path            TEXT NOT NULL,
+    coupled_path    TEXT NOT NULL,
+    support         INTEGER NOT NULL,
+    confidence      REAL NOT NULL,
+    PRIMARY KEY (path, coupled_path)
+);
+
+CREATE INDEX idx_symbols_file_id ON symbols(file_id);
+CREATE INDEX idx_symbols_name ON symbols(name);
+CREATE INDEX idx_symbols_kind ON symbols(kind);
+CREATE INDEX idx_deps_from ON dependencies(from_file_id);
+CREATE INDEX idx_deps_to ON dependencies(to_file_id);
+CREATE INDEX idx_refs_file ON "references"(file_id);
+CREATE INDEX idx_refs_from ON "references"(from_symbol_id);
+CREATE INDEX idx_refs_to ON "references"(to_symbol_id);
+CREATE INDEX idx_refs_to_name ON "references"(to_name);
+CREATE INDEX idx_knowledge_file ON knowledge(related_file);
+CREATE INDEX idx_commit_files_commit ON commit_files(commit_id);
+CREATE INDEX idx_commit_files_path ON commit_files(path);
+CREATE INDEX idx_commit_files_original ON commit_files(original_path);
+
+CREATE UNIQUE INDEX idx_decisions_commit_hash_unique
+    ON decisions(commit_hash)
+    WHERE source = 'commit' AND commit_hash IS NOT NULL;
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    name,
+    path,
+    kind,
+    signature,
+    tokenize='porter unicode61'
+);
+
+INSERT INTO meta (key, value) VALUES ('project_root', '/tmp/fixture-project');
+INSERT INTO meta (key, value) VALUES ('schema_version', '10');
+INSERT INTO meta (key, value) VALUES ('git_head', '0123456789abcdef0123456789abcdef01234567');
+
+INSERT INTO files (id, path, language, size_bytes, hash, line_count)
+    VALUES (1, 'src/main.rs', 'rust', 42, 'abc123', 3);
+INSERT INTO symbols (id, file_id, name, kind, start_line, end_line, signature, complexity, max_nesting, param_count, statement_count)
+    VALUES (1, 1, 'main', 'function', 1, 3, 'fn main()', 1, 0, 0, 1);
+INSERT INTO dependencies (from_file_id, to_path, to_file_id, kind, imported_names, line)
+    VALUES (1, 'crate::config', NULL, 'use', '[]', 1);
+INSERT INTO knowledge (content, source, related_file)
+    VALUES ('Entry point lives in main.rs', 'manual', 'src/main.rs');
+INSERT INTO "references" (file_id, from_symbol_id, to_name, kind, line)
+    VALUES (1, 1, 'println', 'call', 2);
+INSERT INTO search_index (rowid, name, path, kind, signature)
+    VALUES (1, 'main', 'src/main.rs', 'function', 'fn main()');
+INSERT INTO git_commits (id, hash, author, timestamp, summary)
+    VALUES (1, '0123456789abcdef0123456789abcdef01234567', 'alice', '2024-01-02 03:04:05', 'feat: init');
+INSERT INTO commit_files (commit_id, path, original_path, status, additions, deletions)
+    VALUES (1, 'src/main.rs', 'src/main.rs', 'added', 3, 0);
+INSERT INTO cochange (path, coupled_path, support, confidence)
+    VALUES ('src/main.rs', 'src/lib.rs', 3, 0.75);
+INSERT INTO file_stats (file_id, commit_count, last_modified, churn_score, contributors)
+    VALUES (1, 5, '2024-01-02 03:04:05', 1.0, 2);
@@ -1,21 +1,29 @@
+mod cochange;
 mod decisions;
 mod dependencies;
 mod knowledge;
 pub mod models;
+mod references;
 pub mod schema;
 mod search;
 mod stats;
 
 use anyhow::{bail, Context, Result};
-use rusqlite::{Connection, OptionalExtension};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior};
 use std::path::{Path, PathBuf};
 
 use self::models::*;
+use crate::config::Config;
+
+/// Meta key holding the saved `--include`/`--exclude` globs
+const SCAN_GLOBS_KEY: &str = "scan_globs";
 
 /// Main database handle
 pub struct Database {
     conn: Connection,
     pub ctx_dir: PathBuf,
+    /// Project configuration, loaded once when the database is opened
+    pub config: Config,
 }
 
 impl Database {
@@ -32,13 +40,36 @@ impl Database {
             PRAGMA journal_mode=WAL;
             PRAGMA synchronous=NORMAL;
             PRAGMA foreign_keys=ON;
+            PRAGMA busy_timeout=5000;
         ",
         )?;
+        // Scans run the same handful of statements for every file
+        conn.set_prepared_statement_cache_capacity(64);
 
         schema::run_migrations(&conn)?;
         Self::bind_project_root(&conn, project_root)?;
+        let config = Config::load(project_root)?;
+
+        let mut db = Self {
+            conn,
+            ctx_dir,
+            config,
+        };
+        let (include, exclude) = db.saved_scan_globs()?;
+        db.config.scan.add_globs(&include, &exclude);
+        Ok(db)
+    }
 
-        Ok(Self { conn, ctx_dir })
+    /// Scratch database that is never written to disk, with the default config
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+        schema::run_migrations(&conn)?;
+        Ok(Self {
+            conn,
+            ctx_dir: std::env::temp_dir(),
+            config: Config::default(),
+        })
     }
 
     /// Check if the database exists for the project
@@ -48,6 +79,22 @@ impl Database {
             .unwrap_or(false)
     }
 
+    /// Run `f` inside a transaction, committing on success and rolling back on
+    /// error. Nested calls join the outer transaction.
+    ///
+    /// The write lock is taken up front: a deferred transaction that reads
+    /// first cannot upgrade once another process has written (a watcher and
+    /// a foreground scan), and that error is not retried by `busy_timeout`.
+    pub fn transaction<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        if !self.conn.is_autocommit() {
+            return f();
+        }
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let value = f()?;
+        tx.commit()?;
+        Ok(value)
+    }
+
     // =================================================================
     // File operations
     // =================================================================
@@ -61,20 +108,23 @@ impl Database {
         hash: &str,
         line_count: i64,
     ) -> Result<i64> {
-        self.conn.execute(
-            "INSERT INTO files (path, language, size_bytes, hash, line_count, last_analyzed)
-             VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)
-             ON CONFLICT(path) DO UPDATE SET
-                language = ?2, size_bytes = ?3, hash = ?4, line_count = ?5,
-                last_analyzed = CURRENT_TIMESTAMP",
-            rusqlite::params![path, language, size_bytes, hash, line_count],
-        )?;
+        self.conn
+            .prepare_cached(
+                "INSERT INTO files (path, language, size_bytes, hash, line_count, last_analyzed)
+                 VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)
+                 ON CONFLICT(path) DO UPDATE SET
+                    language = ?2, size_bytes = ?3, hash = ?4, line_count = ?5,
+                    last_analyzed = CURRENT_TIMESTAMP",
+            )?
+            .execute(rusqlite::params![
+                path, language, size_bytes, hash, line_count
+            ])?;
         Ok(self.conn.last_insert_rowid())
     }
 
     /// Get file by path
     pub fn get_file_by_path(&self, path: &str) -> Result<Option<TrackedFile>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare_cached(
             "SELECT id, path, language, size_bytes, hash, line_count, last_analyzed FROM files WHERE path = ?1"
         )?;
         let result = stmt.query_row(rusqlite::params![path], |row| {
@@ -97,7 +147,9 @@ impl Database {
 
     /// Get file ID by path
     pub fn get_file_id(&self, path: &str) -> Result<Option<i64>> {
-        let mut stmt = self.conn.prepare("SELECT id FROM files WHERE path = ?1")?;
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT id FROM files WHERE path = ?1")?;
         let result = stmt.query_row(rusqlite::params![path], |row| row.get(0));
         match result {
             Ok(id) => Ok(Some(id)),
@@ -148,6 +200,25 @@ impl Database {
         Ok(count)
     }
 
+    /// Get files at a path or anywhere below it (for deleted files and directories)
+    pub fn get_files_under(&self, path: &str) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path FROM files
+             WHERE path = ?1 OR substr(path, 1, length(?1) + 1) = ?1 || '/'
+             ORDER BY path",
+        )?;
+        let rows = stmt.query_map([path], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Remove a single file and (via cascades) its symbols, dependencies and references
+    pub fn remove_file(&self, file_id: i64) -> Result<()> {
+        self.conn
+            .prepare_cached("DELETE FROM files WHERE id = ?1")?
+            .execute([file_id])?;
+        Ok(())
+    }
+
     // =================================================================
     // Symbol operations
     // =================================================================
@@ -155,7 +226,8 @@ impl Database {
     /// Clear all symbols for a file
     pub fn clear_symbols(&self, file_id: i64) -> Result<()> {
         self.conn
-            .execute("DELETE FROM symbols WHERE file_id = ?1", [file_id])?;
+            .prepare_cached("DELETE FROM symbols WHERE file_id = ?1")?
+            .execute([file_id])?;
         Ok(())
     }
 
@@ -171,17 +243,32 @@ impl Database {
         signature: &str,
         parent_id: Option<i64>,
     ) -> Result<i64> {
-        self.conn.execute(
-            "INSERT INTO symbols (file_id, name, kind, start_line, end_line, signature, parent_symbol_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            rusqlite::params![file_id, name, kind.as_str(), start_line, end_line, signature, parent_id],
-        )?;
+        self.conn.prepare_cached("INSERT INTO symbols (file_id, name, kind, start_line, end_line, signature, parent_symbol_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)")?.execute(rusqlite::params![file_id, name, kind.as_str(), start_line, end_line, signature, parent_id])?;
         Ok(self.conn.last_insert_rowid())
     }
 
+    /// Record the complexity of a function or method
+    pub fn set_symbol_metrics(&self, symbol_id: i64, metrics: &SymbolMetrics) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "UPDATE symbols
+                 SET complexity = ?2, max_nesting = ?3, param_count = ?4, statement_count = ?5
+                 WHERE id = ?1",
+            )?
+            .execute(rusqlite::params![
+                symbol_id,
+                metrics.complexity,
+                metrics.max_nesting,
+                metrics.param_count,
+                metrics.statement_count
+            ])?;
+        Ok(())
+    }
+
     /// Get all symbols for a file
     pub fn get_symbols_for_file(&self, file_id: i64) -> Result<Vec<Symbol>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare_cached(
             "SELECT id, file_id, name, kind, start_line, end_line, signature, parent_symbol_id
              FROM symbols WHERE file_id = ?1 ORDER BY start_line",
         )?;
@@ -217,6 +304,68 @@ impl Database {
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
 
+    /// Read a value from the meta table
+    pub fn get_meta(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Insert or replace a value in the meta table
+    pub fn set_meta(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = ?2",
+            [key, value],
+        )?;
+        Ok(())
+    }
+
+    /// `--include`/`--exclude` globs saved by `save_scan_globs`
+    pub fn saved_scan_globs(&self) -> Result<(Vec<String>, Vec<String>)> {
+        let Some(value) = self.get_meta(SCAN_GLOBS_KEY)? else {
+            return Ok((vec![], vec![]));
+        };
+        let globs: serde_json::Value = serde_json::from_str(&value)?;
+        let list = |key: &str| -> Vec<String> {
+            globs[key]
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|g| g.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Ok((list("include"), list("exclude")))
+    }
+
+    /// Save `--include`/`--exclude` globs with the project, replacing the
+    /// previously saved ones, so later opens (the background watcher, MCP,
+    /// plain `scan`) index the same files. Empty lists forget them.
+    pub fn save_scan_globs(&mut self, include: &[String], exclude: &[String]) -> Result<()> {
+        // Saved globs were appended after the configured ones on open
+        let (old_include, old_exclude) = self.saved_scan_globs()?;
+        let scan = &mut self.config.scan;
+        scan.include
+            .truncate(scan.include.len().saturating_sub(old_include.len()));
+        scan.exclude
+            .truncate(scan.exclude.len().saturating_sub(old_exclude.len()));
+        scan.add_globs(include, exclude);
+
+        if include.is_empty() && exclude.is_empty() {
+            self.conn
+                .execute("DELETE FROM meta WHERE key = ?1", [SCAN_GLOBS_KEY])?;
+            return Ok(());
+        }
+        let globs = serde_json::json!({ "include": include, "exclude": exclude });
+        self.set_meta(SCAN_GLOBS_KEY, &globs.to_string())
+    }
+
     fn bind_project_root(conn: &Connection, project_root: &Path) -> Result<()> {
         let canonical_root = std::fs::canonicalize(project_root)
             .unwrap_or_else(|_| project_root.to_path_buf())
@@ -265,3 +414,78 @@ impl Database {
         Ok((ctx_dir, db_path))
     }
 }
+
+/// Read `complexity, max_nesting, param_count, statement_count` starting at
+/// column `first`; `None` for symbols that were not measured
+fn metrics_from_row(row: &rusqlite::Row, first: usize) -> rusqlite::Result<Option<SymbolMetrics>> {
+    let Some(complexity) = row.get::<_, Option<i64>>(first)? else {
+        return Ok(None);
+    };
+    Ok(Some(SymbolMetrics {
+        complexity,
+        max_nesting: row.get::<_, Option<i64>>(first + 1)?.unwrap_or(0),
+        param_count: row.get::<_, Option<i64>>(first + 2)?.unwrap_or(0),
+        statement_count: row.get::<_, Option<i64>>(first + 3)?.unwrap_or(0),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_scan_globs_replace_earlier_ones() {
+        let mut db = Database::open_in_memory().unwrap();
+        db.config.scan.exclude = vec!["configured/".to_string()];
+
+        db.save_scan_globs(&["src/**".to_string()], &["*.pb.go".to_string()])
+            .unwrap();
+        assert_eq!(db.config.scan.include, ["src/**"]);
+        assert_eq!(db.config.scan.exclude, ["configured/", "*.pb.go"]);
+
+        db.save_scan_globs(&[], &["testdata/".to_string()]).unwrap();
+        assert!(db.config.scan.include.is_empty());
+        assert_eq!(db.config.scan.exclude, ["configured/", "testdata/"]);
+        assert_eq!(
+            db.saved_scan_globs().unwrap(),
+            (vec![], vec!["testdata/".to_string()])
+        );
+
+        db.save_scan_globs(&[], &[]).unwrap();
+        assert_eq!(db.config.scan.exclude, ["configured/"]);
+        assert!(db.get_meta(SCAN_GLOBS_KEY).unwrap().is_none());
+    }
+
+    #[test]
+    fn transactions_lock_out_other_writers() {
+        let dir = std::env::temp_dir().join(format!("ctx-tx-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let open = |busy_timeout: i64| {
+            let conn = Connection::open(dir.join("ctx.db")).unwrap();
+            conn.execute_batch(&format!(
+                "PRAGMA journal_mode=WAL; PRAGMA busy_timeout={busy_timeout};"
+            ))
+            .unwrap();
+            schema::run_migrations(&conn).unwrap();
+            Database {
+                conn,
+                ctx_dir: dir.clone(),
+                config: Config::default(),
+            }
+        };
+        let scan = open(5000);
+        let other = open(0);
+
+        scan.transaction(|| {
+            // Nothing written yet, but another writer must not slip in
+            // between this transaction's reads and writes
+            assert!(other.set_meta("owner", "other").is_err());
+            assert!(scan.get_meta("owner")?.is_none());
+            scan.set_meta("owner", "scan")
+        })
+        .unwrap();
+        assert_eq!(other.get_meta("owner").unwrap().as_deref(), Some("scan"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
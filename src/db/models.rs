@@ -25,6 +25,10 @@ pub enum SymbolKind {
     Constant,
     TypeAlias,
     Module,
+    /// React component (function returning JSX, or a `memo`/`forwardRef` wrapper)
+    Component,
+    /// React hook (`useX` function)
+    Hook,
 }
 
 impl SymbolKind {
@@ -39,6 +43,8 @@ impl SymbolKind {
             Self::Constant => "constant",
             Self::TypeAlias => "type_alias",
             Self::Module => "module",
+            Self::Component => "component",
+            Self::Hook => "hook",
         }
     }
 
@@ -53,13 +59,15 @@ impl SymbolKind {
             "constant" => Self::Constant,
             "type_alias" => Self::TypeAlias,
             "module" => Self::Module,
+            "component" => Self::Component,
+            "hook" => Self::Hook,
             _ => Self::Function,
         }
     }
 
     pub fn icon(&self) -> &'static str {
         match self {
-            Self::Function | Self::Method => "ƒ",
+            Self::Function | Self::Method | Self::Hook => "ƒ",
             Self::Class => "C",
             Self::Struct => "S",
             Self::Interface => "I",
@@ -67,6 +75,7 @@ impl SymbolKind {
             Self::Constant => "K",
             Self::TypeAlias => "T",
             Self::Module => "M",
+            Self::Component => "◇",
         }
     }
 }
@@ -85,6 +94,8 @@ impl FromStr for SymbolKind {
             "constant" => Ok(Self::Constant),
             "type_alias" => Ok(Self::TypeAlias),
             "module" => Ok(Self::Module),
+            "component" => Ok(Self::Component),
+            "hook" => Ok(Self::Hook),
             _ => Err(()),
         }
     }
@@ -103,6 +114,32 @@ pub struct Symbol {
     pub parent_symbol_id: Option<i64>,
 }
 
+/// Complexity of a function or method body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolMetrics {
+    /// Cyclomatic complexity: 1 + branches, loops, cases and `&&`/`||`
+    pub complexity: i64,
+    /// Deepest nesting of control structures
+    pub max_nesting: i64,
+    /// Declared parameters, excluding the receiver
+    pub param_count: i64,
+    /// Statements in the body, including nested blocks
+    pub statement_count: i64,
+}
+
+/// A symbol matched by full-text search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub name: String,
+    pub path: String,
+    pub kind: String,
+    pub signature: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    /// Set for functions and methods
+    pub metrics: Option<SymbolMetrics>,
+}
+
 /// A dependency between two files
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
@@ -114,6 +151,36 @@ pub struct Dependency {
     pub imported_names: String,
 }
 
+/// A call site linking a calling symbol to an invoked name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReference {
+    pub file: String,
+    pub line: i64,
+    pub kind: String,
+    /// Enclosing symbol of the call site (None for top-level code)
+    pub caller: Option<String>,
+    pub callee: String,
+    /// File defining the resolved callee, if it could be resolved
+    pub callee_file: Option<String>,
+}
+
+/// A symbol (or file top level) affected by a change, found via calls or imports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolImpact {
+    #[serde(skip)]
+    pub symbol_id: Option<i64>,
+    /// Affected symbol (None when the use is at file top level or via an import)
+    pub symbol: Option<String>,
+    pub kind: Option<String>,
+    #[serde(skip)]
+    pub file_id: i64,
+    pub path: String,
+    pub line: i64,
+    /// How the impact propagates: "call" or "import"
+    pub via: String,
+    pub depth: usize,
+}
+
 /// A recorded decision
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Decision {
@@ -145,6 +212,154 @@ pub struct FileStats {
     pub contributors: i64,
 }
 
+/// A file touched by a commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitFileChange {
+    pub path: String,
+    /// Source path of a rename or copy
+    pub from_path: Option<String>,
+    /// "added", "modified", "deleted", "renamed", "copied", ...
+    pub status: String,
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+/// Lines changed in a file within a recent time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowChurn {
+    pub days: i64,
+    pub commits: i64,
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+/// An author's part in a file's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorShare {
+    pub author: String,
+    pub commits: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    /// Share of the file's changed lines (of its commits when no lines changed)
+    pub share: f64,
+}
+
+/// A commit in a file's history, following renames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileHistoryEntry {
+    pub hash: String,
+    pub author: String,
+    pub timestamp: Option<String>,
+    pub summary: String,
+    /// The file's name in this commit
+    pub path: String,
+    pub from_path: Option<String>,
+    pub status: String,
+}
+
+/// A file that tends to change in the same commits as another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoupledFile {
+    pub path: String,
+    /// Commits touching both files
+    pub support: i64,
+    /// Share of the other file's commits that also touch this one
+    pub confidence: f64,
+    /// Whether an import edge links the two files
+    pub imports: bool,
+}
+
+/// A complex function in a frequently changed file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub complexity: i64,
+    pub max_nesting: i64,
+    /// Commits touching the containing file
+    pub commit_count: i64,
+    /// complexity × commit_count
+    pub score: i64,
+}
+
+/// An import statement and the indexed file it resolved to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedImport {
+    /// Importing file
+    pub path: String,
+    /// 0 when unknown (indexed before line numbers were recorded)
+    pub line: i64,
+    /// The import as written
+    pub import: String,
+    pub kind: String,
+    /// Names brought into scope, when the import lists them
+    pub names: Vec<String>,
+    /// Resolved file
+    pub target: String,
+}
+
+/// Files that import each other, directly or transitively
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyCycle {
+    pub files: Vec<String>,
+    pub closing_edges: Vec<ClosingEdge>,
+}
+
+/// An import that completes a cycle, with the loop it closes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosingEdge {
+    pub from: String,
+    pub to: String,
+    /// `to → … → from → to`
+    pub cycle: Vec<String>,
+}
+
+/// A file, or a directory of collapsed files, in an exported graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// File path, or directory path when collapsed
+    pub id: String,
+    /// Language covering most of the node's lines
+    pub language: String,
+    pub lines: i64,
+    pub commits: i64,
+    /// Highest churn score among the node's files
+    pub churn: f64,
+    pub files: usize,
+}
+
+/// An import edge between two graph nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// File-to-file imports merged into this edge
+    pub weight: usize,
+}
+
+/// The import graph, or a slice of it, ready for export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A function over one of the `[health]` size thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OversizedFunction {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub start_line: i64,
+    pub lines: i64,
+    pub metrics: SymbolMetrics,
+    /// Thresholds exceeded: "lines", "complexity", "nesting", "params"
+    pub exceeds: Vec<String>,
+}
+
 /// File health metrics for warnings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileHealth {
@@ -156,4 +371,8 @@ pub struct FileHealth {
     pub dependents_count: i64,
     pub is_fragile: bool,
     pub is_dead: bool,
+    pub is_large: bool,
+    /// Only one person has committed to this file
+    pub is_single_owner: bool,
+    pub owner: Option<String>,
 }
@@ -0,0 +1,340 @@
+use super::*;
+
+const REFERENCE_SELECT: &str = "SELECT f.path, r.line, r.kind, caller.name, r.to_name, tf.path
+     FROM \"references\" r
+     JOIN files f ON f.id = r.file_id
+     LEFT JOIN symbols caller ON caller.id = r.from_symbol_id
+     LEFT JOIN symbols target ON target.id = r.to_symbol_id
+     LEFT JOIN files tf ON tf.id = target.file_id";
+
+impl Database {
+    // =================================================================
+    // Reference (call graph) operations
+    // =================================================================
+
+    /// Clear all call references originating in a file
+    pub fn clear_references(&self, file_id: i64) -> Result<()> {
+        self.conn
+            .prepare_cached("DELETE FROM \"references\" WHERE file_id = ?1")?
+            .execute([file_id])?;
+        Ok(())
+    }
+
+    /// Insert a call reference
+    pub fn insert_reference(
+        &self,
+        file_id: i64,
+        from_symbol_id: Option<i64>,
+        to_name: &str,
+        kind: &str,
+        line: i64,
+    ) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT INTO \"references\" (file_id, from_symbol_id, to_name, kind, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute(rusqlite::params![
+                file_id,
+                from_symbol_id,
+                to_name,
+                kind,
+                line
+            ])?;
+        Ok(())
+    }
+
+    /// Link unresolved references to symbol definitions by name.
+    ///
+    /// Prefers a definition in the calling file, then one in a file the caller
+    /// imports (directly, then through barrel re-exports), and otherwise only
+    /// accepts a project-wide unique name. Modules are never call targets.
+    pub fn resolve_references(&self) -> Result<()> {
+        self.resolve_reference_rows("to_symbol_id IS NULL", &[])
+    }
+
+    /// Resolve the unresolved references made from `file_ids`, and re-resolve
+    /// every reference calling one of `names` (symbols that were added or
+    /// removed): a new definition can be a better target than the current
+    /// one, or make a unique name ambiguous.
+    pub fn resolve_references_for(&self, file_ids: &[i64], names: &[String]) -> Result<()> {
+        if file_ids.is_empty() && names.is_empty() {
+            return Ok(());
+        }
+
+        let mut params: Vec<&dyn rusqlite::types::ToSql> = Vec::new();
+        let mut file_slots = Vec::new();
+        for id in file_ids {
+            params.push(id);
+            file_slots.push(format!("?{}", params.len()));
+        }
+        let mut name_slots = Vec::new();
+        for name in names {
+            params.push(name);
+            name_slots.push(format!("?{}", params.len()));
+        }
+
+        self.resolve_reference_rows(
+            &format!(
+                "(r.to_symbol_id IS NULL AND r.file_id IN ({})) OR r.to_name IN ({})",
+                file_slots.join(","),
+                name_slots.join(",")
+            ),
+            &params,
+        )
+    }
+
+    /// Point the references matching `filter` at their best definition, or
+    /// at nothing when none is acceptable
+    fn resolve_reference_rows(
+        &self,
+        filter: &str,
+        params: &[&dyn rusqlite::types::ToSql],
+    ) -> Result<()> {
+        self.conn.execute(
+            &format!(
+                "UPDATE \"references\" AS r SET to_symbol_id = COALESCE(
+                (SELECT MIN(s.id) FROM symbols s
+                 WHERE s.name = r.to_name AND s.kind != 'module' AND s.file_id = r.file_id),
+                (SELECT MIN(s.id) FROM symbols s
+                 WHERE s.name = r.to_name AND s.kind != 'module'
+                   AND s.file_id IN (
+                       SELECT d.to_file_id FROM dependencies d WHERE d.from_file_id = r.file_id
+                   )),
+                (SELECT MIN(s.id) FROM symbols s
+                 WHERE s.name = r.to_name AND s.kind != 'module'
+                   AND s.file_id IN (
+                       WITH RECURSIVE reexported(id) AS (
+                           SELECT d.to_file_id FROM dependencies d WHERE d.from_file_id = r.file_id
+                           UNION
+                           SELECT d.to_file_id FROM dependencies d
+                           JOIN reexported x ON x.id = d.from_file_id
+                           WHERE d.kind IN ('re-export', 're-export-all')
+                       )
+                       SELECT id FROM reexported
+                   )),
+                (SELECT MIN(s.id) FROM symbols s
+                 WHERE s.name = r.to_name AND s.kind != 'module'
+                 HAVING COUNT(*) = 1)
+             )
+             WHERE {filter}"
+            ),
+            params,
+        )?;
+        Ok(())
+    }
+
+    /// Get all call sites that invoke a symbol with the given name
+    pub fn get_callers(&self, name: &str) -> Result<Vec<SymbolReference>> {
+        self.query_references(
+            &format!("{REFERENCE_SELECT} WHERE r.to_name = ?1 ORDER BY f.path, r.line"),
+            name,
+        )
+    }
+
+    /// Get all call sites inside symbols with the given name
+    pub fn get_callees(&self, name: &str) -> Result<Vec<SymbolReference>> {
+        self.query_references(
+            &format!("{REFERENCE_SELECT} WHERE caller.name = ?1 ORDER BY f.path, r.line"),
+            name,
+        )
+    }
+
+    /// Find symbol definitions by name, optionally restricted to one file
+    pub fn find_symbols(&self, name: &str, path: Option<&str>) -> Result<Vec<(Symbol, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.id, s.file_id, s.name, s.kind, s.start_line, s.end_line, s.signature,
+                    s.parent_symbol_id, f.path
+             FROM symbols s JOIN files f ON f.id = s.file_id
+             WHERE s.name = ?1 AND (?2 IS NULL OR f.path = ?2)
+             ORDER BY f.path, s.start_line",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![name, path], |row| {
+            let kind_str: String = row.get(3)?;
+            Ok((
+                Symbol {
+                    id: row.get(0)?,
+                    file_id: row.get(1)?,
+                    name: row.get(2)?,
+                    kind: SymbolKind::from_db_str(&kind_str),
+                    start_line: row.get(4)?,
+                    end_line: row.get(5)?,
+                    signature: row.get(6)?,
+                    parent_symbol_id: row.get(7)?,
+                },
+                row.get(8)?,
+            ))
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Get the call sites resolved to a symbol, as impacts at depth 0
+    pub fn get_symbol_callers(&self, symbol_id: i64) -> Result<Vec<SymbolImpact>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.from_symbol_id, caller.name, caller.kind, r.file_id, f.path, r.line
+             FROM \"references\" r
+             JOIN files f ON f.id = r.file_id
+             LEFT JOIN symbols caller ON caller.id = r.from_symbol_id
+             WHERE r.to_symbol_id = ?1
+             ORDER BY f.path, r.line",
+        )?;
+        let rows = stmt.query_map([symbol_id], |row| {
+            Ok(SymbolImpact {
+                symbol_id: row.get(0)?,
+                symbol: row.get(1)?,
+                kind: row.get(2)?,
+                file_id: row.get(3)?,
+                path: row.get(4)?,
+                line: row.get(5)?,
+                via: "call".to_string(),
+                depth: 0,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Get files importing `name` (or a wildcard) from the given file, as impacts at depth 0.
+    ///
+    /// Barrel files that re-export the name are followed, so importing it from
+    /// `index.ts` counts as importing it from the defining file.
+    pub fn get_name_importers(&self, file_id: i64, name: &str) -> Result<Vec<SymbolImpact>> {
+        let mut stmt = self.conn.prepare(
+            "WITH RECURSIVE exporters(id) AS (
+                 SELECT ?1
+                 UNION
+                 SELECT d.from_file_id FROM dependencies d
+                 JOIN exporters e ON e.id = d.to_file_id
+                 WHERE d.kind IN ('re-export', 're-export-all')
+                   AND EXISTS (
+                       SELECT 1 FROM json_each(d.imported_names) n
+                       WHERE n.value = ?2 OR n.value LIKE '*%'
+                   )
+             )
+             SELECT DISTINCT d.from_file_id, f.path FROM dependencies d
+             JOIN files f ON f.id = d.from_file_id
+             WHERE d.to_file_id IN exporters
+               AND d.from_file_id != ?1
+               AND EXISTS (
+                   SELECT 1 FROM json_each(d.imported_names) n
+                   WHERE n.value = ?2 OR n.value LIKE '*%'
+               )
+             ORDER BY f.path",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![file_id, name], |row| {
+            Ok(SymbolImpact {
+                symbol_id: None,
+                symbol: None,
+                kind: None,
+                file_id: row.get(0)?,
+                path: row.get(1)?,
+                line: 0,
+                via: "import".to_string(),
+                depth: 0,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Count total call references
+    pub fn count_references(&self) -> Result<i64> {
+        Ok(self
+            .conn
+            .query_row("SELECT COUNT(*) FROM \"references\"", [], |row| row.get(0))?)
+    }
+
+    fn query_references(&self, sql: &str, name: &str) -> Result<Vec<SymbolReference>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([name], |row| {
+            Ok(SymbolReference {
+                file: row.get(0)?,
+                line: row.get(1)?,
+                kind: row.get(2)?,
+                caller: row.get(3)?,
+                callee: row.get(4)?,
+                callee_file: row.get(5)?,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzer::modules::ProjectModules;
+    use crate::db::models::SymbolKind;
+    use crate::db::Database;
+
+    #[test]
+    fn name_importers_follow_re_exports() {
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "typescript", 0, "", 1).unwrap();
+        let import = |from: i64, target: &str, kind: &str, names: &str| {
+            db.insert_dependency(from, target, kind, names, 1).unwrap()
+        };
+        let lib = file("src/lib.ts");
+        db.insert_symbol(lib, "save", &SymbolKind::Function, 1, 3, "", None)
+            .unwrap();
+        import(file("src/barrel.ts"), "./lib", "re-export", r#"["save"]"#);
+        import(file("src/all.ts"), "./barrel", "re-export-all", r#"["*"]"#);
+        import(file("src/other.ts"), "./lib", "re-export", r#"["load"]"#);
+        import(file("src/a.ts"), "./barrel", "import", r#"["save"]"#);
+        import(file("src/b.ts"), "./all", "import", r#"["save"]"#);
+        import(file("src/c.ts"), "./other", "import", r#"["save"]"#);
+        import(file("src/d.ts"), "./barrel", "import", r#"["load"]"#);
+        import(file("src/e.ts"), "./lib", "import", r#"["* as lib"]"#);
+        import(lib, "./lib", "import", r#"["save"]"#);
+        db.resolve_dependencies(&ProjectModules::default()).unwrap();
+
+        let importers: Vec<String> = db
+            .get_name_importers(lib, "save")
+            .unwrap()
+            .into_iter()
+            .map(|i| i.path)
+            .collect();
+        // Through one barrel (a), two (b) and a namespace import (e); not
+        // through a barrel that re-exports other names (c), not for other
+        // names (d), and never the defining file itself
+        assert_eq!(
+            importers,
+            [
+                "src/a.ts",
+                "src/all.ts",
+                "src/b.ts",
+                "src/barrel.ts",
+                "src/e.ts"
+            ]
+        );
+    }
+
+    #[test]
+    fn new_definitions_re_resolve_callers() {
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "typescript", 0, "", 1).unwrap();
+        let define = |file_id: i64| {
+            db.insert_symbol(file_id, "save", &SymbolKind::Function, 1, 3, "", None)
+                .unwrap()
+        };
+        let callee_file = || db.get_callers("save").unwrap()[0].callee_file.clone();
+        let util = file("src/util.ts");
+        define(util);
+        let app = file("src/app.ts");
+        db.insert_reference(app, None, "save", "call", 5).unwrap();
+        db.resolve_references().unwrap();
+        assert_eq!(callee_file().as_deref(), Some("src/util.ts"));
+
+        // A second definition makes the name ambiguous
+        let store = file("src/store.ts");
+        define(store);
+        db.resolve_references_for(&[store], &["save".to_string()])
+            .unwrap();
+        assert_eq!(callee_file(), None);
+
+        // Importing one of them makes it the target
+        db.insert_dependency(app, "./store", "import", r#"["save"]"#, 1)
+            .unwrap();
+        db.resolve_dependencies(&ProjectModules::default()).unwrap();
+        db.resolve_references_for(&[], &["save".to_string()])
+            .unwrap();
+        assert_eq!(callee_file().as_deref(), Some("src/store.ts"));
+    }
+}
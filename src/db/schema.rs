@@ -1,8 +1,171 @@
-use anyhow::Result;
-use rusqlite::Connection;
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior};
 
-/// Run all schema migrations
+/// A numbered, forward-only schema change
+struct Migration {
+    version: i64,
+    description: &'static str,
+    up: fn(&Connection) -> Result<()>,
+}
+
+/// All migrations, in order. Never edit or reorder a released migration;
+/// append a new one and add a fixture of the previous schema to the tests.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema",
+        up: migrate_initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "call references",
+        up: migrate_call_references,
+    },
+    Migration {
+        version: 3,
+        description: "search index keyed by symbol id",
+        up: migrate_search_index_rowids,
+    },
+    Migration {
+        version: 4,
+        description: "incremental git history",
+        up: migrate_git_history,
+    },
+    Migration {
+        version: 5,
+        description: "rename-aware git history",
+        up: migrate_git_renames,
+    },
+    Migration {
+        version: 6,
+        description: "co-change coupling",
+        up: migrate_cochange,
+    },
+    Migration {
+        version: 7,
+        description: "line-level churn",
+        up: migrate_line_churn,
+    },
+    Migration {
+        version: 8,
+        description: "function complexity",
+        up: migrate_function_complexity,
+    },
+    Migration {
+        version: 9,
+        description: "function size metrics",
+        up: migrate_function_size,
+    },
+    Migration {
+        version: 10,
+        description: "import line numbers",
+        up: migrate_import_lines,
+    },
+    Migration {
+        version: 11,
+        description: "swift parsing",
+        up: migrate_swift_parsing,
+    },
+    Migration {
+        version: 12,
+        description: "tsx grammar and react symbols",
+        up: migrate_tsx_parsing,
+    },
+    Migration {
+        version: 13,
+        description: "commonjs and dynamic imports",
+        up: migrate_js_module_calls,
+    },
+    Migration {
+        version: 14,
+        description: "kotlin parsing",
+        up: migrate_kotlin_parsing,
+    },
+    Migration {
+        version: 15,
+        description: "rust use names",
+        up: migrate_rust_use_names,
+    },
+    Migration {
+        version: 16,
+        description: "co-change confidence over all commits",
+        up: migrate_cochange_confidence,
+    },
+];
+
+/// Schema version produced by this binary
+pub const SCHEMA_VERSION: i64 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+/// Bring the database up to `SCHEMA_VERSION`.
+///
+/// The version is read and every pending migration applied, together with the
+/// bump of the `schema_version` meta key, in one transaction that holds the
+/// write lock from the start: two processes opening an old database at once
+/// must not both apply the same migration. Databases written by a newer
+/// binary are refused.
 pub fn run_migrations(conn: &Connection) -> Result<()> {
+    // Up to date: no need to take the write lock on every open
+    if schema_version(conn)? == SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        bail!(
+            "Database schema version {} is newer than this ctx-agent supports ({}).\n\
+             Upgrade ctx-agent to open this project.",
+            current,
+            SCHEMA_VERSION
+        );
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        (migration.up)(conn).with_context(|| {
+            format!(
+                "Schema migration {} ({}) failed",
+                migration.version, migration.description
+            )
+        })?;
+        conn.execute(
+            "INSERT INTO meta (key, value) VALUES ('schema_version', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1",
+            [migration.version.to_string()],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Read the stored schema version, inferring it for databases created before
+/// versioning existed (0 for a fresh database)
+pub fn schema_version(conn: &Connection) -> Result<i64> {
+    if table_exists(conn, "meta")? {
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT value FROM meta WHERE key = 'schema_version'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(value) = stored {
+            return value
+                .parse()
+                .with_context(|| format!("Invalid schema_version in meta: {}", value));
+        }
+    }
+
+    if !table_exists(conn, "files")? {
+        Ok(0)
+    } else if !table_exists(conn, "references")? {
+        Ok(1)
+    } else {
+        Ok(2)
+    }
+}
+
+fn migrate_initial_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS files (
@@ -72,12 +235,8 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_deps_from ON dependencies(from_file_id);
         CREATE INDEX IF NOT EXISTS idx_deps_to ON dependencies(to_file_id);
         CREATE INDEX IF NOT EXISTS idx_knowledge_file ON knowledge(related_file);
-    ",
-    )?;
 
-    // Keep only one decision row per commit hash before enabling uniqueness.
-    conn.execute_batch(
-        "
+        -- Keep only one decision row per commit hash before enabling uniqueness.
         DELETE FROM decisions
         WHERE source = 'commit'
           AND commit_hash IS NOT NULL
@@ -90,12 +249,8 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         CREATE UNIQUE INDEX IF NOT EXISTS idx_decisions_commit_hash_unique
             ON decisions(commit_hash)
             WHERE source = 'commit' AND commit_hash IS NOT NULL;
-    ",
-    )?;
 
-    // FTS5 virtual table for full-text search
-    conn.execute_batch(
-        "
+        -- FTS5 virtual table for full-text search
         CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
             name,
             path,
@@ -105,6 +260,343 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         );
     ",
     )?;
+    Ok(())
+}
+
+fn migrate_call_references(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS \"references\" (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_id         INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+            from_symbol_id  INTEGER REFERENCES symbols(id) ON DELETE CASCADE,
+            to_name         TEXT NOT NULL,
+            to_symbol_id    INTEGER REFERENCES symbols(id) ON DELETE SET NULL,
+            kind            TEXT NOT NULL DEFAULT 'call',
+            line            INTEGER NOT NULL
+        );
 
+        CREATE INDEX IF NOT EXISTS idx_refs_file ON \"references\"(file_id);
+        CREATE INDEX IF NOT EXISTS idx_refs_from ON \"references\"(from_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_refs_to ON \"references\"(to_symbol_id);
+        CREATE INDEX IF NOT EXISTS idx_refs_to_name ON \"references\"(to_name);
+
+        -- Call references are only extracted while parsing; force existing files
+        -- to be re-analyzed so the call graph gets populated.
+        UPDATE files SET hash = '';
+    ",
+    )?;
     Ok(())
 }
+
+fn migrate_search_index_rowids(conn: &Connection) -> Result<()> {
+    // Incremental index updates address rows by symbol id
+    conn.execute_batch(
+        "
+        DELETE FROM search_index;
+        INSERT INTO search_index(rowid, name, path, kind, signature)
+            SELECT s.id, s.name, f.path, s.kind, s.signature
+            FROM symbols s JOIN files f ON f.id = s.file_id;
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_git_history(conn: &Connection) -> Result<()> {
+    // Commits already mined, so later scans only walk commits past the
+    // `git_head` watermark in meta. `file_stats` is derived from these.
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS git_commits (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            hash            TEXT NOT NULL UNIQUE,
+            author          TEXT NOT NULL,
+            timestamp       DATETIME
+        );
+
+        CREATE TABLE IF NOT EXISTS commit_files (
+            commit_id       INTEGER NOT NULL REFERENCES git_commits(id) ON DELETE CASCADE,
+            path            TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_commit_files_commit ON commit_files(commit_id);
+        CREATE INDEX IF NOT EXISTS idx_commit_files_path ON commit_files(path);
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_git_renames(conn: &Connection) -> Result<()> {
+    // `commit_files.path` now follows the file through later renames;
+    // `original_path` keeps the name it had in that commit.
+    conn.execute_batch(
+        "
+        ALTER TABLE git_commits ADD COLUMN summary TEXT NOT NULL DEFAULT '';
+        ALTER TABLE commit_files ADD COLUMN original_path TEXT NOT NULL DEFAULT '';
+        ALTER TABLE commit_files ADD COLUMN from_path TEXT;
+        ALTER TABLE commit_files ADD COLUMN status TEXT NOT NULL DEFAULT 'modified';
+
+        CREATE INDEX IF NOT EXISTS idx_commit_files_original ON commit_files(original_path);
+
+        -- Stored commits carry no rename information; mine them again
+        DELETE FROM commit_files;
+        DELETE FROM git_commits;
+        DELETE FROM meta WHERE key = 'git_head';
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_cochange(conn: &Connection) -> Result<()> {
+    // Derived from commit_files on the next history run; one row per
+    // direction, with confidence = support / commits touching `path`
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS cochange (
+            path            TEXT NOT NULL,
+            coupled_path    TEXT NOT NULL,
+            support         INTEGER NOT NULL,
+            confidence      REAL NOT NULL,
+            PRIMARY KEY (path, coupled_path)
+        );
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_line_churn(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        ALTER TABLE commit_files ADD COLUMN additions INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE commit_files ADD COLUMN deletions INTEGER NOT NULL DEFAULT 0;
+
+        -- Stored commits carry no line counts; mine them again
+        DELETE FROM commit_files;
+        DELETE FROM git_commits;
+        DELETE FROM meta WHERE key = 'git_head';
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_function_complexity(conn: &Connection) -> Result<()> {
+    // NULL for symbols that are not functions or methods
+    conn.execute_batch(
+        "
+        ALTER TABLE symbols ADD COLUMN complexity INTEGER;
+        ALTER TABLE symbols ADD COLUMN max_nesting INTEGER;
+
+        -- Metrics are only computed while parsing; re-analyze every file
+        UPDATE files SET hash = '';
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_function_size(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        ALTER TABLE symbols ADD COLUMN param_count INTEGER;
+        ALTER TABLE symbols ADD COLUMN statement_count INTEGER;
+
+        -- Metrics are only computed while parsing; re-analyze every file
+        UPDATE files SET hash = '';
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_import_lines(conn: &Connection) -> Result<()> {
+    // 0 until the importing file is parsed again
+    conn.execute_batch(
+        "
+        ALTER TABLE dependencies ADD COLUMN line INTEGER NOT NULL DEFAULT 0;
+
+        UPDATE files SET hash = '';
+    ",
+    )?;
+    Ok(())
+}
+
+fn migrate_swift_parsing(conn: &Connection) -> Result<()> {
+    // Swift files were tracked without symbols or imports before
+    conn.execute_batch("UPDATE files SET hash = '' WHERE language = 'swift';")?;
+    Ok(())
+}
+
+fn migrate_tsx_parsing(conn: &Connection) -> Result<()> {
+    // .tsx files were parsed with the plain TypeScript grammar, and no
+    // TS/JS file was scanned for components, hooks or JSX references
+    conn.execute_batch(
+        "UPDATE files SET language = 'tsx' WHERE path LIKE '%.tsx';
+         UPDATE files SET hash = '' WHERE language IN ('typescript', 'tsx', 'javascript');",
+    )?;
+    Ok(())
+}
+
+fn migrate_js_module_calls(conn: &Connection) -> Result<()> {
+    // require() and import() calls were not recorded as dependencies
+    conn.execute_batch(
+        "UPDATE files SET hash = '' WHERE language IN ('typescript', 'tsx', 'javascript');",
+    )?;
+    Ok(())
+}
+
+fn migrate_kotlin_parsing(conn: &Connection) -> Result<()> {
+    // Kotlin files were tracked without symbols or imports before
+    conn.execute_batch("UPDATE files SET hash = '' WHERE language = 'kotlin';")?;
+    Ok(())
+}
+
+fn migrate_rust_use_names(conn: &Connection) -> Result<()> {
+    // `use` imports recorded aliases instead of defining names, and `pub use`
+    // paths kept the visibility
+    conn.execute_batch("UPDATE files SET hash = '' WHERE language = 'rust';")?;
+    Ok(())
+}
+
+fn migrate_cochange_confidence(conn: &Connection) -> Result<()> {
+    // Confidence left single-file commits out of a file's total; forget the
+    // thresholds so the next history run recomputes every pair
+    conn.execute_batch("DELETE FROM meta WHERE key = 'cochange_settings';")?;
+    Ok(())
+}
+
+fn table_exists(conn: &Connection, name: &str) -> Result<bool> {
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Databases as written by past releases, oldest first. Each one must
+    /// upgrade cleanly to the current schema without losing data.
+    const FIXTURES: &[(&str, &str)] = &[
+        (
+            "unversioned, before call references",
+            include_str!("fixtures/unversioned_v1.sql"),
+        ),
+        (
+            "unversioned, with call references",
+            include_str!("fixtures/unversioned_v2.sql"),
+        ),
+        ("schema_version 1", include_str!("fixtures/schema_v1.sql")),
+        ("schema_version 2", include_str!("fixtures/schema_v2.sql")),
+        ("schema_version 3", include_str!("fixtures/schema_v3.sql")),
+        ("schema_version 4", include_str!("fixtures/schema_v4.sql")),
+        ("schema_version 5", include_str!("fixtures/schema_v5.sql")),
+        ("schema_version 6", include_str!("fixtures/schema_v6.sql")),
+        ("schema_version 7", include_str!("fixtures/schema_v7.sql")),
+        ("schema_version 8", include_str!("fixtures/schema_v8.sql")),
+        ("schema_version 9", include_str!("fixtures/schema_v9.sql")),
+        ("schema_version 10", include_str!("fixtures/schema_v10.sql")),
+        ("schema_version 11", include_str!("fixtures/schema_v11.sql")),
+        ("schema_version 12", include_str!("fixtures/schema_v12.sql")),
+        ("schema_version 13", include_str!("fixtures/schema_v13.sql")),
+        ("schema_version 14", include_str!("fixtures/schema_v14.sql")),
+        ("schema_version 15", include_str!("fixtures/schema_v15.sql")),
+    ];
+
+    fn open_fixture(sql: &str) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
+        conn.execute_batch(sql).unwrap();
+        conn
+    }
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn fresh_database_reaches_current_version() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        // Re-running is a no-op
+        run_migrations(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn every_past_schema_upgrades() {
+        for (name, sql) in FIXTURES {
+            let conn = open_fixture(sql);
+            assert!(schema_version(&conn).unwrap() < SCHEMA_VERSION, "{name}");
+
+            run_migrations(&conn).unwrap_or_else(|e| panic!("{name}: {e:#}"));
+            assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION, "{name}");
+
+            // Existing rows survive and every current table is usable
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM files"), 1, "{name}");
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM symbols"), 1, "{name}");
+            assert_eq!(count(&conn, "SELECT COUNT(*) FROM knowledge"), 1, "{name}");
+            count(&conn, "SELECT COUNT(*) FROM \"references\"");
+            count(&conn, "SELECT COUNT(*) FROM commit_files");
+            count(&conn, "SELECT COUNT(*) FROM cochange");
+
+            // Search rows are keyed by symbol id
+            assert_eq!(
+                count(
+                    &conn,
+                    "SELECT COUNT(*) FROM search_index si JOIN symbols s ON s.id = si.rowid"
+                ),
+                1,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn concurrent_opens_upgrade_once() {
+        let dir = std::env::temp_dir().join(format!("ctx-migrate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ctx.db");
+        let _ = std::fs::remove_file(&path);
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch("PRAGMA journal_mode=WAL;").unwrap();
+        conn.execute_batch(include_str!("fixtures/schema_v6.sql"))
+            .unwrap();
+        drop(conn);
+
+        // Each process reads the old version; only one may add the columns
+        let start = std::sync::Barrier::new(4);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let conn = Connection::open(&path).unwrap();
+                    start.wait();
+                    run_migrations(&conn).unwrap();
+                });
+            }
+        });
+        let conn = Connection::open(&path).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+
+        drop(conn);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_newer_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute(
+            "UPDATE meta SET value = ?1 WHERE key = 'schema_version'",
+            [(SCHEMA_VERSION + 1).to_string()],
+        )
+        .unwrap();
+
+        let err = run_migrations(&conn).unwrap_err();
+        assert!(err.to_string().contains("newer"), "{err}");
+    }
+}
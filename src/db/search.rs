@@ -5,20 +5,44 @@ impl Database {
     // Search operations (FTS5)
     // =================================================================
 
-    /// Rebuild the FTS5 search index
+    /// Rebuild the FTS5 search index. Rows are keyed by symbol id so they can
+    /// be updated per file.
     pub fn rebuild_search_index(&self) -> Result<()> {
         self.conn.execute("DELETE FROM search_index", [])?;
         self.conn.execute(
-            "INSERT INTO search_index(name, path, kind, signature)
-             SELECT s.name, f.path, s.kind, s.signature
+            "INSERT INTO search_index(rowid, name, path, kind, signature)
+             SELECT s.id, s.name, f.path, s.kind, s.signature
              FROM symbols s JOIN files f ON f.id = s.file_id",
             [],
         )?;
         Ok(())
     }
 
+    /// Add the symbols of one file to the search index
+    pub fn index_file_symbols(&self, file_id: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO search_index(rowid, name, path, kind, signature)
+             SELECT s.id, s.name, f.path, s.kind, s.signature
+             FROM symbols s JOIN files f ON f.id = s.file_id
+             WHERE s.file_id = ?1",
+            [file_id],
+        )?;
+        Ok(())
+    }
+
+    /// Remove symbols from the search index by symbol id
+    pub fn unindex_symbols(&self, symbol_ids: &[i64]) -> Result<()> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("DELETE FROM search_index WHERE rowid = ?1")?;
+        for id in symbol_ids {
+            stmt.execute([id])?;
+        }
+        Ok(())
+    }
+
     /// Full-text search across symbols
-    pub fn search(&self, query: &str) -> Result<Vec<(String, String, String, String)>> {
+    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
         let fts_query = query
             .split_whitespace()
             .map(|w| format!("{}*", w))
@@ -26,10 +50,22 @@ impl Database {
             .join(" ");
 
         let mut stmt = self.conn.prepare(
-            "SELECT name, path, kind, signature FROM search_index WHERE search_index MATCH ?1 LIMIT 50"
+            "SELECT search_index.name, search_index.path, search_index.kind, search_index.signature,
+                    s.start_line, s.end_line,
+                    s.complexity, s.max_nesting, s.param_count, s.statement_count
+             FROM search_index JOIN symbols s ON s.id = search_index.rowid
+             WHERE search_index MATCH ?1 LIMIT 50",
         )?;
         let rows = stmt.query_map([&fts_query], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            Ok(SearchResult {
+                name: row.get(0)?,
+                path: row.get(1)?,
+                kind: row.get(2)?,
+                signature: row.get(3)?,
+                start_line: row.get(4)?,
+                end_line: row.get(5)?,
+                metrics: metrics_from_row(row, 6)?,
+            })
         })?;
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
@@ -2,52 +2,367 @@ use super::*;
 
 impl Database {
     // =================================================================
-    // File stats operations
+    // Git history operations
     // =================================================================
 
-    /// Upsert file stats
-    pub fn upsert_file_stats(
+    /// Record a mined commit and the files it touched
+    pub fn insert_commit(
         &self,
-        file_id: i64,
-        commit_count: i64,
-        last_modified: Option<&str>,
-        churn_score: f64,
-        contributors: i64,
+        hash: &str,
+        author: &str,
+        timestamp: Option<&str>,
+        summary: &str,
+        changes: &[CommitFileChange],
     ) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT OR IGNORE INTO git_commits (hash, author, timestamp, summary)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute(rusqlite::params![hash, author, timestamp, summary])?;
+        if self.conn.changes() == 0 {
+            return Ok(());
+        }
+
+        let commit_id = self.conn.last_insert_rowid();
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT INTO commit_files
+                 (commit_id, path, original_path, from_path, status, additions, deletions)
+             VALUES (?1, ?2, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for change in changes {
+            stmt.execute(rusqlite::params![
+                commit_id,
+                change.path,
+                change.from_path,
+                change.status,
+                change.additions,
+                change.deletions
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Carry the history of `old_path` over to `new_path` after a rename.
+    ///
+    /// Must run before the renaming commit is recorded: commits are inserted
+    /// oldest first, so only earlier commits and their decisions are updated.
+    pub fn follow_rename(&self, old_path: &str, new_path: &str) -> Result<()> {
+        self.conn
+            .prepare_cached("UPDATE commit_files SET path = ?2 WHERE path = ?1")?
+            .execute([old_path, new_path])?;
+        self.conn
+            .prepare_cached(
+                "UPDATE decisions
+                 SET related_files = (
+                     SELECT json_group_array(CASE WHEN value = ?1 THEN ?2 ELSE value END)
+                     FROM json_each(decisions.related_files)
+                 )
+                 WHERE source = 'commit'
+                   AND commit_hash IN (SELECT hash FROM git_commits)
+                   AND EXISTS (SELECT 1 FROM json_each(decisions.related_files) WHERE value = ?1)",
+            )?
+            .execute([old_path, new_path])?;
+        Ok(())
+    }
+
+    /// Commits touching a file under any of its past names, newest first
+    pub fn get_file_history(&self, path: &str) -> Result<Vec<FileHistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.hash, c.author, c.timestamp, c.summary, cf.original_path, cf.from_path, cf.status
+             FROM commit_files cf
+             JOIN git_commits c ON c.id = cf.commit_id
+             WHERE cf.path = ?1
+             ORDER BY c.timestamp DESC, c.id DESC",
+        )?;
+        let rows = stmt.query_map([path], |row| {
+            Ok(FileHistoryEntry {
+                hash: row.get(0)?,
+                author: row.get(1)?,
+                timestamp: row.get(2)?,
+                summary: row.get(3)?,
+                path: row.get(4)?,
+                from_path: row.get(5)?,
+                status: row.get(6)?,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Commits and changed lines for a file within each of the last `days` windows
+    pub fn get_file_churn(&self, path: &str, windows: &[i64]) -> Result<Vec<WindowChurn>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT COUNT(DISTINCT cf.commit_id),
+                    COALESCE(SUM(cf.additions), 0),
+                    COALESCE(SUM(cf.deletions), 0)
+             FROM commit_files cf
+             JOIN git_commits c ON c.id = cf.commit_id
+             WHERE cf.path = ?1 AND c.timestamp >= datetime('now', ?2)",
+        )?;
+        windows
+            .iter()
+            .map(|&days| {
+                Ok(
+                    stmt.query_row(rusqlite::params![path, format!("-{} days", days)], |row| {
+                        Ok(WindowChurn {
+                            days,
+                            commits: row.get(0)?,
+                            additions: row.get(1)?,
+                            deletions: row.get(2)?,
+                        })
+                    })?,
+                )
+            })
+            .collect()
+    }
+
+    /// Authors of a file's stored history, largest share first
+    pub fn get_file_authors(&self, path: &str) -> Result<Vec<AuthorShare>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.author, COUNT(DISTINCT cf.commit_id), SUM(cf.additions), SUM(cf.deletions)
+             FROM commit_files cf
+             JOIN git_commits c ON c.id = cf.commit_id
+             WHERE cf.path = ?1
+             GROUP BY c.author
+             ORDER BY SUM(cf.additions + cf.deletions) DESC, COUNT(DISTINCT cf.commit_id) DESC",
+        )?;
+        let mut authors: Vec<AuthorShare> = stmt
+            .query_map([path], |row| {
+                Ok(AuthorShare {
+                    author: row.get(0)?,
+                    commits: row.get(1)?,
+                    additions: row.get(2)?,
+                    deletions: row.get(3)?,
+                    share: 0.0,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let total_lines: i64 = authors.iter().map(|a| a.additions + a.deletions).sum();
+        let total_commits: i64 = authors.iter().map(|a| a.commits).sum();
+        for author in &mut authors {
+            author.share = if total_lines > 0 {
+                (author.additions + author.deletions) as f64 / total_lines as f64
+            } else {
+                author.commits as f64 / total_commits.max(1) as f64
+            };
+        }
+        Ok(authors)
+    }
+
+    /// Current name of a file that was known as `path` in some commit
+    pub fn get_renamed_path(&self, path: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT path FROM commit_files WHERE original_path = ?1
+                 ORDER BY commit_id DESC LIMIT 1",
+                [path],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Forget all mined commits (before re-walking rewritten history)
+    pub fn clear_git_history(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM git_commits", [])?;
+        Ok(())
+    }
+
+    /// Keep only the `keep` most recent commits, returning the paths the
+    /// dropped commits touched. Commits are inserted oldest first, so the id
+    /// breaks timestamp ties.
+    pub fn prune_git_history(&self, keep: usize) -> Result<Vec<String>> {
+        let stale = "SELECT id FROM git_commits WHERE id NOT IN (
+                         SELECT id FROM git_commits ORDER BY timestamp DESC, id DESC LIMIT ?1
+                     )";
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT DISTINCT path FROM commit_files WHERE commit_id IN ({stale})"
+        ))?;
+        let paths: Vec<String> = stmt
+            .query_map([keep as i64], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
         self.conn.execute(
-            "INSERT INTO file_stats (file_id, commit_count, last_modified, churn_score, contributors)
-             VALUES (?1, ?2, ?3, ?4, ?5)
-             ON CONFLICT(file_id) DO UPDATE SET
-                commit_count = ?2, last_modified = ?3, churn_score = ?4, contributors = ?5",
-            rusqlite::params![file_id, commit_count, last_modified, churn_score, contributors],
+            &format!("DELETE FROM git_commits WHERE id IN ({stale})"),
+            [keep as i64],
+        )?;
+        Ok(paths)
+    }
+
+    // =================================================================
+    // File stats operations
+    // =================================================================
+
+    /// Recompute `file_stats` for every indexed file from the stored commits
+    /// and return how many files have history
+    pub fn refresh_file_stats(&self) -> Result<usize> {
+        self.conn.execute("DELETE FROM file_stats", [])?;
+        self.insert_file_stats("")?;
+        self.rescale_churn()
+    }
+
+    /// Recompute `file_stats` only for `paths` (files touched by newly mined
+    /// or pruned commits) and for indexed files that have no stats yet, e.g.
+    /// re-added since the last refresh. Returns how many files have history.
+    pub fn refresh_file_stats_for(&self, paths: &[String]) -> Result<usize> {
+        for path in paths {
+            self.conn
+                .prepare_cached(
+                    "DELETE FROM file_stats
+                     WHERE file_id IN (SELECT id FROM files WHERE path = ?1)",
+                )?
+                .execute([path])?;
+        }
+        self.insert_file_stats("WHERE f.id NOT IN (SELECT file_id FROM file_stats)")?;
+        self.rescale_churn()
+    }
+
+    /// Insert stats for the indexed files matching `filter` (on `files f`)
+    fn insert_file_stats(&self, filter: &str) -> Result<()> {
+        self.conn.execute(
+            &format!(
+                "INSERT INTO file_stats
+                     (file_id, commit_count, last_modified, churn_score, contributors)
+                 SELECT f.id, COUNT(*), MAX(c.timestamp), 0.0, COUNT(DISTINCT c.author)
+                 FROM files f
+                 JOIN commit_files cf ON cf.path = f.path
+                 JOIN git_commits c ON c.id = cf.commit_id
+                 {filter}
+                 GROUP BY f.id"
+            ),
+            [],
         )?;
         Ok(())
     }
 
+    /// Scale churn scores against the busiest file, returning the number of
+    /// files with stats
+    fn rescale_churn(&self) -> Result<usize> {
+        self.conn.execute(
+            "UPDATE file_stats
+             SET churn_score = CAST(commit_count AS REAL) / (SELECT MAX(commit_count) FROM file_stats)
+             WHERE churn_score IS NOT CAST(commit_count AS REAL) / (SELECT MAX(commit_count) FROM file_stats)",
+            [],
+        )?;
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM file_stats", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
     /// Get file health overview (for warnings)
     pub fn get_file_health(&self) -> Result<Vec<FileHealth>> {
         let mut stmt = self.conn.prepare(
             "SELECT f.path, f.language, f.line_count,
                     COALESCE(fs.commit_count, 0),
                     COALESCE(fs.churn_score, 0.0),
-                    (SELECT COUNT(*) FROM dependencies d WHERE d.to_file_id = f.id)
+                    (SELECT COUNT(*) FROM dependencies d WHERE d.to_file_id = f.id),
+                    COALESCE(fs.contributors, 0),
+                    CASE WHEN fs.contributors = 1 THEN (
+                        SELECT c.author FROM commit_files cf
+                        JOIN git_commits c ON c.id = cf.commit_id
+                        WHERE cf.path = f.path LIMIT 1
+                    ) END
              FROM files f
              LEFT JOIN file_stats fs ON fs.file_id = f.id
              ORDER BY fs.churn_score DESC NULLS LAST",
         )?;
+        let health = &self.config.health;
+        // Single ownership only means something once several people commit
+        let shared_repo = self.conn.query_row(
+            "SELECT COUNT(DISTINCT author) > 1 FROM git_commits",
+            [],
+            |row| row.get::<_, bool>(0),
+        )?;
         let rows = stmt.query_map([], |row| {
+            let line_count: i64 = row.get(2)?;
             let churn_score: f64 = row.get(4)?;
             let commit_count: i64 = row.get(3)?;
             let dependents_count: i64 = row.get(5)?;
+            let contributors: i64 = row.get(6)?;
             Ok(FileHealth {
                 path: row.get(0)?,
                 language: row.get(1)?,
-                line_count: row.get(2)?,
+                line_count,
                 commit_count,
                 churn_score,
                 dependents_count,
-                is_fragile: churn_score > 0.7 && dependents_count > 3,
+                is_fragile: health.is_fragile(churn_score, dependents_count),
                 is_dead: commit_count == 0 && dependents_count == 0,
+                is_large: health.is_large(line_count),
+                is_single_owner: shared_repo && health.is_single_owner(contributors, commit_count),
+                owner: row.get(7)?,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Functions over the configured size thresholds, most complex first
+    pub fn get_oversized_functions(&self) -> Result<Vec<OversizedFunction>> {
+        let health = &self.config.health;
+        let mut stmt = self.conn.prepare(
+            "SELECT s.name, s.kind, f.path, s.start_line, s.end_line - s.start_line + 1,
+                    s.complexity, s.max_nesting, s.param_count, s.statement_count
+             FROM symbols s
+             JOIN files f ON f.id = s.file_id
+             WHERE s.complexity IS NOT NULL
+               AND (s.end_line - s.start_line + 1 > ?1 OR s.complexity > ?2
+                    OR s.max_nesting > ?3 OR s.param_count > ?4)
+             ORDER BY s.complexity DESC, f.path, s.start_line",
+        )?;
+        let params = rusqlite::params![
+            health.large_function_lines,
+            health.large_function_complexity,
+            health.large_function_nesting,
+            health.large_function_params
+        ];
+        let rows = stmt.query_map(params, |row| {
+            let lines: i64 = row.get(4)?;
+            let metrics = metrics_from_row(row, 5)?.unwrap_or_default();
+            Ok(OversizedFunction {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                path: row.get(2)?,
+                start_line: row.get(3)?,
+                lines,
+                exceeds: health
+                    .oversized_function(lines, &metrics)
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                metrics,
+            })
+        })?;
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Functions ranked by complexity × commits to their file, highest first
+    pub fn get_hotspots(&self, limit: usize) -> Result<Vec<Hotspot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.name, s.kind, f.path, s.start_line, s.end_line,
+                    s.complexity, s.max_nesting, fs.commit_count,
+                    s.complexity * fs.commit_count AS score
+             FROM symbols s
+             JOIN files f ON f.id = s.file_id
+             JOIN file_stats fs ON fs.file_id = f.id
+             WHERE s.complexity IS NOT NULL AND fs.commit_count > 0
+             ORDER BY score DESC, s.complexity DESC, f.path, s.start_line
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([limit as i64], |row| {
+            Ok(Hotspot {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                path: row.get(2)?,
+                start_line: row.get(3)?,
+                end_line: row.get(4)?,
+                complexity: row.get(5)?,
+                max_nesting: row.get(6)?,
+                commit_count: row.get(7)?,
+                score: row.get(8)?,
             })
         })?;
         Ok(rows.filter_map(|r| r.ok()).collect())
@@ -82,3 +397,93 @@ impl Database {
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::analyze_git_history;
+    use crate::git::testing::TestRepo;
+
+    fn authors(db: &Database, path: &str) -> Vec<(String, i64)> {
+        db.get_file_authors(path)
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.author, a.commits))
+            .collect()
+    }
+
+    fn commit_count(db: &Database, path: &str) -> Option<i64> {
+        db.get_file_health()
+            .unwrap()
+            .into_iter()
+            .find(|f| f.path == path)
+            .map(|f| f.commit_count)
+    }
+
+    #[test]
+    fn history_follows_renames() {
+        let repo = TestRepo::new("stats-rename");
+        let body = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+        repo.commit("ann", "add old", &[("src/old.rs", Some(body))]);
+        repo.commit(
+            "ann",
+            "edit old",
+            &[("src/old.rs", Some(&body.replace('2', "3")))],
+        );
+        repo.rename("src/old.rs", "src/new.rs");
+        repo.commit("bob", "move to new", &[]);
+        repo.commit(
+            "carol",
+            "edit new",
+            &[("src/new.rs", Some(&body.replace('2', "4")))],
+        );
+
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_file("src/new.rs", "rust", 0, "", 5).unwrap();
+        analyze_git_history(&db, &repo.root).unwrap();
+
+        assert_eq!(commit_count(&db, "src/new.rs"), Some(4));
+        let window = db.get_file_churn("src/new.rs", &[30]).unwrap();
+        assert_eq!(window[0].commits, 4);
+        assert_eq!(
+            authors(&db, "src/new.rs"),
+            [("ann".into(), 2), ("carol".into(), 1), ("bob".into(), 1)]
+        );
+        assert!(db.get_file_history("src/old.rs").unwrap().is_empty());
+        assert!(authors(&db, "src/old.rs").is_empty());
+        assert_eq!(
+            db.get_renamed_path("src/old.rs").unwrap().as_deref(),
+            Some("src/new.rs")
+        );
+    }
+
+    #[test]
+    fn history_follows_renames_with_edits() {
+        let repo = TestRepo::new("stats-rename-edit");
+        let body: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+        repo.commit("ann", "add util", &[("util.rs", Some(&body))]);
+        repo.rename("util.rs", "lib/util.rs");
+        // Moved and changed in the same commit, still similar enough to pair
+        repo.commit(
+            "bob",
+            "move util",
+            &[("lib/util.rs", Some(&body.replace("line 20", "last line")))],
+        );
+
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_file("lib/util.rs", "rust", 0, "", 20).unwrap();
+        analyze_git_history(&db, &repo.root).unwrap();
+
+        let history = db.get_file_history("lib/util.rs").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, "renamed");
+        assert_eq!(history[0].from_path.as_deref(), Some("util.rs"));
+        assert_eq!(history[1].path, "util.rs");
+        assert_eq!(commit_count(&db, "lib/util.rs"), Some(2));
+        assert_eq!(
+            authors(&db, "lib/util.rs"),
+            [("ann".into(), 1), ("bob".into(), 1)]
+        );
+        assert!(db.get_file_history("util.rs").unwrap().is_empty());
+    }
+}
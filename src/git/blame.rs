@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use git2::Oid;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::db::models::Symbol;
+
+/// Who last changed a symbol, and who wrote its current lines
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolOwnership {
+    pub name: String,
+    pub kind: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub last_touched: Option<LastTouch>,
+    /// Current lines per author, most first
+    pub authors: Vec<LineShare>,
+}
+
+/// Most recent change to a range of lines
+#[derive(Debug, Clone, Serialize)]
+pub struct LastTouch {
+    /// `None` for uncommitted working-tree changes
+    pub commit: Option<String>,
+    pub author: String,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LineShare {
+    pub author: String,
+    pub lines: usize,
+}
+
+/// A blamed run of lines
+struct Hunk {
+    start: i64,
+    end: i64,
+    commit: Option<String>,
+    author: String,
+    seconds: i64,
+}
+
+/// Blame a file as it is on disk and attribute each symbol's line range
+pub fn symbol_ownership(
+    project_root: &Path,
+    path: &str,
+    symbols: &[Symbol],
+) -> Result<Vec<SymbolOwnership>> {
+    let hunks = blame_hunks(project_root, path)?;
+
+    Ok(symbols
+        .iter()
+        .map(|sym| {
+            let mut lines: HashMap<&str, usize> = HashMap::new();
+            let mut last: Option<&Hunk> = None;
+            for hunk in &hunks {
+                let overlap = hunk.end.min(sym.end_line) - hunk.start.max(sym.start_line) + 1;
+                if overlap <= 0 {
+                    continue;
+                }
+                *lines.entry(hunk.author.as_str()).or_default() += overlap as usize;
+                if last.is_none_or(|l| hunk.seconds > l.seconds) {
+                    last = Some(hunk);
+                }
+            }
+
+            let mut authors: Vec<LineShare> = lines
+                .into_iter()
+                .map(|(author, lines)| LineShare {
+                    author: author.to_string(),
+                    lines,
+                })
+                .collect();
+            authors.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.author.cmp(&b.author)));
+
+            SymbolOwnership {
+                name: sym.name.clone(),
+                kind: sym.kind.as_str().to_string(),
+                start_line: sym.start_line,
+                end_line: sym.end_line,
+                last_touched: last.map(|h| LastTouch {
+                    commit: h.commit.clone(),
+                    author: h.author.clone(),
+                    date: h.commit.as_ref().and_then(|_| {
+                        chrono::DateTime::from_timestamp(h.seconds, 0)
+                            .map(|t| t.format("%Y-%m-%d").to_string())
+                    }),
+                }),
+                authors,
+            }
+        })
+        .collect())
+}
+
+/// Blame HEAD, then overlay the working-tree content so line numbers match
+/// the stored symbols; lines not committed yet have no commit
+fn blame_hunks(project_root: &Path, path: &str) -> Result<Vec<Hunk>> {
+    let (repo, prefix) = super::open_repository(project_root)?;
+    let committed = repo
+        .blame_file(Path::new(&format!("{prefix}{path}")), None)
+        .with_context(|| format!("Failed to blame {}", path))?;
+    let content = std::fs::read(project_root.join(path))
+        .with_context(|| format!("Failed to read {}", path))?;
+    let blame = committed.blame_buffer(&content)?;
+
+    // Hunks split by the buffer overlay can lack a signature, so authors come
+    // from the commits themselves
+    let mut authors: HashMap<Oid, (String, i64)> = HashMap::new();
+    Ok(blame
+        .iter()
+        .map(|hunk| {
+            let start = hunk.final_start_line() as i64;
+            let end = start + hunk.lines_in_hunk() as i64 - 1;
+            let oid = hunk.final_commit_id();
+            if oid.is_zero() {
+                // Uncommitted lines are the most recent change
+                return Hunk {
+                    start,
+                    end,
+                    commit: None,
+                    author: "(uncommitted)".to_string(),
+                    seconds: i64::MAX,
+                };
+            }
+            let (author, seconds) = authors
+                .entry(oid)
+                .or_insert_with(|| match repo.find_commit(oid) {
+                    Ok(commit) => {
+                        let author = commit.author();
+                        let name = author.name().unwrap_or("unknown").to_string();
+                        (name, author.when().seconds())
+                    }
+                    Err(_) => ("unknown".to_string(), 0),
+                })
+                .clone();
+            Hunk {
+                start,
+                end,
+                commit: Some(oid.to_string()),
+                author,
+                seconds,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::SymbolKind;
+    use crate::git::testing::TestRepo;
+
+    fn function(name: &str, start_line: i64, end_line: i64) -> Symbol {
+        Symbol {
+            id: 0,
+            file_id: 0,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            start_line,
+            end_line,
+            signature: String::new(),
+            parent_symbol_id: None,
+        }
+    }
+
+    fn shares(ownership: &SymbolOwnership) -> Vec<(&str, usize)> {
+        ownership
+            .authors
+            .iter()
+            .map(|a| (a.author.as_str(), a.lines))
+            .collect()
+    }
+
+    #[test]
+    fn attributes_symbol_lines_to_authors() {
+        let repo = TestRepo::new("blame-owners");
+        repo.commit(
+            "ann",
+            "add lib",
+            &[(
+                "lib.rs",
+                Some("fn one() {\n    1\n}\n\nfn two() {\n    2\n}\n"),
+            )],
+        );
+        let bob = repo.commit(
+            "bob",
+            "rework two",
+            &[(
+                "lib.rs",
+                Some("fn one() {\n    1\n}\n\nfn two() {\n    20\n    + 2\n}\n"),
+            )],
+        );
+        // Uncommitted edit inside `one`
+        std::fs::write(
+            repo.root.join("lib.rs"),
+            "fn one() {\n    10\n}\n\nfn two() {\n    20\n    + 2\n}\n",
+        )
+        .unwrap();
+
+        let owners = symbol_ownership(
+            &repo.root,
+            "lib.rs",
+            &[function("one", 1, 3), function("two", 5, 8)],
+        )
+        .unwrap();
+
+        assert_eq!(shares(&owners[0]), [("ann", 2), ("(uncommitted)", 1)]);
+        let one = owners[0].last_touched.as_ref().unwrap();
+        assert_eq!((one.author.as_str(), &one.commit), ("(uncommitted)", &None));
+
+        assert_eq!(shares(&owners[1]), [("ann", 2), ("bob", 2)]);
+        let two = owners[1].last_touched.as_ref().unwrap();
+        assert_eq!(two.author, "bob");
+        assert_eq!(two.commit, Some(bob.to_string()));
+        assert!(two.date.is_some());
+    }
+
+    #[test]
+    fn blames_files_of_a_subdirectory_project() {
+        let repo = TestRepo::new("blame-subdir");
+        repo.commit(
+            "ann",
+            "add app",
+            &[("app/lib.rs", Some("fn one() {\n    1\n}\n"))],
+        );
+        repo.commit(
+            "bob",
+            "add tool",
+            &[("tool/lib.rs", Some("fn other() {}\n"))],
+        );
+
+        let owners =
+            symbol_ownership(&repo.root.join("app"), "lib.rs", &[function("one", 1, 3)]).unwrap();
+        assert_eq!(shares(&owners[0]), [("ann", 3)]);
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{bail, Context, Result};
+use git2::{Diff, DiffOptions, Repository};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Which changes to diff
+#[derive(Debug, Clone)]
+pub enum DiffSpec {
+    /// Staged + unstaged + untracked changes against HEAD
+    WorkingTree,
+    /// Staged changes against HEAD
+    Staged,
+    /// Everything since a revision, including uncommitted changes
+    Since(String),
+    /// Between two revisions (`a..b`)
+    Range(String, String),
+    /// What `b` changed since it forked from `a` (`a...b`): from their merge
+    /// base to `b`, like `git diff a...b`
+    MergeBase(String, String),
+}
+
+impl DiffSpec {
+    /// Parse a revision argument: `a..b` and `a...b` are ranges, anything else
+    /// is `--since`
+    pub fn from_rev(rev: &str) -> Self {
+        if let Some((from, to)) = rev.split_once("...") {
+            return Self::MergeBase(from.to_string(), to.to_string());
+        }
+        match rev.split_once("..") {
+            Some((from, to)) => Self::Range(from.to_string(), to.to_string()),
+            None => Self::Since(rev.to_string()),
+        }
+    }
+}
+
+/// A file touched by a diff, with changed line ranges on the new side
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    pub path: String,
+    /// "added", "modified", "deleted", "renamed", "untracked", ...
+    pub status: String,
+    /// Inclusive 1-indexed `(start, end)` line ranges
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Compute changed files and line ranges for a diff spec.
+///
+/// The project may be a subdirectory of the repository: only changes below it
+/// are reported, with project-relative paths like the index uses.
+pub fn changed_ranges(project_root: &Path, spec: &DiffSpec) -> Result<Vec<ChangedFile>> {
+    let (repo, prefix) = super::open_repository(project_root)?;
+
+    let mut opts = DiffOptions::new();
+    opts.context_lines(0);
+    if !prefix.is_empty() {
+        opts.pathspec(&prefix);
+    }
+
+    let diff = match spec {
+        DiffSpec::WorkingTree => {
+            opts.include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .show_untracked_content(true);
+            let head = head_tree(&repo)?;
+            repo.diff_tree_to_workdir_with_index(head.as_ref(), Some(&mut opts))?
+        }
+        DiffSpec::Staged => {
+            let head = head_tree(&repo)?;
+            repo.diff_tree_to_index(head.as_ref(), None, Some(&mut opts))?
+        }
+        DiffSpec::Since(rev) => {
+            let tree = rev_tree(&repo, rev)?;
+            repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut opts))?
+        }
+        DiffSpec::Range(from, to) => {
+            let from_tree = rev_tree(&repo, from)?;
+            let to_tree = rev_tree(&repo, to)?;
+            repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut opts))?
+        }
+        DiffSpec::MergeBase(from, to) => {
+            let from_commit = rev_commit(&repo, from)?;
+            let to_commit = rev_commit(&repo, to)?;
+            let base = repo
+                .merge_base(from_commit.id(), to_commit.id())
+                .with_context(|| format!("No merge base between '{}' and '{}'", from, to))?;
+            let base_tree = repo.find_commit(base)?.tree()?;
+            repo.diff_tree_to_tree(Some(&base_tree), Some(&to_commit.tree()?), Some(&mut opts))?
+        }
+    };
+
+    let mut files = collect_changes(&diff)?;
+    if !prefix.is_empty() {
+        files.retain_mut(|file| match file.path.strip_prefix(&prefix) {
+            Some(relative) => {
+                file.path = relative.to_string();
+                true
+            }
+            None => false,
+        });
+    }
+    Ok(files)
+}
+
+fn collect_changes(diff: &Diff) -> Result<Vec<ChangedFile>> {
+    let files: RefCell<BTreeMap<String, ChangedFile>> = RefCell::new(BTreeMap::new());
+
+    let delta_path = |delta: &git2::DiffDelta| {
+        delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+    };
+
+    diff.foreach(
+        &mut |delta, _| {
+            if let Some(path) = delta_path(&delta) {
+                let status = format!("{:?}", delta.status()).to_lowercase();
+                files
+                    .borrow_mut()
+                    .entry(path.clone())
+                    .or_insert(ChangedFile {
+                        path,
+                        status,
+                        ranges: Vec::new(),
+                    });
+            }
+            true
+        },
+        None,
+        Some(&mut |delta, hunk| {
+            if let Some(path) = delta_path(&delta) {
+                // Pure deletions have no new-side lines; anchor them at the insertion point
+                let start = hunk.new_start().max(1) as usize;
+                let end = start + (hunk.new_lines() as usize).saturating_sub(1);
+                if let Some(file) = files.borrow_mut().get_mut(&path) {
+                    file.ranges.push((start, end));
+                }
+            }
+            true
+        }),
+        None,
+    )?;
+
+    Ok(files.into_inner().into_values().collect())
+}
+
+fn head_tree(repo: &Repository) -> Result<Option<git2::Tree<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_tree()?)),
+        // Unborn branch: everything is new
+        Err(_) => Ok(None),
+    }
+}
+
+fn rev_tree<'r>(repo: &'r Repository, rev: &str) -> Result<git2::Tree<'r>> {
+    let rev = if rev.is_empty() { "HEAD" } else { rev };
+    match repo.revparse_single(rev) {
+        Ok(obj) => Ok(obj.peel_to_tree()?),
+        Err(e) => bail!("Unknown revision '{}': {}", rev, e.message()),
+    }
+}
+
+fn rev_commit<'r>(repo: &'r Repository, rev: &str) -> Result<git2::Commit<'r>> {
+    let rev = if rev.is_empty() { "HEAD" } else { rev };
+    match repo.revparse_single(rev) {
+        Ok(obj) => Ok(obj.peel_to_commit()?),
+        Err(e) => bail!("Unknown revision '{}': {}", rev, e.message()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_rev_distinguishes_ranges() {
+        assert!(matches!(
+            DiffSpec::from_rev("main..HEAD"),
+            DiffSpec::Range(from, to) if from == "main" && to == "HEAD"
+        ));
+        assert!(matches!(DiffSpec::from_rev("v1.0"), DiffSpec::Since(rev) if rev == "v1.0"));
+        assert!(matches!(
+            DiffSpec::from_rev("main...feature"),
+            DiffSpec::MergeBase(from, to) if from == "main" && to == "feature"
+        ));
+    }
+
+    #[test]
+    fn changed_ranges_in_subdirectory_and_since_fork() {
+        let root = std::env::temp_dir().join(format!("ctx-diff-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("app/src")).unwrap();
+        let repo = Repository::init(&root).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let commit = |files: &[(&str, &str)], parents: &[&git2::Commit]| {
+            for (path, content) in files {
+                std::fs::write(root.join(path), content).unwrap();
+            }
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let id = repo
+                .commit(None, &sig, &sig, "change", &tree, parents)
+                .unwrap();
+            repo.find_commit(id).unwrap()
+        };
+
+        let base = commit(&[("app/src/a.rs", "a\n"), ("README.md", "x\n")], &[]);
+        let main = commit(&[("app/src/a.rs", "a\nmain\n")], &[&base]);
+        repo.branch("main-line", &main, true).unwrap();
+        let feature = commit(
+            &[("app/src/a.rs", "a\nfeature\n"), ("README.md", "y\n")],
+            &[&base],
+        );
+        repo.branch("feature", &feature, true).unwrap();
+
+        // Only the fork's changes, relative to the project in app/
+        let files = changed_ranges(
+            &root.join("app"),
+            &DiffSpec::from_rev("main-line...feature"),
+        )
+        .unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert_eq!(files[0].ranges, vec![(2, 2)]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,107 +1,214 @@
 use anyhow::{Context, Result};
-use git2::{Repository, Sort};
-use std::collections::{HashMap, HashSet};
+use git2::{Delta, DiffFindOptions, DiffOptions, Oid, Patch, Repository, Sort};
+use std::collections::HashSet;
 use std::path::Path;
 
+use crate::db::models::CommitFileChange;
 use crate::db::Database;
 
-/// Stats accumulated per file from git history
-#[derive(Debug, Default)]
-struct GitFileStats {
-    commit_count: i64,
-    last_modified: Option<String>,
-    contributors: HashSet<String>,
-}
+/// Meta key holding the HEAD commit history was last mined up to
+const HEAD_KEY: &str = "git_head";
+/// Meta key holding the `git.max_commits` window the stored history was built with
+const WINDOW_KEY: &str = "git_max_commits";
+/// Meta key holding the coupling thresholds the `cochange` table was built with
+const COUPLING_KEY: &str = "cochange_settings";
 
-/// Analyze git history and populate file_stats + decisions
+/// Analyze git history and populate file_stats + decisions.
+///
+/// Only commits made since the last run are walked; they are merged into the
+/// stored history, which is trimmed back to the `git.max_commits` most recent
+/// commits. If HEAD no longer descends from the last analyzed commit (rebase,
+/// force-push, branch switch) or the window changed, history is rebuilt.
+/// Renames are followed, so stats and decision files stay with a moved file.
+/// Co-change coupling between files is derived from the same commits.
+///
+/// When the project is a subdirectory of the repository, only commits
+/// touching it count, with project-relative paths.
 pub fn analyze_git_history(db: &Database, project_root: &Path) -> Result<GitAnalysisResult> {
-    let repo = match Repository::open(project_root) {
+    let (repo, prefix) = match super::open_repository(project_root) {
         Ok(r) => r,
         Err(_) => {
             return Ok(GitAnalysisResult {
                 commits_analyzed: 0,
                 files_with_stats: 0,
                 decisions_found: 0,
+                rebuilt: false,
                 error: Some("Not a git repository".to_string()),
             });
         }
     };
 
-    let mut revwalk = repo.revwalk()?;
-    revwalk.set_sorting(Sort::TIME)?;
-    revwalk
-        .push_head()
-        .context("Failed to push HEAD to revwalk")?;
-
-    let mut file_stats: HashMap<String, GitFileStats> = HashMap::new();
-    let mut decisions_found = 0;
-    let mut commits_analyzed = 0;
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .context("Failed to resolve HEAD")?
+        .id();
+    let max_commits = db.config.git.max_commits;
 
-    // Limit to last 1000 commits for performance
-    let max_commits = 1000;
-
-    for oid_result in revwalk {
-        if commits_analyzed >= max_commits {
-            break;
-        }
+    db.transaction(|| {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(head)?;
 
-        let oid = match oid_result {
-            Ok(o) => o,
-            Err(_) => continue,
+        let watermark = db
+            .get_meta(HEAD_KEY)?
+            .and_then(|hash| Oid::from_str(&hash).ok());
+        let same_window = db.get_meta(WINDOW_KEY)? == Some(max_commits.to_string());
+        let rebuilt = match watermark {
+            Some(last) if same_window && is_ancestor(&repo, last, head) => {
+                revwalk.hide(last)?;
+                false
+            }
+            _ => {
+                db.clear_git_history()?;
+                true
+            }
         };
 
-        let commit = match repo.find_commit(oid) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
+        // Limit to the most recent commits for performance, then record them
+        // oldest first so commit ids follow history order
+        let commits: Vec<(git2::Commit, Vec<CommitFileChange>)> = revwalk
+            .filter_map(|o| o.ok())
+            .filter_map(|oid| repo.find_commit(oid).ok())
+            .map(|commit| {
+                let changes = changed_files(&repo, &commit, &prefix);
+                (commit, changes)
+            })
+            .filter(|(_, changes)| prefix.is_empty() || !changes.is_empty())
+            .take(max_commits)
+            .collect();
 
-        commits_analyzed += 1;
+        let mut decisions_found = 0;
+        let mut commits_analyzed = 0;
+        // Files whose stats the new commits (and pruning) change
+        let mut touched: HashSet<String> = HashSet::new();
+        for (commit, changes) in commits.into_iter().rev() {
+            let oid = commit.id();
+            commits_analyzed += 1;
 
-        let author = commit.author().name().unwrap_or("unknown").to_string();
-        let time = commit.time();
-        let timestamp = chrono::DateTime::from_timestamp(time.seconds(), 0)
-            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+            let author = commit.author().name().unwrap_or("unknown").to_string();
+            let time = commit.time();
+            let timestamp = chrono::DateTime::from_timestamp(time.seconds(), 0)
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
 
-        let message = commit.message().unwrap_or("").to_string();
+            let message = commit.message().unwrap_or("").to_string();
+            for change in &changes {
+                if let (Some(from), "renamed") = (&change.from_path, change.status.as_str()) {
+                    db.follow_rename(from, &change.path)?;
+                }
+                touched.extend(change.from_path.iter().cloned());
+                touched.insert(change.path.clone());
+            }
+            db.insert_commit(
+                &oid.to_string(),
+                &author,
+                timestamp.as_deref(),
+                commit.summary().unwrap_or(""),
+                &changes,
+            )?;
+
+            if is_decision(&message) {
+                let changed_files: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+                let related =
+                    serde_json::to_string(&changed_files).unwrap_or_else(|_| "[]".to_string());
+                if db
+                    .insert_decision(message.trim(), "commit", Some(&oid.to_string()), &related)
+                    .unwrap_or(false)
+                {
+                    decisions_found += 1;
+                }
+            }
+        }
 
-        // Get changed files by diffing with parent
-        let tree = match commit.tree() {
-            Ok(t) => t,
-            Err(_) => continue,
+        let pruned = db.prune_git_history(max_commits)?;
+        let files_with_stats = if rebuilt {
+            db.refresh_file_stats()?
+        } else {
+            touched.extend(pruned.iter().cloned());
+            db.refresh_file_stats_for(&touched.into_iter().collect::<Vec<_>>())?
         };
 
-        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        // Co-change pairs only depend on the stored commits and thresholds
+        let coupling = &db.config.coupling;
+        let settings = format!("{}:{}", coupling.max_commit_files, coupling.min_support);
+        if rebuilt
+            || commits_analyzed > 0
+            || !pruned.is_empty()
+            || db.get_meta(COUPLING_KEY)?.as_deref() != Some(settings.as_str())
+        {
+            db.refresh_cochange(coupling.max_commit_files, coupling.min_support)?;
+            db.set_meta(COUPLING_KEY, &settings)?;
+        }
+        db.set_meta(HEAD_KEY, &head.to_string())?;
+        db.set_meta(WINDOW_KEY, &max_commits.to_string())?;
 
-        let diff = match repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None) {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
+        Ok(GitAnalysisResult {
+            commits_analyzed,
+            files_with_stats,
+            decisions_found,
+            rebuilt,
+            error: None,
+        })
+    })
+}
 
-        let mut changed_files = Vec::new();
-        diff.foreach(
-            &mut |delta, _| {
-                if let Some(path) = delta.new_file().path() {
-                    let path_str = path.to_string_lossy().to_string();
-                    changed_files.push(path_str.clone());
-
-                    let stats = file_stats.entry(path_str).or_default();
-                    stats.commit_count += 1;
-                    stats.contributors.insert(author.clone());
-                    if stats.last_modified.is_none() {
-                        stats.last_modified = timestamp.clone();
-                    }
-                }
-                true
-            },
-            None,
-            None,
-            None,
-        )
+/// Whether `ancestor` is `head` or reachable from it
+fn is_ancestor(repo: &Repository, ancestor: Oid, head: Oid) -> bool {
+    ancestor == head || repo.graph_descendant_of(head, ancestor).unwrap_or(false)
+}
+
+/// Files changed by a commit below `prefix` with their line counts, diffing
+/// against its first parent with rename and copy detection
+fn changed_files(repo: &Repository, commit: &git2::Commit, prefix: &str) -> Vec<CommitFileChange> {
+    let Ok(tree) = commit.tree() else {
+        return Vec::new();
+    };
+    let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+    let mut opts = DiffOptions::new();
+    if !prefix.is_empty() {
+        opts.pathspec(prefix);
+    }
+    let Ok(mut diff) = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+    else {
+        return Vec::new();
+    };
+    diff.find_similar(Some(DiffFindOptions::new().renames(true).copies(true)))
         .ok();
 
-        // Detect decisions from commit messages
-        // Conventional commits with "feat:", "fix:", "refactor:", "breaking:" etc.
-        let is_decision = message.starts_with("feat:")
+    let path_of = |file: git2::DiffFile| {
+        let path = file.path()?.to_string_lossy();
+        path.strip_prefix(prefix).map(str::to_string)
+    };
+    diff.deltas()
+        .enumerate()
+        .filter_map(|(idx, delta)| {
+            let path = path_of(delta.new_file())?;
+            let from_path = match delta.status() {
+                Delta::Renamed | Delta::Copied => path_of(delta.old_file()),
+                _ => None,
+            };
+            // Binary files have no line stats
+            let (_, additions, deletions) = Patch::from_diff(&diff, idx)
+                .ok()
+                .flatten()
+                .and_then(|patch| patch.line_stats().ok())
+                .unwrap_or_default();
+            Some(CommitFileChange {
+                path,
+                from_path,
+                status: format!("{:?}", delta.status()).to_lowercase(),
+                additions: additions as i64,
+                deletions: deletions as i64,
+            })
+        })
+        .collect()
+}
+
+/// Detect decisions from commit messages: conventional commits with "feat:",
+/// "refactor:", breaking changes, migrations and replacements
+fn is_decision(message: &str) -> bool {
+    !message.is_empty()
+        && (message.starts_with("feat:")
             || message.starts_with("feat(")
             || message.starts_with("refactor:")
             || message.starts_with("refactor(")
@@ -109,48 +216,7 @@ pub fn analyze_git_history(db: &Database, project_root: &Path) -> Result<GitAnal
             || message.contains("migration")
             || message.contains("replace")
             || message.contains("switch to")
-            || message.contains("switch from");
-
-        if is_decision && !message.is_empty() {
-            let related =
-                serde_json::to_string(&changed_files).unwrap_or_else(|_| "[]".to_string());
-            if db
-                .insert_decision(message.trim(), "commit", Some(&oid.to_string()), &related)
-                .unwrap_or(false)
-            {
-                decisions_found += 1;
-            }
-        }
-    }
-
-    // Store file stats
-    let mut files_with_stats = 0;
-    let max_commit_count = file_stats
-        .values()
-        .map(|s| s.commit_count)
-        .max()
-        .unwrap_or(1) as f64;
-
-    for (path, stats) in &file_stats {
-        if let Ok(Some(file_id)) = db.get_file_id(path) {
-            let churn_score = stats.commit_count as f64 / max_commit_count;
-            db.upsert_file_stats(
-                file_id,
-                stats.commit_count,
-                stats.last_modified.as_deref(),
-                churn_score,
-                stats.contributors.len() as i64,
-            )?;
-            files_with_stats += 1;
-        }
-    }
-
-    Ok(GitAnalysisResult {
-        commits_analyzed,
-        files_with_stats,
-        decisions_found,
-        error: None,
-    })
+            || message.contains("switch from"))
 }
 
 /// Result of git history analysis
@@ -159,5 +225,135 @@ pub struct GitAnalysisResult {
     pub commits_analyzed: usize,
     pub files_with_stats: usize,
     pub decisions_found: usize,
+    /// Stored history was discarded and re-walked
+    pub rebuilt: bool,
     pub error: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::testing::TestRepo;
+
+    /// Commit count and churn score of each indexed file
+    fn stats(db: &Database) -> Vec<(String, i64, f64)> {
+        let mut stats: Vec<_> = db
+            .get_file_health()
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.path, f.commit_count, f.churn_score))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    fn history(db: &Database, path: &str) -> Vec<String> {
+        db.get_file_history(path)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.summary)
+            .collect()
+    }
+
+    #[test]
+    fn walks_only_commits_since_the_watermark() {
+        let repo = TestRepo::new("history-incremental");
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_file("a.rs", "rust", 0, "", 1).unwrap();
+        db.upsert_file("b.rs", "rust", 0, "", 1).unwrap();
+        repo.commit("ann", "add a", &[("a.rs", Some("1"))]);
+        repo.commit("ann", "edit a", &[("a.rs", Some("2"))]);
+
+        let first = analyze_git_history(&db, &repo.root).unwrap();
+        assert!(first.rebuilt);
+        assert_eq!(first.commits_analyzed, 2);
+        assert_eq!(
+            stats(&db),
+            [("a.rs".into(), 2, 1.0), ("b.rs".into(), 0, 0.0)]
+        );
+
+        let unchanged = analyze_git_history(&db, &repo.root).unwrap();
+        assert!(!unchanged.rebuilt);
+        assert_eq!(unchanged.commits_analyzed, 0);
+
+        repo.commit("bob", "add b", &[("b.rs", Some("1"))]);
+        let next = analyze_git_history(&db, &repo.root).unwrap();
+        assert!(!next.rebuilt);
+        assert_eq!(next.commits_analyzed, 1);
+        assert_eq!(history(&db, "a.rs"), ["edit a", "add a"]);
+        // Only b.rs was touched, but its stats rescale against a.rs
+        assert_eq!(
+            stats(&db),
+            [("a.rs".into(), 2, 1.0), ("b.rs".into(), 1, 0.5)]
+        );
+    }
+
+    #[test]
+    fn rewritten_history_is_rebuilt() {
+        let repo = TestRepo::new("history-rewrite");
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_file("a.rs", "rust", 0, "", 1).unwrap();
+        let base = repo.commit("ann", "add a", &[("a.rs", Some("1"))]);
+        repo.commit("ann", "wip", &[("a.rs", Some("2"))]);
+        analyze_git_history(&db, &repo.root).unwrap();
+
+        // Amend the last commit: the watermark is no longer an ancestor of HEAD
+        repo.reset(base);
+        repo.commit("ann", "edit a", &[("a.rs", Some("3"))]);
+        let result = analyze_git_history(&db, &repo.root).unwrap();
+        assert!(result.rebuilt);
+        assert_eq!(result.commits_analyzed, 2);
+        assert_eq!(history(&db, "a.rs"), ["edit a", "add a"]);
+    }
+
+    #[test]
+    fn changing_max_commits_invalidates_the_watermark() {
+        let repo = TestRepo::new("history-window");
+        let mut db = Database::open_in_memory().unwrap();
+        db.upsert_file("a.rs", "rust", 0, "", 1).unwrap();
+        for n in 1..=3 {
+            repo.commit(
+                "ann",
+                &format!("edit {n}"),
+                &[("a.rs", Some(&n.to_string()))],
+            );
+        }
+        db.config.git.max_commits = 2;
+        let narrow = analyze_git_history(&db, &repo.root).unwrap();
+        assert_eq!(narrow.commits_analyzed, 2);
+        assert_eq!(history(&db, "a.rs"), ["edit 3", "edit 2"]);
+
+        db.config.git.max_commits = 10;
+        let wide = analyze_git_history(&db, &repo.root).unwrap();
+        assert!(wide.rebuilt);
+        assert_eq!(wide.commits_analyzed, 3);
+        assert_eq!(history(&db, "a.rs"), ["edit 3", "edit 2", "edit 1"]);
+        assert_eq!(stats(&db), [("a.rs".into(), 3, 1.0)]);
+    }
+
+    #[test]
+    fn subdirectory_project_counts_only_its_commits() {
+        let repo = TestRepo::new("history-subdir");
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_file("a.rs", "rust", 0, "", 1).unwrap();
+        db.upsert_file("b.rs", "rust", 0, "", 1).unwrap();
+        repo.commit("ann", "add a", &[("app/a.rs", Some("1"))]);
+        repo.commit("ann", "add tool", &[("tool/x.rs", Some("1"))]);
+        repo.commit(
+            "bob",
+            "edit both",
+            &[("app/a.rs", Some("2")), ("tool/x.rs", Some("2"))],
+        );
+        repo.rename("app/a.rs", "app/b.rs");
+        repo.commit("bob", "rename a", &[]);
+
+        let result = analyze_git_history(&db, &repo.root.join("app")).unwrap();
+        assert_eq!(result.error, None);
+        assert_eq!(result.commits_analyzed, 3);
+        assert_eq!(history(&db, "b.rs"), ["rename a", "edit both", "add a"]);
+        assert_eq!(
+            stats(&db),
+            [("a.rs".into(), 0, 0.0), ("b.rs".into(), 3, 1.0)]
+        );
+    }
+}
@@ -1,3 +1,36 @@
+pub mod blame;
+pub mod diff;
 pub mod history;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub use history::analyze_git_history;
+
+use anyhow::{Context, Result};
+use git2::Repository;
+use std::path::Path;
+
+/// Open the repository holding `project_root`, which may be a subdirectory of
+/// its work tree, together with the project's path inside the work tree: a
+/// prefix with a trailing `/`, or "" when the project is the whole repository.
+///
+/// Git reports repository-relative paths; strip the prefix to get the
+/// project-relative paths the index uses.
+pub fn open_repository(project_root: &Path) -> Result<(Repository, String)> {
+    let repo = Repository::discover(project_root).context("Not a git repository")?;
+    let prefix = project_prefix(&repo, project_root);
+    Ok((repo, prefix))
+}
+
+fn project_prefix(repo: &Repository, project_root: &Path) -> String {
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let Some(workdir) = repo.workdir() else {
+        return String::new();
+    };
+    match canonical(project_root).strip_prefix(canonical(workdir)) {
+        Ok(relative) if !relative.as_os_str().is_empty() => {
+            format!("{}/", relative.to_string_lossy().replace('\\', "/"))
+        }
+        _ => String::new(),
+    }
+}
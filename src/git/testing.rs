@@ -0,0 +1,77 @@
+//! Throwaway git repositories for tests
+
+use git2::{IndexAddOption, Oid, Repository, ResetType, Signature, Time};
+use std::cell::Cell;
+use std::path::PathBuf;
+
+/// A repository in the temp directory, removed on drop
+pub(crate) struct TestRepo {
+    pub root: PathBuf,
+    pub repo: Repository,
+    /// Commit time of the next commit: a minute apart, starting a day ago
+    clock: Cell<i64>,
+}
+
+impl TestRepo {
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("ctx-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let repo = Repository::init(&root).unwrap();
+        let clock = Cell::new(chrono::Utc::now().timestamp() - 86_400);
+        Self { root, repo, clock }
+    }
+
+    /// Write (`Some`) or delete (`None`) files, stage everything and commit
+    /// on top of HEAD
+    pub fn commit(&self, author: &str, message: &str, files: &[(&str, Option<&str>)]) -> Oid {
+        for (path, content) in files {
+            let path = self.root.join(path);
+            match content {
+                Some(content) => {
+                    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    std::fs::write(path, content).unwrap();
+                }
+                None => std::fs::remove_file(path).unwrap(),
+            }
+        }
+
+        let mut index = self.repo.index().unwrap();
+        index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let time = Time::new(self.clock.replace(self.clock.get() + 60), 0);
+        let signature = Signature::new(author, &format!("{author}@example.com"), &time).unwrap();
+        self.repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parent.iter().collect::<Vec<_>>(),
+            )
+            .unwrap()
+    }
+
+    /// Move a file in the work tree; the next `commit` records the rename
+    pub fn rename(&self, from: &str, to: &str) {
+        let to = self.root.join(to);
+        std::fs::create_dir_all(to.parent().unwrap()).unwrap();
+        std::fs::rename(self.root.join(from), to).unwrap();
+    }
+
+    /// Point HEAD (and the work tree) at `commit`, dropping later commits
+    pub fn reset(&self, commit: Oid) {
+        let object = self.repo.find_object(commit, None).unwrap();
+        self.repo.reset(&object, ResetType::Hard, None).unwrap();
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
@@ -1,10 +1,13 @@
 use crate::analyzer::graph;
+use crate::db::models::Symbol;
 use crate::db::Database;
 use anyhow::Result;
 use colored::*;
+use std::collections::HashSet;
 
-/// Execute blast-radius analysis and display results
-pub fn execute_blast_radius(db: &Database, file_path: &str) -> Result<()> {
+/// Execute blast-radius analysis and display results, optionally adding
+/// files coupled through git history
+pub fn execute_blast_radius(db: &Database, file_path: &str, include_coupled: bool) -> Result<()> {
     let file_id = match db.get_file_id(file_path)? {
         Some(id) => id,
         None => {
@@ -51,6 +54,12 @@ pub fn execute_blast_radius(db: &Database, file_path: &str) -> Result<()> {
 
     // Show transitive blast radius
     let radius = graph::blast_radius(db, file_id)?;
+    let historical = if include_coupled {
+        graph::historical_blast_radius(db, file_path, &radius)?
+    } else {
+        Vec::new()
+    };
+
     if !radius.is_empty() {
         let max_depth = radius.iter().map(|r| r.2).max().unwrap_or(0);
         println!(
@@ -65,18 +74,35 @@ pub fn execute_blast_radius(db: &Database, file_path: &str) -> Result<()> {
             println!("    {}{} {}", indent, marker.dimmed(), path);
         }
         println!();
+    }
+
+    // Files that change together without an import path between them
+    if !historical.is_empty() {
+        println!(
+            "  {} {} files historically change with this one:",
+            "HISTORY".magenta(),
+            historical.len().to_string().magenta().bold()
+        );
+        for coupled in &historical {
+            println!(
+                "    {} {} {}",
+                "~".dimmed(),
+                coupled.path,
+                format!(
+                    "({:.0}% of commits, {} shared)",
+                    coupled.confidence * 100.0,
+                    coupled.support
+                )
+                .dimmed()
+            );
+        }
+        println!();
+    }
 
+    if !radius.is_empty() || !historical.is_empty() {
         // Risk assessment
-        let risk = if radius.len() > 20 {
-            "CRITICAL".red().bold()
-        } else if radius.len() > 10 {
-            "HIGH".red()
-        } else if radius.len() > 5 {
-            "MEDIUM".yellow()
-        } else {
-            "LOW".green()
-        };
-        println!("  Risk: {}", risk);
+        let affected = radius.len() + historical.len();
+        println!("  Risk: {}", risk_label(db.config.risk.level(affected)));
     } else if dependents.is_empty() {
         println!(
             "  {} No files depend on this file (leaf node)",
@@ -86,3 +112,121 @@ pub fn execute_blast_radius(db: &Database, file_path: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Look up the symbols named by a blast-radius target: `name` or `path::name`
+pub fn find_symbol_targets(db: &Database, target: &str) -> Result<Vec<(Symbol, String)>> {
+    if let Some((path, name)) = target.rsplit_once("::") {
+        if db.get_file_id(path)?.is_some() {
+            return db.find_symbols(name, Some(path));
+        }
+    }
+    db.find_symbols(target, None)
+}
+
+/// Execute symbol-level blast-radius analysis and display results
+pub fn execute_symbol_blast_radius(
+    db: &Database,
+    target: &str,
+    max_depth: Option<usize>,
+) -> Result<()> {
+    let definitions = find_symbol_targets(db, target)?;
+    if definitions.is_empty() {
+        println!("  {} Symbol not found: {}", "ERROR".red(), target);
+        return Ok(());
+    }
+
+    println!(
+        "\n  {} {}\n",
+        "Blast Radius:".yellow().bold(),
+        target.white().bold()
+    );
+
+    println!(
+        "  {} {} definition(s):",
+        "•".blue(),
+        definitions.len().to_string().cyan()
+    );
+    for (sym, path) in &definitions {
+        println!(
+            "    {} {}:{} {}",
+            sym.kind.icon().dimmed(),
+            path,
+            sym.start_line,
+            sym.signature.dimmed()
+        );
+    }
+    println!();
+
+    let symbols: Vec<Symbol> = definitions.into_iter().map(|(s, _)| s).collect();
+    let impacts = graph::symbol_blast_radius(db, &symbols, max_depth)?;
+    if impacts.is_empty() {
+        println!("  {} Nothing calls or imports this symbol", "OK".green());
+        return Ok(());
+    }
+
+    let files: HashSet<&str> = impacts.iter().map(|i| i.path.as_str()).collect();
+    let max_depth = impacts.iter().map(|i| i.depth).max().unwrap_or(0);
+    println!(
+        "  {} {} symbols/sites in {} files (depth {}):",
+        "IMPACT".red(),
+        impacts.len().to_string().red().bold(),
+        files.len().to_string().red().bold(),
+        max_depth.to_string().yellow()
+    );
+    for impact in &impacts {
+        let indent = "  ".repeat(impact.depth);
+        let marker = if impact.depth == 1 { "→" } else { "↳" };
+        let what = match &impact.symbol {
+            Some(name) => format!("{} ({}:{})", name, impact.path, impact.line),
+            None if impact.via == "import" => format!("{} (imports)", impact.path),
+            None => format!("{}:{} (top level)", impact.path, impact.line),
+        };
+        println!("    {}{} {}", indent, marker.dimmed(), what);
+    }
+    println!();
+
+    println!("  Risk: {}", risk_label(db.config.risk.level(files.len())));
+
+    Ok(())
+}
+
+/// Colored label for a risk level
+pub fn risk_label(level: &str) -> ColoredString {
+    match level {
+        "critical" => "CRITICAL".red().bold(),
+        "high" => "HIGH".red(),
+        "medium" => "MEDIUM".yellow(),
+        _ => "LOW".green(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::SymbolKind;
+
+    #[test]
+    fn symbol_targets_by_name_or_path() {
+        let db = Database::open_in_memory().unwrap();
+        let file = |path: &str| db.upsert_file(path, "rust", 0, "", 10).unwrap();
+        let db_mod = file("src/db/mod.rs");
+        let cache = file("src/cache.rs");
+        for file_id in [db_mod, cache] {
+            db.insert_symbol(file_id, "open", &SymbolKind::Function, 1, 3, "", None)
+                .unwrap();
+        }
+        let paths = |target: &str| -> Vec<String> {
+            find_symbol_targets(&db, target)
+                .unwrap()
+                .into_iter()
+                .map(|(symbol, path)| format!("{}::{}", path, symbol.name))
+                .collect()
+        };
+
+        assert_eq!(paths("open"), ["src/cache.rs::open", "src/db/mod.rs::open"]);
+        assert_eq!(paths("src/db/mod.rs::open"), ["src/db/mod.rs::open"]);
+        assert!(paths("src/cache.rs::close").is_empty());
+        // A prefix that is not an indexed file is part of the name
+        assert!(paths("Database::open").is_empty());
+    }
+}
@@ -17,8 +17,8 @@ pub fn execute_search(db: &Database, query: &str) -> Result<()> {
         query.yellow()
     );
 
-    for (_name, path, kind, signature) in &results {
-        let icon = match kind.as_str() {
+    for result in &results {
+        let icon = match result.kind.as_str() {
             "function" => "ƒ".cyan(),
             "method" => "ƒ".blue(),
             "class" => "C".magenta(),
@@ -28,9 +28,16 @@ pub fn execute_search(db: &Database, query: &str) -> Result<()> {
             "constant" => "K".white(),
             "type_alias" => "T".cyan(),
             "module" => "M".blue(),
+            "component" => "◇".magenta(),
+            "hook" => "ƒ".magenta(),
             _ => "?".dimmed(),
         };
-        println!("  {} {} {}", icon, signature.white().bold(), path.dimmed());
+        println!(
+            "  {} {} {}",
+            icon,
+            result.signature.white().bold(),
+            result.path.dimmed()
+        );
     }
 
     Ok(())
@@ -1,16 +1,25 @@
 use anyhow::Result;
 use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::mpsc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::analyzer;
+use crate::analyzer::modules::ProjectModules;
+use crate::analyzer::{self, scanner};
 use crate::db::Database;
 
-/// Start watching for file changes and re-analyze incrementally
+/// How long changes must be quiet before they are re-analyzed
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Start watching for file changes and re-analyze incrementally.
+///
+/// Saved `--include`/`--exclude` globs apply, as for every database open.
 pub fn watch_project(project_root: &Path) -> Result<()> {
+    let project_root =
+        &std::fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
     let (tx, rx) = mpsc::channel();
 
     let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
@@ -22,52 +31,106 @@ pub fn watch_project(project_root: &Path) -> Result<()> {
     // Watch the project root (excluding .ctx and .git)
     watcher.watch(project_root, RecursiveMode::Recursive)?;
 
+    // Catch up on anything that changed while no watcher was running
+    let db = Database::open(project_root)?;
+    let filter = scanner::PathFilter::new(project_root, &db.config.scan)?;
+    let manifest_filter = ProjectModules::manifest_filter(project_root, &db.config.scan)?;
+    analyzer::analyze_project(&db, project_root)?;
+
     println!("  Watching for changes... (Ctrl+C to stop)");
 
-    let db = Database::open(project_root)?;
-    let mut debounce_timer = std::time::Instant::now();
+    let mut pending: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut ignore_rules_changed = false;
+    // Import maps from tsconfig/package.json/go.mod, loaded on first use
+    let mut modules = None;
+    // When the last event that needs re-analysis arrived; events for ignored
+    // paths (a build writing to target/) must not hold back the debounce
+    let mut last_change: Option<Instant> = None;
 
     loop {
-        match rx.recv_timeout(Duration::from_millis(500)) {
+        let timeout = last_change.map_or(DEBOUNCE, |at| DEBOUNCE.saturating_sub(at.elapsed()));
+        match rx.recv_timeout(timeout) {
             Ok(event) => {
-                // Skip events in .ctx, .git, target directories
-                let dominated_by_ignored = event.paths.iter().all(|p| {
-                    let path_str = p.to_string_lossy();
-                    path_str.contains("/.ctx/")
-                        || path_str.contains("/.git/")
-                        || path_str.contains("/target/")
-                        || path_str.contains("/node_modules/")
-                });
-
-                if dominated_by_ignored {
+                if !matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) {
                     continue;
                 }
 
-                match event.kind {
-                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                        // Debounce: wait at least 1 second between re-analyses
-                        if debounce_timer.elapsed() > Duration::from_secs(1) {
-                            println!("  Change detected, re-analyzing...");
-                            match analyzer::analyze_project(&db, project_root) {
-                                Ok(result) => {
-                                    println!(
-                                        "  OK  Updated: {} files, {} symbols",
-                                        result.analyzed_files, result.total_symbols
-                                    );
-                                }
-                                Err(e) => {
-                                    eprintln!("  ERROR  Analysis error: {}", e);
-                                }
-                            }
-                            debounce_timer = std::time::Instant::now();
-                        }
-                    }
-                    _ => {}
+                // Edited ignore files can change what belongs in the index anywhere
+                let ignore_file_changed = event.paths.iter().any(|p| {
+                    p.file_name()
+                        .is_some_and(|n| n == scanner::CTXIGNORE_FILE || n == ".gitignore")
+                });
+
+                // Skip events for paths the scanner would not index (.git, target, ...),
+                // except config files outside the include globs that map imports
+                let accepted: Vec<PathBuf> = event
+                    .paths
+                    .into_iter()
+                    .filter(|p| {
+                        p.strip_prefix(project_root).map_or(true, |relative| {
+                            let is_dir = !p.is_file();
+                            !filter.is_excluded(relative, is_dir)
+                                || (ProjectModules::is_manifest(p)
+                                    && !manifest_filter.is_excluded(relative, is_dir))
+                        })
+                    })
+                    .collect();
+
+                if ignore_file_changed || !accepted.is_empty() {
+                    ignore_rules_changed |= ignore_file_changed;
+                    pending.extend(accepted);
+                    last_change = Some(Instant::now());
                 }
             }
-            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         }
+
+        // Debounce: re-analyze once relevant events have been quiet for a moment
+        if last_change.is_none_or(|at| at.elapsed() < DEBOUNCE) {
+            continue;
+        }
+        last_change = None;
+
+        if ignore_rules_changed {
+            ignore_rules_changed = false;
+            pending.clear();
+            modules = None;
+            println!("  Ignore rules changed, re-scanning...");
+            match analyzer::analyze_project(&db, project_root) {
+                Ok(result) => {
+                    println!(
+                        "  OK  Updated: {} files, {} symbols, {} removed",
+                        result.analyzed_files, result.total_symbols, result.removed_files
+                    );
+                }
+                Err(e) => {
+                    eprintln!("  ERROR  Analysis error: {}, retrying", e);
+                    ignore_rules_changed = true;
+                    last_change = Some(Instant::now());
+                }
+            }
+        } else if !pending.is_empty() {
+            let paths: Vec<PathBuf> = std::mem::take(&mut pending).into_iter().collect();
+            println!("  Change detected, re-analyzing {} path(s)...", paths.len());
+            match analyzer::analyze_paths(&db, project_root, &paths, &mut modules) {
+                Ok(result) => {
+                    println!(
+                        "  OK  Updated: {} files, {} symbols, {} removed",
+                        result.analyzed_files, result.total_symbols, result.removed_files
+                    );
+                }
+                Err(e) => {
+                    // Keep the paths so the index does not silently stay stale
+                    eprintln!("  ERROR  Analysis error: {}, retrying", e);
+                    pending.extend(paths);
+                    last_change = Some(Instant::now());
+                }
+            }
+        }
     }
 
     Ok(())